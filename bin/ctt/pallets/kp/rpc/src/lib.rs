@@ -3,17 +3,21 @@
 pub use self::gen_client::Client as KpClient;
 use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
 use jsonrpc_derive::rpc;
-use kp::{CommoditySlashRecord, ModelDisputeRecord};
+use kp::{
+    AppFinancedUserExchangeData, AppFinancedUserExchangeDataV3, CommoditySlashRecord,
+    ExchangeStatus, LeaderBoardHistoryItem, ModelDisputeRecord,
+};
 use kp_runtime_api::KpApi as KpRuntimeApi;
 pub use kp_runtime_api::KpApi as KpRuntimeRpcApi;
 use primitives::{AuthAccountId, Balance, BlockNumber, PowerSize};
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_core::Bytes;
 use sp_runtime::{
     generic::BlockId,
     traits::{Block as BlockT, SaturatedConversion},
 };
+use std::convert::TryFrom;
 use std::sync::Arc;
 
 #[cfg(feature = "std")]
@@ -67,6 +71,37 @@ pub struct QueryLeaderBoardParams {
     block: u32,
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct QueryLeaderBoardHistoryParams {
+    app_id: u32,
+    // all boards of app if not set
+    model_id: Option<Bytes>,
+    start_block: Option<BlockNumber>,
+    end_block: Option<BlockNumber>,
+    // last item of previous page
+    cursor: Option<LeaderBoardHistoryItemRPC>,
+    limit: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct LeaderBoardHistoryItemRPC {
+    model_id: Bytes,
+    block: BlockNumber,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct LeaderBoardHistoryRPC {
+    items: Vec<LeaderBoardHistoryItemRPC>,
+    total: u32,
+    next_cursor: Option<LeaderBoardHistoryItemRPC>,
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(deny_unknown_fields)]
@@ -82,6 +117,7 @@ pub struct LeaderBoardItemRPC<AccountId> {
 pub struct LeaderBoardResultRPC<AccountId> {
     accounts: Vec<AccountId>,
     board: Vec<LeaderBoardItemRPC<AccountId>>,
+    seed: Bytes,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -133,8 +169,37 @@ pub struct AppIncomeExchangeDataParams {
 #[serde(deny_unknown_fields)]
 pub struct AppFinanceExchangeDataRPC {
     exchange_amount: u64,
-    status: u8, // 0: initial state, 1: reserved, 2: received cash and burned
+    // 0: initial state, 1: reserved, 2: received cash and burned,
+    // 3: compensated by finance member, 4: compensate failed
+    status: u8,
     pay_id: Bytes,
+    transitions: Vec<(u8, BlockNumber)>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct AppFinanceExchangeRecordsParams {
+    app_id: u32,
+    proposal_id: Bytes,
+    status: u8,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct AppIncomeExchangeRecordsParams {
+    app_id: u32,
+    cycle: BlockNumber,
+    status: u8,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct AppFinanceExchangeRecordRPC<AccountId> {
+    account: AccountId,
+    data: AppFinanceExchangeDataRPC,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -147,6 +212,9 @@ pub struct AppFinanceDataRPC {
     total_balance: u64,
     exchanged: u64,
     exchange_end_block: BlockNumber,
+    // none for rounds read from runtimes before KpApi version 2
+    confirm_end_block: Option<BlockNumber>,
+    compensate_end_block: Option<BlockNumber>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -166,6 +234,23 @@ pub struct ModelIncomeCurrentStageRPC {
     left: BlockNumber,
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct ModelCycleRewardParams {
+    app_id: u32,
+    model_id: Bytes,
+    cycle: BlockNumber,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct ModelCycleRewardRPC<AccountId> {
+    account: AccountId,
+    reward: u64,
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(deny_unknown_fields)]
@@ -183,14 +268,40 @@ pub struct AppCommentKeyParams {
     comment_id: Bytes,
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct LeaderBoardRewardParams<AccountId> {
+    app_id: u32,
+    model_id: Bytes,
+    block: u32,
+    account: AccountId,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct LeaderBoardRewardRPC {
+    reward: u64,
+    is_claimed: bool,
+    end_block: BlockNumber,
+    is_released: bool,
+}
+
 #[rpc]
 pub trait KpApi<BlockHash, AccountId, Balance, BlockNumber> {
     #[rpc(name = "kp_totalPower")]
     fn total_power(&self, at: Option<BlockHash>) -> Result<PowerSize>;
 
+    #[rpc(name = "kp_totalRawPower")]
+    fn total_raw_power(&self, at: Option<BlockHash>) -> Result<PowerSize>;
+
     #[rpc(name = "kp_accountPower")]
     fn account_power(&self, account: AccountId, at: Option<BlockHash>) -> Result<PowerSize>;
 
+    #[rpc(name = "kp_accountRawPower")]
+    fn account_raw_power(&self, account: AccountId, at: Option<BlockHash>) -> Result<PowerSize>;
+
     #[rpc(name = "kp_commodityPower")]
     fn commodity_power(
         &self,
@@ -219,6 +330,13 @@ pub trait KpApi<BlockHash, AccountId, Balance, BlockNumber> {
         at: Option<BlockHash>,
     ) -> Result<LeaderBoardResultRPC<AccountId>>;
 
+    #[rpc(name = "kp_leaderBoardHistory")]
+    fn leader_board_history(
+        &self,
+        query: QueryLeaderBoardHistoryParams,
+        at: Option<BlockHash>,
+    ) -> Result<LeaderBoardHistoryRPC>;
+
     #[rpc(name = "kp_stakeToVote")]
     fn stake_to_vote(
         &self,
@@ -247,6 +365,13 @@ pub trait KpApi<BlockHash, AccountId, Balance, BlockNumber> {
         at: Option<BlockHash>,
     ) -> Result<AppFinanceExchangeDataRPC>;
 
+    #[rpc(name = "kp_appFinanceExchangeRecords")]
+    fn app_finance_exchange_records(
+        &self,
+        params: AppFinanceExchangeRecordsParams,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<AppFinanceExchangeRecordRPC<AccountId>>>;
+
     #[rpc(name = "kp_appIncomeRecord")]
     fn app_income_record(
         &self,
@@ -268,12 +393,26 @@ pub trait KpApi<BlockHash, AccountId, Balance, BlockNumber> {
         at: Option<BlockHash>,
     ) -> Result<AppFinanceExchangeDataRPC>;
 
+    #[rpc(name = "kp_appIncomeExchangeRecords")]
+    fn app_income_exchange_records(
+        &self,
+        params: AppIncomeExchangeRecordsParams,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<AppFinanceExchangeRecordRPC<AccountId>>>;
+
     #[rpc(name = "kp_modelIncomeCurrentStage")]
     fn model_income_current_stage(
         &self,
         at: Option<BlockHash>,
     ) -> Result<ModelIncomeCurrentStageRPC>;
 
+    #[rpc(name = "kp_modelCycleRewards")]
+    fn model_cycle_rewards(
+        &self,
+        params: ModelCycleRewardParams,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<ModelCycleRewardRPC<AccountId>>>;
+
     #[rpc(name = "kp_isTechMemberSign")]
     fn is_tech_member_sign(
         &self,
@@ -314,6 +453,13 @@ pub trait KpApi<BlockHash, AccountId, Balance, BlockNumber> {
 
     #[rpc(name = "kp_powerRatio")]
     fn power_ratio(&self, account: AccountId, at: Option<BlockHash>) -> Result<u64>;
+
+    #[rpc(name = "kp_leaderBoardRewardStatus")]
+    fn leader_board_reward_status(
+        &self,
+        params: LeaderBoardRewardParams<AccountId>,
+        at: Option<BlockHash>,
+    ) -> Result<LeaderBoardRewardRPC>;
 }
 
 /// A struct that implements the `KpApi`.
@@ -357,6 +503,48 @@ fn convert_balance(source: Balance) -> u64 {
     reduce.saturated_into()
 }
 
+fn convert_exchange_data(
+    source: AppFinancedUserExchangeData<Balance, BlockNumber>,
+) -> AppFinanceExchangeDataRPC {
+    AppFinanceExchangeDataRPC {
+        exchange_amount: convert_balance(source.exchange_amount),
+        status: source.status.into(),
+        pay_id: source.pay_id.into(),
+        transitions: source
+            .transitions
+            .into_iter()
+            .map(|(status, block)| (status.into(), block))
+            .collect(),
+    }
+}
+
+// runtimes before version 2 did not keep transitions
+fn convert_legacy_exchange_data(
+    source: AppFinancedUserExchangeDataV3<Balance>,
+) -> AppFinanceExchangeDataRPC {
+    convert_exchange_data(AppFinancedUserExchangeData {
+        exchange_amount: source.exchange_amount,
+        status: source.status,
+        pay_id: source.pay_id,
+        transitions: vec![],
+    })
+}
+
+// runtimes before KpApi version 2 only answer the version 1 signature of changed methods
+// and know nothing of methods added since
+fn has_api_v2<Block: BlockT, Api: ApiExt<Block>>(api: &Api, at: &BlockId<Block>) -> bool {
+    api.has_api_with::<dyn KpRuntimeApi<Block, AuthAccountId, Balance, BlockNumber, Error = ()>, _>(
+        at,
+        |v| v >= 2,
+    )
+    .unwrap_or_default()
+}
+
+fn convert_exchange_status(source: u8) -> Result<ExchangeStatus> {
+    ExchangeStatus::try_from(source)
+        .map_err(|_| RpcError::invalid_params(format!("unknown exchange status {}", source)))
+}
+
 impl<C, Block> KpApi<<Block as BlockT>::Hash, AuthAccountId, Balance, BlockNumber> for Kp<C, Block>
 where
     Block: BlockT,
@@ -397,6 +585,38 @@ where
         })
     }
 
+    fn total_raw_power(&self, at: Option<<Block as BlockT>::Hash>) -> Result<PowerSize> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(||
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash));
+
+        let runtime_api_result = api.total_raw_power(&at);
+        runtime_api_result.map_err(|e| RpcError {
+            code: ErrorCode::ServerError(9876), // No real reason for this value
+            message: "Something wrong".into(),
+            data: Some(format!("{:?}", e).into()),
+        })
+    }
+
+    fn account_raw_power(
+        &self,
+        account: AuthAccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<PowerSize> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(||
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash));
+
+        let runtime_api_result = api.account_raw_power(&at, account);
+        runtime_api_result.map_err(|e| RpcError {
+            code: ErrorCode::ServerError(9876), // No real reason for this value
+            message: "Something wrong".into(),
+            data: Some(format!("{:?}", e).into()),
+        })
+    }
+
     fn power_ratio(
         &self,
         account: AuthAccountId,
@@ -559,14 +779,24 @@ where
             block,
         } = query;
 
-        let runtime_api_result = api.leader_board_result(&at, block, app_id, model_id.to_vec());
+        let runtime_api_result = api
+            .leader_board_result(&at, block, app_id, model_id.to_vec())
+            .and_then(|v| {
+                if has_api_v2(&*api, &at) {
+                    api.leader_board_seed(&at, block, app_id, model_id.to_vec())
+                        .map(|seed| (v, seed.to_vec()))
+                } else {
+                    Ok((v, vec![]))
+                }
+            });
 
         // convert result
         match runtime_api_result {
-            Ok(v) => {
+            Ok((v, seed)) => {
                 let mut converted: LeaderBoardResultRPC<AuthAccountId> = LeaderBoardResultRPC {
                     accounts: v.accounts,
                     board: vec![],
+                    seed: seed.into(),
                 };
 
                 for item in v.board {
@@ -588,6 +818,65 @@ where
         }
     }
 
+    fn leader_board_history(
+        &self,
+        query: QueryLeaderBoardHistoryParams,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<LeaderBoardHistoryRPC> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(||
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash));
+
+        let QueryLeaderBoardHistoryParams {
+            app_id,
+            model_id,
+            start_block,
+            end_block,
+            cursor,
+            limit,
+        } = query;
+
+        let runtime_api_result = api.leader_board_history(
+            &at,
+            app_id,
+            model_id.map(|id| id.to_vec()),
+            start_block,
+            end_block,
+            cursor.map(|item| LeaderBoardHistoryItem {
+                model_id: item.model_id.to_vec(),
+                block: item.block,
+            }),
+            limit.unwrap_or_default(),
+        );
+
+        // convert result
+        match runtime_api_result {
+            Ok(v) => Ok(LeaderBoardHistoryRPC {
+                items: v
+                    .items
+                    .into_iter()
+                    .map(|item| LeaderBoardHistoryItemRPC {
+                        model_id: item.model_id.into(),
+                        block: item.block,
+                    })
+                    .collect(),
+                total: v.total,
+                next_cursor: v.next_cursor.map(|item| LeaderBoardHistoryItemRPC {
+                    model_id: item.model_id.into(),
+                    block: item.block,
+                }),
+            }),
+            Err(e) => {
+                Err(RpcError {
+                    code: ErrorCode::ServerError(9876), // No real reason for this value
+                    message: "Something wrong".into(),
+                    data: Some(format!("{:?}", e).into()),
+                })
+            }
+        }
+    }
+
     fn stake_to_vote(
         &self,
         params: StakeToVoteParams<AuthAccountId, u64>,
@@ -633,25 +922,37 @@ where
             proposal_id,
         } = query;
 
-        let runtime_api_result = api.app_finance_record(&at, app_id, proposal_id.to_vec());
-        // convert result
-        match runtime_api_result {
-            Ok(v) => Ok(AppFinanceDataRPC {
-                amount: convert_balance(v.amount),
-                exchange: convert_balance(v.exchange),
-                block: v.block,
-                total_balance: convert_balance(v.total_balance),
-                exchanged: convert_balance(v.exchanged),
-                exchange_end_block: v.exchange_end_block,
-            }),
-            Err(e) => {
-                Err(RpcError {
-                    code: ErrorCode::ServerError(9876), // No real reason for this value
-                    message: "Something wrong".into(),
-                    data: Some(format!("{:?}", e).into()),
+        let runtime_api_result = if has_api_v2(&*api, &at) {
+            api.app_finance_record(&at, app_id, proposal_id.to_vec())
+                .map(|v| AppFinanceDataRPC {
+                    amount: convert_balance(v.amount),
+                    exchange: convert_balance(v.exchange),
+                    block: v.block,
+                    total_balance: convert_balance(v.total_balance),
+                    exchanged: convert_balance(v.exchanged),
+                    exchange_end_block: v.exchange_end_block,
+                    confirm_end_block: Some(v.confirm_end_block),
+                    compensate_end_block: Some(v.compensate_end_block),
                 })
-            }
-        }
+        } else {
+            #[allow(deprecated)] // old app_finance_record
+            api.app_finance_record_before_version_2(&at, app_id, proposal_id.to_vec())
+                .map(|v| AppFinanceDataRPC {
+                    amount: convert_balance(v.amount),
+                    exchange: convert_balance(v.exchange),
+                    block: v.block,
+                    total_balance: convert_balance(v.total_balance),
+                    exchanged: convert_balance(v.exchanged),
+                    exchange_end_block: v.exchange_end_block,
+                    confirm_end_block: None,
+                    compensate_end_block: None,
+                })
+        };
+        runtime_api_result.map_err(|e| RpcError {
+            code: ErrorCode::ServerError(9876), // No real reason for this value
+            message: "Something wrong".into(),
+            data: Some(format!("{:?}", e).into()),
+        })
     }
 
     fn app_finance_exchange_accounts(
@@ -694,15 +995,54 @@ where
             account,
         } = query;
 
+        let runtime_api_result = if has_api_v2(&*api, &at) {
+            api.app_finance_exchange_data(&at, app_id, proposal_id.to_vec(), account)
+                .map(convert_exchange_data)
+        } else {
+            #[allow(deprecated)] // old app_finance_exchange_data
+            api.app_finance_exchange_data_before_version_2(
+                &at,
+                app_id,
+                proposal_id.to_vec(),
+                account,
+            )
+            .map(convert_legacy_exchange_data)
+        };
+        runtime_api_result.map_err(|e| RpcError {
+            code: ErrorCode::ServerError(9876), // No real reason for this value
+            message: "Something wrong".into(),
+            data: Some(format!("{:?}", e).into()),
+        })
+    }
+
+    fn app_finance_exchange_records(
+        &self,
+        query: AppFinanceExchangeRecordsParams,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<AppFinanceExchangeRecordRPC<AuthAccountId>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(||
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash));
+
+        let AppFinanceExchangeRecordsParams {
+            app_id,
+            proposal_id,
+            status,
+        } = query;
+        let status = convert_exchange_status(status)?;
+
         let runtime_api_result =
-            api.app_finance_exchange_data(&at, app_id, proposal_id.to_vec(), account);
+            api.app_finance_exchange_records(&at, app_id, proposal_id.to_vec(), status);
         // convert result
         match runtime_api_result {
-            Ok(v) => Ok(AppFinanceExchangeDataRPC {
-                exchange_amount: convert_balance(v.exchange_amount),
-                status: v.status,
-                pay_id: v.pay_id.into(),
-            }),
+            Ok(v) => Ok(v
+                .into_iter()
+                .map(|(account, data)| AppFinanceExchangeRecordRPC {
+                    account,
+                    data: convert_exchange_data(data),
+                })
+                .collect()),
             Err(e) => {
                 Err(RpcError {
                     code: ErrorCode::ServerError(9876), // No real reason for this value
@@ -779,14 +1119,48 @@ where
             account,
         } = query;
 
-        let runtime_api_result = api.app_income_exchange_data(&at, app_id, cycle, account);
+        let runtime_api_result = if has_api_v2(&*api, &at) {
+            api.app_income_exchange_data(&at, app_id, cycle, account)
+                .map(convert_exchange_data)
+        } else {
+            #[allow(deprecated)] // old app_income_exchange_data
+            api.app_income_exchange_data_before_version_2(&at, app_id, cycle, account)
+                .map(convert_legacy_exchange_data)
+        };
+        runtime_api_result.map_err(|e| RpcError {
+            code: ErrorCode::ServerError(9876), // No real reason for this value
+            message: "Something wrong".into(),
+            data: Some(format!("{:?}", e).into()),
+        })
+    }
+
+    fn app_income_exchange_records(
+        &self,
+        query: AppIncomeExchangeRecordsParams,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<AppFinanceExchangeRecordRPC<AuthAccountId>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(||
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash));
+
+        let AppIncomeExchangeRecordsParams {
+            app_id,
+            cycle,
+            status,
+        } = query;
+        let status = convert_exchange_status(status)?;
+
+        let runtime_api_result = api.app_income_exchange_records(&at, app_id, cycle, status);
         // convert result
         match runtime_api_result {
-            Ok(v) => Ok(AppFinanceExchangeDataRPC {
-                exchange_amount: convert_balance(v.exchange_amount),
-                status: v.status,
-                pay_id: v.pay_id.into(),
-            }),
+            Ok(v) => Ok(v
+                .into_iter()
+                .map(|(account, data)| AppFinanceExchangeRecordRPC {
+                    account,
+                    data: convert_exchange_data(data),
+                })
+                .collect()),
             Err(e) => {
                 Err(RpcError {
                     code: ErrorCode::ServerError(9876), // No real reason for this value
@@ -822,6 +1196,42 @@ where
         }
     }
 
+    fn model_cycle_rewards(
+        &self,
+        query: ModelCycleRewardParams,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<ModelCycleRewardRPC<AuthAccountId>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(||
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash));
+
+        let ModelCycleRewardParams {
+            app_id,
+            model_id,
+            cycle,
+        } = query;
+
+        let runtime_api_result = api.model_cycle_rewards(&at, app_id, model_id.to_vec(), cycle);
+        // convert result
+        match runtime_api_result {
+            Ok(v) => Ok(v
+                .into_iter()
+                .map(|item| ModelCycleRewardRPC {
+                    account: item.account,
+                    reward: convert_balance(item.reward),
+                })
+                .collect()),
+            Err(e) => {
+                Err(RpcError {
+                    code: ErrorCode::ServerError(9876), // No real reason for this value
+                    message: "Something wrong".into(),
+                    data: Some(format!("{:?}", e).into()),
+                })
+            }
+        }
+    }
+
     fn is_tech_member_sign(
         &self,
         query: TechMemberSignParams,
@@ -909,4 +1319,41 @@ where
             data: Some(format!("{:?}", e).into()),
         })
     }
+
+    fn leader_board_reward_status(
+        &self,
+        query: LeaderBoardRewardParams<AuthAccountId>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<LeaderBoardRewardRPC> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(||
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash));
+
+        let LeaderBoardRewardParams {
+            app_id,
+            model_id,
+            block,
+            account,
+        } = query;
+
+        let runtime_api_result =
+            api.leader_board_reward_status(&at, block, app_id, model_id.to_vec(), account);
+        // convert result
+        match runtime_api_result {
+            Ok(v) => Ok(LeaderBoardRewardRPC {
+                reward: convert_balance(v.reward),
+                is_claimed: v.is_claimed,
+                end_block: v.end_block,
+                is_released: v.is_released,
+            }),
+            Err(e) => {
+                Err(RpcError {
+                    code: ErrorCode::ServerError(9876), // No real reason for this value
+                    message: "Something wrong".into(),
+                    data: Some(format!("{:?}", e).into()),
+                })
+            }
+        }
+    }
 }
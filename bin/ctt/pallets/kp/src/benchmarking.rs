@@ -0,0 +1,1281 @@
+//! Kp pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::UnfilteredDispatchable;
+use frame_system::RawOrigin;
+use sp_core::crypto::key_types;
+use sp_runtime::traits::Bounded;
+
+use crate::Module as Kp;
+
+const SEED: u32 = 0;
+const APP_ID: u32 = 100;
+const COMMODITY_TYPE: u32 = 1;
+
+fn new_signer() -> sr25519::Public {
+    sp_io::crypto::sr25519_generate(key_types::ACCOUNT, None)
+}
+
+fn sign(signer: &sr25519::Public, msg: &[u8]) -> AuthSignature {
+    sp_io::crypto::sr25519_sign(key_types::ACCOUNT, signer, msg)
+        .expect("signer was generated in keystore; qed")
+        .into()
+}
+
+// envelope with the current nonce of the primary `signer`, returns it with the message to sign
+fn enveloped<T: Trait>(
+    signer: &sr25519::Public,
+    tag: &[u8],
+    payload: &[u8],
+) -> (SignEnvelope<T::BlockNumber>, Vec<u8>) {
+    let envelope = SignEnvelope {
+        nonce: T::Membership::sign_nonce(&AuthAccountId::from(*signer)),
+        expiry: T::BlockNumber::max_value(),
+    };
+    let genesis_hash = frame_system::Module::<T>::block_hash(T::BlockNumber::zero());
+    let message = envelope.message(tag, &genesis_hash, payload);
+    (envelope, message)
+}
+
+fn account_of<T: Trait>(signer: &sr25519::Public) -> T::AccountId {
+    Kp::<T>::convert_account(&AuthAccountId::from(*signer))
+}
+
+fn funded<T: Trait>(who: &T::AccountId) {
+    T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 2u32.into());
+}
+
+fn fund_treasury<T: Trait>(id: ModuleId) {
+    funded::<T>(&id.into_account());
+}
+
+fn dollars<T: Trait>(n: u32) -> BalanceOf<T> {
+    let unit: BalanceOf<T> = 1_000_000_000_000u128.saturated_into();
+    unit * n.into()
+}
+
+// register benchmark app, returns (admin, auth server)
+fn setup_app<T: Trait>() -> (sr25519::Public, sr25519::Public) {
+    let admin = new_signer();
+    let auth = new_signer();
+
+    T::Membership::config_app_setting(APP_ID, 100, b"bench".to_vec(), 0u32.into());
+    T::Membership::config_app_admin(&account_of::<T>(&admin), APP_ID);
+    T::Membership::config_app_key(&account_of::<T>(&auth), APP_ID);
+    funded::<T>(&account_of::<T>(&admin));
+
+    (admin, auth)
+}
+
+// `auth` and `s - 1` more app keys of benchmark app signing `msg`
+fn app_key_signs<T: Trait>(
+    auth: &sr25519::Public,
+    s: u32,
+    msg: &[u8],
+) -> Vec<(AuthAccountId, AuthSignature)> {
+    let mut signs = vec![(AuthAccountId::from(*auth), sign(auth, msg))];
+    for _ in 1..s {
+        let key = new_signer();
+        T::Membership::config_app_key(&account_of::<T>(&key), APP_ID);
+        signs.push((key.into(), sign(&key, msg)));
+    }
+    signs
+}
+
+fn setup_model<T: Trait>(creator: &sr25519::Public, model_id: Vec<u8>) -> Vec<u8> {
+    let key = T::Hashing::hash_of(&(APP_ID, &model_id));
+    let owner = AuthAccountId::from(*creator);
+
+    T::Membership::set_model_creator(&key, &Kp::<T>::convert_account(&owner), false);
+    <KPModelDataByIdHash<T>>::insert(
+        &key,
+        KPModelData {
+            app_id: APP_ID,
+            model_id: model_id.clone(),
+            commodity_type: COMMODITY_TYPE,
+            owner,
+            ..Default::default()
+        },
+    );
+
+    model_id
+}
+
+// dispute case of a model owned by `owner`, all stages end at current block
+fn model_dispute_case<T: Trait>(
+    model_id: &Vec<u8>,
+    reporter: &T::AccountId,
+    owner: &T::AccountId,
+) -> ModelDisputeCase<T::AccountId, BalanceOf<T>, T::BlockNumber, T::Hash> {
+    let block = frame_system::Module::<T>::block_number();
+    let bond = T::ModelDisputeBond::get();
+    funded::<T>(reporter);
+    T::Currency::reserve(reporter, bond).expect("reporter was funded; qed");
+
+    ModelDisputeCase {
+        app_id: APP_ID,
+        model_id: model_id.clone(),
+        comment_id: b"comment".to_vec(),
+        dispute_type: ModelDisputeType::Serious,
+        reporter: reporter.clone(),
+        owner: owner.clone(),
+        bond,
+        evidence_end: block,
+        vote_end: block,
+        appeal_end: block,
+        ..Default::default()
+    }
+}
+
+fn create_publish_doc<T: Trait>(
+    user: &sr25519::Public,
+    auth: &sr25519::Public,
+    model_id: &Vec<u8>,
+) -> Result<(), &'static str> {
+    let client_params = ClientParamsCreatePublishDoc {
+        app_id: APP_ID,
+        document_id: b"publish_doc".to_vec(),
+        model_id: model_id.clone(),
+        product_id: b"product".to_vec(),
+        content_hash: T::Hash::default(),
+        para_issue_rate: 50,
+        self_issue_rate: 50,
+    };
+    let (envelope, encode) = enveloped::<T>(
+        user,
+        b"kp/create_product_publish_document",
+        &client_params.encode(),
+    );
+
+    Kp::<T>::create_product_publish_document(
+        RawOrigin::Signed(whitelisted_caller()).into(),
+        client_params,
+        envelope,
+        (*user).into(),
+        sign(user, &encode),
+        vec![((*auth).into(), sign(auth, &encode))],
+    )?;
+    Ok(())
+}
+
+fn identify_params<T: Trait>(index: u32) -> ClientParamsCreateIdentifyDoc<T::Hash> {
+    ClientParamsCreateIdentifyDoc {
+        app_id: APP_ID,
+        document_id: (b"identify_doc", index).encode(),
+        product_id: b"product".to_vec(),
+        content_hash: T::Hash::default(),
+        goods_price: 100,
+        ident_rate: 50,
+        ident_consistence: 50,
+        seller_consistence: 50,
+        cart_id: (b"cart", index).encode(),
+    }
+}
+
+fn create_identify_doc<T: Trait>(
+    user: &sr25519::Public,
+    auth: &sr25519::Public,
+    index: u32,
+) -> Result<(), &'static str> {
+    let client_params = identify_params::<T>(index);
+    let (envelope, encode) = enveloped::<T>(
+        user,
+        b"kp/create_product_identify_document",
+        &client_params.encode(),
+    );
+
+    Kp::<T>::create_product_identify_document(
+        RawOrigin::Signed(whitelisted_caller()).into(),
+        client_params,
+        envelope,
+        (*user).into(),
+        sign(user, &encode),
+        vec![((*auth).into(), sign(auth, &encode))],
+    )?;
+    Ok(())
+}
+
+// fill both model and app leader boards with `b` low power items
+fn fill_leader_boards<T: Trait>(model_id: &Vec<u8>, owner: &T::AccountId, b: u32) {
+    for board_model_id in &[model_id.clone(), vec![]] {
+        let key = T::Hashing::hash_of(&(APP_ID, board_model_id));
+        let mut board: Vec<CommodityLeaderBoardData<T>> = vec![];
+        for i in 0..b {
+            let cart_id = (b"board_cart", i).encode();
+            <LeaderBoardCommoditySet<T>>::insert(&key, &cart_id, ());
+            board.push(CommodityLeaderBoardData {
+                cart_id_hash: T::Hashing::hash_of(&cart_id),
+                cart_id,
+                power: (b - i) as PowerSize,
+                owner: owner.clone(),
+            });
+        }
+        <AppModelCommodityLeaderBoards<T>>::insert(&key, board);
+    }
+}
+
+fn fill_comment_pool<T: Trait>(document_id: &Vec<u8>, p: u32) {
+    let key = T::Hashing::hash_of(&(APP_ID, document_id));
+    let pool: Vec<CommentWeightData<T>> = (0..p)
+        .map(|i| CommentWeightData {
+            account: account("commenter", i, SEED),
+            position: i as u64,
+            cash_cost: 100,
+        })
+        .collect();
+
+    <DocumentCommentsAccountPool<T>>::insert(&key, pool);
+    <KPDocumentDataByIdHash<T>>::mutate(&key, |doc| {
+        doc.comment_count = p as PowerSize;
+        doc.comment_total_fee = 100 * p as PowerSize;
+    });
+    <CommentMaxInfoPerDocMap>::mutate(APP_ID, |record| {
+        record.max_count = max(record.max_count, p as PowerSize);
+        record.max_fee = max(record.max_fee, 100 * p as PowerSize);
+    });
+}
+
+fn income_stage_block<T: Trait>(stage: ModelIncomeStage) -> T::BlockNumber {
+    let params = Kp::<T>::active_economic_params();
+    let cycle = params.model_income_cycle_period;
+    let collecting = params.model_income_collecting_period;
+    let rewarding = params.model_income_rewarding_period;
+
+    match stage {
+        ModelIncomeStage::COLLECTING => cycle,
+        ModelIncomeStage::REWARDING => cycle + collecting,
+        ModelIncomeStage::CONFIRMING => cycle + collecting + rewarding,
+        ModelIncomeStage::COMPENSATING => {
+            cycle + collecting + rewarding + rewarding / 2u32.into()
+        }
+        ModelIncomeStage::NORMAL => 0u32.into(),
+    }
+}
+
+fn setup_finance_member<T: Trait>() -> T::AccountId {
+    let member: T::AccountId = account("finance", 0, SEED);
+    funded::<T>(&member);
+    T::Membership::add_finance_member(&member, dollars::<T>(100));
+    member
+}
+
+fn app_income_redeem_request_params<T: Trait>(
+    account: &T::AccountId,
+) -> AppIncomeRedeemParams<T::AccountId, BalanceOf<T>, T::BlockNumber> {
+    AppIncomeRedeemParams {
+        account: account.clone(),
+        app_id: APP_ID,
+        cycle: 1u32.into(),
+        exchange_amount: dollars::<T>(1),
+    }
+}
+
+// prepare app income cycle and perform one redeem request
+fn setup_app_income_redeem<T: Trait>(
+    do_request: bool,
+) -> Result<(T::AccountId, sr25519::Public, sr25519::Public, T::AccountId), &'static str> {
+    let (admin, _) = setup_app::<T>();
+    let user = new_signer();
+    let account = account_of::<T>(&user);
+    funded::<T>(&account);
+    let finance_member = setup_finance_member::<T>();
+
+    <AppCycleIncome<T>>::mutate(T::BlockNumber::from(1u32), APP_ID, |record| {
+        record.income = 100_000;
+        record.app_id = APP_ID;
+    });
+    frame_system::Module::<T>::set_block_number(income_stage_block::<T>(ModelIncomeStage::REWARDING));
+
+    if do_request {
+        let params = app_income_redeem_request_params::<T>(&account);
+        let (envelope, encode) =
+            enveloped::<T>(&user, b"kp/app_income_redeem_request", &params.encode());
+        Kp::<T>::app_income_redeem_request(
+            RawOrigin::Signed(account.clone()).into(),
+            params,
+            envelope,
+            user.into(),
+            sign(&user, &encode),
+            admin.into(),
+            sign(&admin, &encode),
+        )?;
+    }
+
+    Ok((account, user, admin, finance_member))
+}
+
+const PROPOSAL_ID: &[u8] = b"proposal";
+
+fn app_financed_user_exchange_params<T: Trait>(
+    account: &T::AccountId,
+) -> AppFinancedUserExchangeParams<T::AccountId, BalanceOf<T>> {
+    AppFinancedUserExchangeParams {
+        account: account.clone(),
+        app_id: APP_ID,
+        proposal_id: PROPOSAL_ID.to_vec(),
+        exchange_amount: dollars::<T>(1),
+    }
+}
+
+// prepare an app financed record and perform one user exchange request
+fn setup_app_financed_exchange<T: Trait>(
+    do_request: bool,
+) -> Result<(T::AccountId, sr25519::Public, sr25519::Public, T::AccountId), &'static str> {
+    let (admin, _) = setup_app::<T>();
+    let user = new_signer();
+    let account = account_of::<T>(&user);
+    funded::<T>(&account);
+    let finance_member = setup_finance_member::<T>();
+
+    frame_system::Module::<T>::set_block_number(1u32.into());
+    let current_block = frame_system::Module::<T>::block_number();
+    let exchange_period = Kp::<T>::active_economic_params().app_finance_exchange_period;
+    let exchange_end_block = current_block + exchange_period;
+    let key = T::Hashing::hash_of(&(APP_ID, PROPOSAL_ID));
+    <AppFinancedRecord<T>>::insert(
+        &key,
+        AppFinancedData {
+            app_id: APP_ID,
+            proposal_id: PROPOSAL_ID.to_vec(),
+            amount: dollars::<T>(1000),
+            exchange: dollars::<T>(1000),
+            block: current_block,
+            total_balance: dollars::<T>(1000),
+            exchanged: 0u32.into(),
+            exchange_end_block,
+            confirm_end_block: exchange_end_block + exchange_period / 2u32.into(),
+            compensate_end_block: exchange_end_block + exchange_period,
+        },
+    );
+
+    if do_request {
+        let params = app_financed_user_exchange_params::<T>(&account);
+        let (envelope, encode) = enveloped::<T>(
+            &user,
+            b"kp/app_financed_user_exchange_request",
+            &params.encode(),
+        );
+        Kp::<T>::app_financed_user_exchange_request(
+            RawOrigin::Signed(account.clone()).into(),
+            params,
+            envelope,
+            user.into(),
+            sign(&user, &encode),
+            admin.into(),
+            sign(&admin, &encode),
+        )?;
+    }
+
+    Ok((account, user, admin, finance_member))
+}
+
+// weights of the runtime, every group sums up to 100
+fn power_weight_profile() -> PowerWeightProfile {
+    PowerWeightProfile {
+        top: (15, 30, 40, 10, 5),
+        document: (40, 30, 30),
+        comment: (65, 20, 10, 5),
+        publish: (20, 35, 45),
+        identify: (20, 35, 45),
+        product_try: (20, 35, 45),
+        choose: (60, 40),
+        model: (40, 60),
+        cm: (10, 40, 20, 30),
+        comment_cm: (65, 20, 10, 5),
+        comment_power: 40,
+    }
+}
+
+benchmarks! {
+    _ { }
+
+    create_model {
+        let s in 1 .. AUTH_SIGNS_MAX as u32;
+
+        let caller: T::AccountId = whitelisted_caller();
+        let (_, auth) = setup_app::<T>();
+        let user = new_signer();
+        funded::<T>(&account_of::<T>(&user));
+        CommodityTypeMap::insert(COMMODITY_TYPE, b"bench".to_vec());
+
+        let client_params = ClientParamsCreateModel {
+            app_id: APP_ID,
+            expert_id: b"expert".to_vec(),
+            commodity_name: b"commodity".to_vec(),
+            commodity_type: COMMODITY_TYPE,
+            content_hash: T::Hash::default(),
+        };
+        let auth_params = AuthParamsCreateModel {
+            model_id: b"model".to_vec(),
+        };
+        let (envelope, client_encode) = enveloped::<T>(&user, b"kp/create_model", &client_params.encode());
+        let (_, auth_encode) = enveloped::<T>(&user, b"kp/create_model", &auth_params.encode());
+        let user_sign = sign(&user, &client_encode);
+        let auth_signs = app_key_signs::<T>(&auth, s, &auth_encode);
+    }: _(RawOrigin::Signed(caller), client_params, auth_params, envelope, user.into(), user_sign, auth_signs)
+    verify {
+        assert_eq!(AppModelCount::get(APP_ID), 1);
+    }
+
+    model_owner_release {
+        let caller: T::AccountId = whitelisted_caller();
+        let (admin, _) = setup_app::<T>();
+        let user = new_signer();
+        let model_id = setup_model::<T>(&user, b"model".to_vec());
+
+        let params = ModelKeyParams {
+            app_id: APP_ID,
+            model_id: model_id.clone(),
+        };
+        let (envelope, encode) = enveloped::<T>(&user, b"kp/model_owner_release", &params.encode());
+        let user_sign = sign(&user, &encode);
+        let auth_sign = sign(&admin, &encode);
+    }: _(RawOrigin::Signed(caller), params, envelope, user.into(), user_sign, admin.into(), auth_sign)
+    verify {
+        assert!(T::Membership::is_model_creator(&account_of::<T>(&admin), APP_ID, &model_id));
+    }
+
+    add_model_deposit {
+        let _ = setup_app::<T>();
+        let user = new_signer();
+        let creator = account_of::<T>(&user);
+        funded::<T>(&creator);
+        let model_id = setup_model::<T>(&user, b"model".to_vec());
+        let amount = T::ModelCreateDeposit::get().max(dollars::<T>(1));
+
+        // deposit recovered path, model leaves pre-black list
+        let end_block = frame_system::Module::<T>::block_number() + T::ModelDisputeDelayTime::get();
+        <ModelPreBlackList<T>>::put(vec![(APP_ID, model_id.clone(), creator.clone(), end_block)]);
+    }: _(RawOrigin::Signed(creator), APP_ID, model_id.clone(), amount)
+    verify {
+        assert_eq!(Kp::<T>::model_deposit(APP_ID, model_id), amount);
+        assert!(<ModelPreBlackList<T>>::get().is_empty());
+    }
+
+    retire_model {
+        let _ = setup_app::<T>();
+        let user = new_signer();
+        let creator = account_of::<T>(&user);
+        let model_id = setup_model::<T>(&user, b"model".to_vec());
+    }: _(RawOrigin::Signed(creator), APP_ID, model_id.clone())
+    verify {
+        let key = T::Hashing::hash_of(&(APP_ID, &model_id));
+        assert!(<ModelRetireRecords<T>>::contains_key(&key));
+    }
+
+    create_product_publish_document {
+        let s in 1 .. AUTH_SIGNS_MAX as u32;
+
+        let caller: T::AccountId = whitelisted_caller();
+        let (_, auth) = setup_app::<T>();
+        let user = new_signer();
+        let model_id = setup_model::<T>(&user, b"model".to_vec());
+
+        let client_params = ClientParamsCreatePublishDoc {
+            app_id: APP_ID,
+            document_id: b"publish_doc".to_vec(),
+            model_id,
+            product_id: b"product".to_vec(),
+            content_hash: T::Hash::default(),
+            para_issue_rate: 50,
+            self_issue_rate: 50,
+        };
+        let (envelope, encode) = enveloped::<T>(&user, b"kp/create_product_publish_document", &client_params.encode());
+        let user_sign = sign(&user, &encode);
+        let auth_signs = app_key_signs::<T>(&auth, s, &encode);
+    }: _(RawOrigin::Signed(caller), client_params, envelope, user.into(), user_sign, auth_signs)
+    verify {
+        assert!(Kp::<T>::kp_document_power(APP_ID, b"publish_doc".to_vec()).is_exist);
+    }
+
+    create_product_identify_document {
+        let b in 1 .. T::AppLeaderBoardMaxPos::get();
+        let s in 1 .. AUTH_SIGNS_MAX as u32;
+
+        let caller: T::AccountId = whitelisted_caller();
+        let (_, auth) = setup_app::<T>();
+        let user = new_signer();
+        let model_id = setup_model::<T>(&user, b"model".to_vec());
+        create_publish_doc::<T>(&user, &auth, &model_id)?;
+        fill_leader_boards::<T>(&model_id, &account_of::<T>(&user), b);
+
+        let client_params = identify_params::<T>(0);
+        let document_id = client_params.document_id.clone();
+        let (envelope, encode) = enveloped::<T>(&user, b"kp/create_product_identify_document", &client_params.encode());
+        let user_sign = sign(&user, &encode);
+        let auth_signs = app_key_signs::<T>(&auth, s, &encode);
+    }: _(RawOrigin::Signed(caller), client_params, envelope, user.into(), user_sign, auth_signs)
+    verify {
+        assert!(Kp::<T>::kp_document_power(APP_ID, document_id).is_exist);
+    }
+
+    create_product_try_document {
+        let b in 1 .. T::AppLeaderBoardMaxPos::get();
+        let s in 1 .. AUTH_SIGNS_MAX as u32;
+
+        let caller: T::AccountId = whitelisted_caller();
+        let (_, auth) = setup_app::<T>();
+        let user = new_signer();
+        let model_id = setup_model::<T>(&user, b"model".to_vec());
+        create_publish_doc::<T>(&user, &auth, &model_id)?;
+        fill_leader_boards::<T>(&model_id, &account_of::<T>(&user), b);
+
+        let client_params = ClientParamsCreateTryDoc {
+            app_id: APP_ID,
+            document_id: b"try_doc".to_vec(),
+            product_id: b"product".to_vec(),
+            content_hash: T::Hash::default(),
+            goods_price: 100,
+            offset_rate: 50,
+            true_rate: 50,
+            seller_consistence: 50,
+            cart_id: b"cart".to_vec(),
+        };
+        let (envelope, encode) = enveloped::<T>(&user, b"kp/create_product_try_document", &client_params.encode());
+        let user_sign = sign(&user, &encode);
+        let auth_signs = app_key_signs::<T>(&auth, s, &encode);
+    }: _(RawOrigin::Signed(caller), client_params, envelope, user.into(), user_sign, auth_signs)
+    verify {
+        assert!(Kp::<T>::kp_document_power(APP_ID, b"try_doc".to_vec()).is_exist);
+    }
+
+    create_product_choose_document {
+        let s in 1 .. AUTH_SIGNS_MAX as u32;
+
+        let caller: T::AccountId = whitelisted_caller();
+        let (_, auth) = setup_app::<T>();
+        let user = new_signer();
+        let model_id = setup_model::<T>(&user, b"model".to_vec());
+
+        let client_params = ClientParamsCreateChooseDoc {
+            app_id: APP_ID,
+            document_id: b"choose_doc".to_vec(),
+            model_id,
+            product_id: b"product".to_vec(),
+            content_hash: T::Hash::default(),
+            sell_count: 100,
+            try_count: 100,
+        };
+        let (envelope, encode) = enveloped::<T>(&user, b"kp/create_product_choose_document", &client_params.encode());
+        let user_sign = sign(&user, &encode);
+        let auth_signs = app_key_signs::<T>(&auth, s, &encode);
+    }: _(RawOrigin::Signed(caller), client_params, envelope, user.into(), user_sign, auth_signs)
+    verify {
+        assert!(Kp::<T>::kp_misc_document_power(APP_ID, b"choose_doc".to_vec()).is_exist);
+    }
+
+    create_model_create_document {
+        let s in 1 .. AUTH_SIGNS_MAX as u32;
+
+        let caller: T::AccountId = whitelisted_caller();
+        let (_, auth) = setup_app::<T>();
+        let user = new_signer();
+        let model_id = setup_model::<T>(&user, b"model".to_vec());
+
+        let client_params = ClientParamsCreateModelDoc {
+            app_id: APP_ID,
+            document_id: b"model_doc".to_vec(),
+            model_id,
+            product_id: b"product".to_vec(),
+            content_hash: T::Hash::default(),
+            producer_count: 100,
+            product_count: 100,
+        };
+        let (envelope, encode) = enveloped::<T>(&user, b"kp/create_model_create_document", &client_params.encode());
+        let user_sign = sign(&user, &encode);
+        let auth_signs = app_key_signs::<T>(&auth, s, &encode);
+    }: _(RawOrigin::Signed(caller), client_params, envelope, user.into(), user_sign, auth_signs)
+    verify {
+        assert!(Kp::<T>::kp_misc_document_power(APP_ID, b"model_doc".to_vec()).is_exist);
+    }
+
+    create_comment {
+        // full pool takes the eviction path
+        let p in 1 .. T::DocumentCommentPoolMax::get();
+        let b in 1 .. T::AppLeaderBoardMaxPos::get();
+        let s in 1 .. AUTH_SIGNS_MAX as u32;
+
+        let (_, auth) = setup_app::<T>();
+        let user = new_signer();
+        let model_id = setup_model::<T>(&user, b"model".to_vec());
+        create_publish_doc::<T>(&user, &auth, &model_id)?;
+        fill_leader_boards::<T>(&model_id, &account_of::<T>(&user), b);
+        create_identify_doc::<T>(&user, &auth, 0)?;
+        let document_id = identify_params::<T>(0).document_id;
+        fill_comment_pool::<T>(&document_id, p);
+        fund_treasury::<T>(T::TreasuryModuleId::get());
+
+        let commenter = new_signer();
+        let caller = account_of::<T>(&commenter);
+        let comment_data = CommentData {
+            app_id: APP_ID,
+            document_id,
+            comment_id: b"comment".to_vec(),
+            comment_hash: T::Hash::default(),
+            comment_fee: 100,
+            comment_trend: 0,
+        };
+        let (envelope, encode) = enveloped::<T>(&commenter, b"kp/create_comment", &comment_data.encode());
+        let user_sign = sign(&commenter, &encode);
+        let auth_signs = app_key_signs::<T>(&auth, s, &encode);
+    }: _(RawOrigin::Signed(caller), comment_data, envelope, commenter.into(), user_sign, auth_signs)
+    verify {
+        let key = T::Hashing::hash_of(&(APP_ID, b"comment".to_vec()));
+        assert!(<KPCommentDataByIdHash<T>>::contains_key(&key));
+    }
+
+    moderate_comment {
+        let p in 1 .. T::DocumentCommentPoolMax::get();
+        let b in 1 .. T::AppLeaderBoardMaxPos::get();
+
+        let (admin, auth) = setup_app::<T>();
+        let user = new_signer();
+        let model_id = setup_model::<T>(&user, b"model".to_vec());
+        create_publish_doc::<T>(&user, &auth, &model_id)?;
+        fill_leader_boards::<T>(&model_id, &account_of::<T>(&user), b);
+        create_identify_doc::<T>(&user, &auth, 0)?;
+        let document_id = identify_params::<T>(0).document_id;
+        fill_comment_pool::<T>(&document_id, p);
+        fund_treasury::<T>(T::TreasuryModuleId::get());
+
+        let commenter = new_signer();
+        let comment_data = CommentData {
+            app_id: APP_ID,
+            document_id,
+            comment_id: b"comment".to_vec(),
+            comment_hash: T::Hash::default(),
+            comment_fee: 100,
+            comment_trend: 0,
+        };
+        let (envelope, encode) = enveloped::<T>(&commenter, b"kp/create_comment", &comment_data.encode());
+        let user_sign = sign(&commenter, &encode);
+        let auth_sign = sign(&auth, &encode);
+        Kp::<T>::create_comment(
+            RawOrigin::Signed(account_of::<T>(&commenter)).into(),
+            comment_data,
+            envelope,
+            commenter.into(),
+            user_sign,
+            vec![(auth.into(), auth_sign)],
+        )?;
+    }: _(RawOrigin::Signed(account_of::<T>(&admin)), APP_ID, b"comment".to_vec())
+    verify {
+        let key = T::Hashing::hash_of(&(APP_ID, b"comment".to_vec()));
+        assert!(<KPCommentHidden<T>>::contains_key(&key));
+    }
+
+    create_commodity_type {
+    }: _(RawOrigin::Root, COMMODITY_TYPE, b"bench".to_vec())
+    verify {
+        assert!(CommodityTypeMap::contains_key(COMMODITY_TYPE));
+    }
+
+    set_app_model_total {
+        let _ = setup_app::<T>();
+    }: _(RawOrigin::Root, APP_ID, 100)
+    verify {
+        assert_eq!(AppModelTotalConfig::get(APP_ID), 100);
+    }
+
+    set_model_income {
+        let n in 1 .. T::ModelIncomeParamsMax::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+        let (admin, _) = setup_app::<T>();
+        let finance = new_signer();
+        T::Membership::add_finance_member(&account_of::<T>(&finance), 0u32.into());
+
+        let user = new_signer();
+        let mut model_ids = vec![];
+        for i in 0..n {
+            model_ids.push(setup_model::<T>(&user, (b"model", i).encode()));
+        }
+        let params = ModelIncomeCollectingParam {
+            app_id: APP_ID,
+            model_ids,
+            incomes: vec![100; n as usize],
+        };
+        let (envelope, encode) = enveloped::<T>(&admin, b"kp/set_model_income", &params.encode());
+        let user_sign = sign(&admin, &encode);
+        let auth_sign = sign(&finance, &encode);
+
+        let block = income_stage_block::<T>(ModelIncomeStage::COLLECTING);
+        frame_system::Module::<T>::set_block_number(block);
+    }: _(RawOrigin::Signed(caller), params, envelope, admin.into(), user_sign, finance.into(), auth_sign)
+    verify {
+        let cycle_index = Kp::<T>::model_income_cycle_index(block);
+        assert_eq!(<ModelCycleIncomeTotal<T>>::get(cycle_index), 100 * n as u64);
+    }
+
+    request_model_reward {
+        // every expert takes 1 percent of model reward, model creator is kept in the expert set too
+        let e in 0 .. T::ModelExpertsMax::get() - 1;
+
+        let _ = setup_app::<T>();
+        let user = new_signer();
+        let creator = account_of::<T>(&user);
+        let model_id = setup_model::<T>(&user, b"model".to_vec());
+        for i in 0 .. e {
+            let expert: T::AccountId = account("expert", i, SEED);
+            T::Membership::add_model_expert(&expert, APP_ID, &model_id, 1);
+        }
+        fund_treasury::<T>(T::ModTreasuryModuleId::get());
+
+        let block = income_stage_block::<T>(ModelIncomeStage::REWARDING);
+        frame_system::Module::<T>::set_block_number(block);
+        let cycle_index = Kp::<T>::model_income_cycle_index(block);
+        let sub_key = T::Hashing::hash_of(&(APP_ID, &model_id));
+        <ModelCycleIncomeTotal<T>>::insert(cycle_index, 100);
+        <ModelCycleIncome<T>>::insert(cycle_index, &sub_key, 100);
+    }: _(RawOrigin::Signed(creator), APP_ID, model_id, e)
+    verify {
+        assert!(<ModelCycleIncomeRewardRecords<T>>::contains_key(cycle_index, &sub_key));
+    }
+
+    app_income_redeem_request {
+        let (account, user, admin, _) = setup_app_income_redeem::<T>(false)?;
+        let params = app_income_redeem_request_params::<T>(&account);
+        let (envelope, encode) = enveloped::<T>(&user, b"kp/app_income_redeem_request", &params.encode());
+        let user_sign = sign(&user, &encode);
+        let auth_sign = sign(&admin, &encode);
+    }: _(RawOrigin::Signed(account.clone()), params, envelope, user.into(), user_sign, admin.into(), auth_sign)
+    verify {
+        assert_eq!(Kp::<T>::app_income_exchange_data(APP_ID, 1u32.into(), account).status, ExchangeStatus::Reserved);
+    }
+
+    app_income_redeem_confirm {
+        let (account, _, _, finance_member) = setup_app_income_redeem::<T>(true)?;
+        let params = AppIncomeRedeemConfirmParams {
+            account: account.clone(),
+            app_id: APP_ID,
+            pay_id: b"pay".to_vec(),
+            cycle: 1u32.into(),
+        };
+    }: _(RawOrigin::Signed(finance_member), params)
+    verify {
+        assert_eq!(Kp::<T>::app_income_exchange_data(APP_ID, 1u32.into(), account).status, ExchangeStatus::Confirmed);
+    }
+
+    app_income_redeem_compensate {
+        let (account, _, _, _) = setup_app_income_redeem::<T>(true)?;
+        frame_system::Module::<T>::set_block_number(income_stage_block::<T>(ModelIncomeStage::COMPENSATING));
+    }: _(RawOrigin::Signed(account.clone()), APP_ID, 1u32.into())
+    verify {
+        assert_eq!(Kp::<T>::app_income_exchange_data(APP_ID, 1u32.into(), account).status, ExchangeStatus::Compensated);
+    }
+
+    democracy_slash_commodity_power {
+        let b in 1 .. T::AppLeaderBoardMaxPos::get();
+
+        let (_, auth) = setup_app::<T>();
+        let user = new_signer();
+        let model_id = setup_model::<T>(&user, b"model".to_vec());
+        create_publish_doc::<T>(&user, &auth, &model_id)?;
+        fill_leader_boards::<T>(&model_id, &account_of::<T>(&user), b);
+        create_identify_doc::<T>(&user, &auth, 0)?;
+
+        let params = identify_params::<T>(0);
+        let comment_key = T::Hashing::hash_of(&(APP_ID, b"comment".to_vec()));
+        <KPCommentDataByIdHash<T>>::insert(&comment_key, KPCommentData {
+            app_id: APP_ID,
+            document_id: params.document_id,
+            comment_id: b"comment".to_vec(),
+            ..Default::default()
+        });
+        let reporter: T::AccountId = account("reporter", 0, SEED);
+    }: _(RawOrigin::Root, APP_ID, params.cart_id.clone(), b"comment".to_vec(), reporter)
+    verify {
+        assert!(Kp::<T>::is_commodity_in_black_list(APP_ID, params.cart_id));
+    }
+
+    democracy_model_dispute {
+        let _ = setup_app::<T>();
+        let user = new_signer();
+        let model_id = setup_model::<T>(&user, b"model".to_vec());
+        fund_treasury::<T>(T::TreasuryModuleId::get());
+
+        // reach dispute max to take the slash path
+        let block = frame_system::Module::<T>::block_number();
+        let key = T::Hashing::hash_of(&(APP_ID, &model_id));
+        <ModelCycleDisputeCount<T>>::insert(Kp::<T>::model_income_cycle_index(block), &key, T::ModelDisputeCycleCount::get());
+        let reporter: T::AccountId = account("reporter", 0, SEED);
+    }: _(RawOrigin::Root, APP_ID, model_id, ModelDisputeType::Serious, b"comment".to_vec(), reporter)
+    verify {
+        assert_eq!(Kp::<T>::model_dispute_record(APP_ID, b"comment".to_vec()).dispute_type, ModelDisputeType::Serious);
+    }
+
+    democracy_add_app {
+        let user = new_signer();
+        let admin = account_of::<T>(&user);
+        funded::<T>(&admin);
+        <AppIdRange<T>>::insert(b"bench".to_vec(), (APP_ID, dollars::<T>(1), 0, 0, 0));
+
+        let params = AddAppParams {
+            app_type: b"bench".to_vec(),
+            app_name: b"bench app".to_vec(),
+            app_key: account("app_key", 0, SEED),
+            app_admin_key: admin,
+            return_rate: 100,
+        };
+        let (envelope, encode) = enveloped::<T>(&user, b"kp/democracy_add_app", &params.encode());
+        let user_sign = sign(&user, &encode);
+    }: _(RawOrigin::Root, params, envelope, user.into(), user_sign)
+    verify {
+        assert!(T::Membership::is_valid_app(APP_ID + 1));
+    }
+
+    democracy_suspend_app {
+        let _ = setup_app::<T>();
+    }: _(RawOrigin::Root, APP_ID)
+    verify {
+        assert!(T::Membership::is_app_suspended(APP_ID));
+    }
+
+    democracy_resume_app {
+        let _ = setup_app::<T>();
+        T::Membership::set_app_suspended(APP_ID, true);
+    }: _(RawOrigin::Root, APP_ID)
+    verify {
+        assert!(T::Membership::is_valid_app(APP_ID));
+    }
+
+    democracy_deregister_app {
+        let (admin, _) = setup_app::<T>();
+        let owner = account_of::<T>(&admin);
+        let stake = dollars::<T>(1);
+        T::Currency::reserve(&owner, stake)?;
+        <AppIdRange<T>>::insert(b"bench".to_vec(), (APP_ID, stake, 0, 1, 0));
+        <AppStakes<T>>::insert(APP_ID, AppStakeRecord {
+            app_type: b"bench".to_vec(),
+            owner,
+            stake,
+        });
+    }: _(RawOrigin::Root, APP_ID, true)
+    verify {
+        assert!(!T::Membership::is_valid_app(APP_ID));
+        assert_eq!(<AppIdRange<T>>::get(b"bench".to_vec()).3, 0);
+    }
+
+    democracy_set_economic_params {
+        let mut params = Kp::<T>::default_economic_params();
+        params.model_cycle_income_reward_total = dollars::<T>(1);
+    }: _(RawOrigin::Root, params.clone())
+    verify {
+        assert_eq!(<PendingEconomicParams<T>>::get().map(|x| x.1), Some(params));
+    }
+
+    propose_power_weight_profile {
+        let (admin, _) = setup_app::<T>();
+    }: _(RawOrigin::Signed(account_of::<T>(&admin)), APP_ID, power_weight_profile())
+    verify {
+        assert_eq!(<PendingPowerWeightProfiles>::get(APP_ID), Some(power_weight_profile()));
+    }
+
+    democracy_review_power_weight_profile {
+        let _ = setup_app::<T>();
+        <PendingPowerWeightProfiles>::insert(APP_ID, power_weight_profile());
+    }: _(RawOrigin::Root, APP_ID, true)
+    verify {
+        assert_eq!(Kp::<T>::power_weight_profile(APP_ID), power_weight_profile());
+    }
+
+    democracy_set_leader_board_reward {
+        let _ = setup_app::<T>();
+        let reward = dollars::<T>(10);
+    }: _(RawOrigin::Root, APP_ID, b"model".to_vec(), reward)
+    verify {
+        assert_eq!(<LeaderBoardRewardPools<T>>::get(APP_ID, b"model".to_vec()), Some(reward));
+    }
+
+    submit_batch {
+        let n in 1 .. T::SubmitBatchMax::get();
+
+        let (_, auth) = setup_app::<T>();
+        // items pass the user sign check then fail for a missing product, only the batch overhead is left
+        let items: Vec<BatchItem<T::Hash, T::BlockNumber>> = (0..n).map(|i| {
+            let user = new_signer();
+            let client_params = identify_params::<T>(i);
+            let (envelope, encode) = enveloped::<T>(&user, b"kp/create_product_identify_document", &client_params.encode());
+            BatchItem {
+                params: BatchItemParams::ProductIdentify(client_params),
+                envelope,
+                app_user_account: user.into(),
+                app_user_sign: sign(&user, &encode),
+            }
+        }).collect();
+        let (envelope, encode) = enveloped::<T>(&auth, b"kp/submit_batch", &(APP_ID, &items).encode());
+        let auth_signs = vec![(auth.into(), sign(&auth, &encode))];
+    }: _(RawOrigin::Signed(whitelisted_caller()), APP_ID, items, envelope, auth_signs)
+    verify {
+        assert_eq!(Kp::<T>::app_commodity_count(APP_ID), 0);
+    }
+
+    democracy_app_financed {
+        let r in 0 .. T::AppFinancedOpenRoundsMax::get() - 1;
+
+        let _ = setup_app::<T>();
+        // rounds of other apps not settled yet
+        let open_rounds: Vec<(u32, T::BlockNumber, BalanceOf<T>)> = (0..r)
+            .map(|i| (APP_ID + 1 + i, T::BlockNumber::max_value(), dollars::<T>(1)))
+            .collect();
+        <AppFinancedOpenRounds<T>>::put(open_rounds);
+        let user = new_signer();
+        let finance = new_signer();
+        T::Membership::add_finance_member(&account_of::<T>(&finance), 0u32.into());
+        let investor: T::AccountId = account("investor", 0, SEED);
+        T::Membership::add_investor(&investor, APP_ID);
+        fund_treasury::<T>(T::FinTreasuryModuleId::get());
+
+        let params = AppFinancedProposalParams {
+            account: investor,
+            app_id: APP_ID,
+            proposal_id: PROPOSAL_ID.to_vec(),
+            exchange: dollars::<T>(1000),
+            amount: dollars::<T>(1000),
+        };
+        let (envelope, encode) = enveloped::<T>(&user, b"kp/democracy_app_financed", &params.encode());
+        let user_sign = sign(&user, &encode);
+        let auth_sign = sign(&finance, &encode);
+    }: _(RawOrigin::Root, params, envelope, user.into(), user_sign, finance.into(), auth_sign)
+    verify {
+        assert_eq!(Kp::<T>::app_finance_record(APP_ID, PROPOSAL_ID.to_vec()).app_id, APP_ID);
+        assert_eq!(<AppFinancedOpenRounds<T>>::get().len() as u32, r + 1);
+    }
+
+    app_financed_user_exchange_request {
+        let (account, user, admin, _) = setup_app_financed_exchange::<T>(false)?;
+        let params = app_financed_user_exchange_params::<T>(&account);
+        let (envelope, encode) = enveloped::<T>(&user, b"kp/app_financed_user_exchange_request", &params.encode());
+        let user_sign = sign(&user, &encode);
+        let auth_sign = sign(&admin, &encode);
+    }: _(RawOrigin::Signed(account.clone()), params, envelope, user.into(), user_sign, admin.into(), auth_sign)
+    verify {
+        assert_eq!(Kp::<T>::app_finance_exchange_data(APP_ID, PROPOSAL_ID.to_vec(), account).status, ExchangeStatus::Reserved);
+    }
+
+    app_financed_user_exchange_confirm {
+        let (account, _, _, finance_member) = setup_app_financed_exchange::<T>(true)?;
+        let params = AppFinancedUserExchangeConfirmParams {
+            account: account.clone(),
+            app_id: APP_ID,
+            pay_id: b"pay".to_vec(),
+            proposal_id: PROPOSAL_ID.to_vec(),
+        };
+    }: _(RawOrigin::Signed(finance_member), params)
+    verify {
+        assert_eq!(Kp::<T>::app_finance_exchange_data(APP_ID, PROPOSAL_ID.to_vec(), account).status, ExchangeStatus::Confirmed);
+    }
+
+    app_finance_redeem_compensate {
+        let (account, _, _, _) = setup_app_financed_exchange::<T>(true)?;
+        let record = Kp::<T>::app_finance_record(APP_ID, PROPOSAL_ID.to_vec());
+        frame_system::Module::<T>::set_block_number(record.compensate_end_block);
+    }: _(RawOrigin::Signed(account.clone()), APP_ID, PROPOSAL_ID.to_vec())
+    verify {
+        assert_eq!(Kp::<T>::app_finance_exchange_data(APP_ID, PROPOSAL_ID.to_vec(), account).status, ExchangeStatus::Compensated);
+    }
+
+    create_power_leader_board {
+        let b in 1 .. T::AppLeaderBoardMaxPos::get();
+        // total comment pool entries attending lottery, full pools with every board item
+        let c in 0 .. T::DocumentCommentPoolMax::get() * T::AppLeaderBoardMaxPos::get();
+
+        let (_, auth) = setup_app::<T>();
+        let user = new_signer();
+        let model_id = setup_model::<T>(&user, b"model".to_vec());
+        create_publish_doc::<T>(&user, &auth, &model_id)?;
+        for i in 0..b {
+            create_identify_doc::<T>(&user, &auth, i)?;
+            fill_comment_pool::<T>(&identify_params::<T>(i).document_id, min(c / b, T::DocumentCommentPoolMax::get()));
+        }
+
+        // make every board item a leader
+        let model_key = T::Hashing::hash_of(&(APP_ID, &model_id));
+        if b > 5 {
+            <AppModelCommodityCount<T>>::insert(&model_key, b * 5);
+        }
+
+        fund_treasury::<T>(T::TreasuryModuleId::get());
+
+        let block: T::BlockNumber = 1u32.into();
+        frame_system::Module::<T>::set_block_number(block);
+    }: _(RawOrigin::Root, APP_ID, model_id.clone())
+    verify {
+        let key = Kp::<T>::leader_record_key(APP_ID, block, &model_id);
+        assert_eq!(<AppLeaderBoardRcord<T>>::get(&key).board.len(), b as usize);
+    }
+
+    democracy_tech_fund_withdraw {
+        fund_treasury::<T>(T::TechTreasuryModuleId::get());
+        let receiver: T::AccountId = account("receiver", 0, SEED);
+        let origin = T::TechMemberOrigin::successful_origin();
+        let call = Call::<T>::democracy_tech_fund_withdraw(
+            receiver.clone(),
+            T::Hash::default(),
+            TechFundWithdrawType::ChainDev,
+            TechFundWithdrawLevel::LV1,
+        );
+    }: { call.dispatch_bypass_filter(origin)? }
+    verify {
+        assert_eq!(<TechFundWithdrawRecords<T>>::get().len(), 1);
+    }
+
+    open_model_dispute {
+        let _ = setup_app::<T>();
+        let user = new_signer();
+        let model_id = setup_model::<T>(&user, b"model".to_vec());
+        let caller: T::AccountId = whitelisted_caller();
+        funded::<T>(&caller);
+    }: _(RawOrigin::Signed(caller), APP_ID, model_id, ModelDisputeType::Serious, b"comment".to_vec())
+    verify {
+        let key = T::Hashing::hash_of(&(APP_ID, b"comment".to_vec()));
+        assert!(<ModelDisputeCases<T>>::contains_key(&key));
+    }
+
+    submit_model_dispute_evidence {
+        let user = new_signer();
+        let model_id = setup_model::<T>(&user, b"model".to_vec());
+        let caller: T::AccountId = whitelisted_caller();
+        let mut case = model_dispute_case::<T>(&model_id, &caller, &account_of::<T>(&user));
+        case.evidence_end = case.evidence_end + 1u32.into();
+        case.evidences = (1..MODEL_DISPUTE_EVIDENCE_MAX).map(|_| (caller.clone(), T::Hash::default())).collect();
+        let key = T::Hashing::hash_of(&(APP_ID, &case.comment_id));
+        <ModelDisputeCases<T>>::insert(&key, case);
+    }: _(RawOrigin::Signed(caller), APP_ID, b"comment".to_vec(), T::Hash::default())
+    verify {
+        assert_eq!(<ModelDisputeCases<T>>::get(&key).evidences.len(), MODEL_DISPUTE_EVIDENCE_MAX);
+    }
+
+    vote_model_dispute {
+        // model creator is an expert, case owner stays another account
+        let user = new_signer();
+        let model_id = setup_model::<T>(&user, b"model".to_vec());
+        let reporter: T::AccountId = account("reporter", 0, SEED);
+        let mut case = model_dispute_case::<T>(&model_id, &reporter, &account("owner", 0, SEED));
+        case.vote_end = case.vote_end + 1u32.into();
+        case.ayes = (1..MODEL_DISPUTE_VOTE_MAX as u32).map(|i| account("voter", i, SEED)).collect();
+        let key = T::Hashing::hash_of(&(APP_ID, &case.comment_id));
+        <ModelDisputeCases<T>>::insert(&key, case);
+        frame_system::Module::<T>::set_block_number(frame_system::Module::<T>::block_number() + 1u32.into());
+    }: _(RawOrigin::Signed(account_of::<T>(&user)), APP_ID, b"comment".to_vec(), true)
+    verify {
+        assert_eq!(<ModelDisputeCases<T>>::get(&key).ayes.len(), MODEL_DISPUTE_VOTE_MAX);
+    }
+
+    decide_model_dispute {
+        let model_id = b"model".to_vec();
+        let caller: T::AccountId = whitelisted_caller();
+        let reporter: T::AccountId = account("reporter", 0, SEED);
+        let mut case = model_dispute_case::<T>(&model_id, &reporter, &account("owner", 0, SEED));
+        case.ayes = (0..MODEL_DISPUTE_VOTE_MAX as u32).map(|i| account("voter", i, SEED)).collect();
+        let key = T::Hashing::hash_of(&(APP_ID, &case.comment_id));
+        <ModelDisputeCases<T>>::insert(&key, case);
+        frame_system::Module::<T>::set_block_number(frame_system::Module::<T>::block_number() + 1u32.into());
+    }: _(RawOrigin::Signed(caller), APP_ID, b"comment".to_vec())
+    verify {
+        assert_eq!(<ModelDisputeCases<T>>::get(&key).status, ModelDisputeStatus::Decided);
+    }
+
+    appeal_model_dispute {
+        let model_id = b"model".to_vec();
+        let caller: T::AccountId = whitelisted_caller();
+        funded::<T>(&caller);
+        let reporter: T::AccountId = account("reporter", 0, SEED);
+        let mut case = model_dispute_case::<T>(&model_id, &reporter, &caller);
+        case.status = ModelDisputeStatus::Decided;
+        case.upheld = true;
+        let key = T::Hashing::hash_of(&(APP_ID, &case.comment_id));
+        <ModelDisputeCases<T>>::insert(&key, case);
+    }: _(RawOrigin::Signed(caller), APP_ID, b"comment".to_vec())
+    verify {
+        assert_eq!(<ModelDisputeCases<T>>::get(&key).status, ModelDisputeStatus::Appealed);
+    }
+
+    democracy_judge_model_dispute {
+        let _ = setup_app::<T>();
+        let user = new_signer();
+        let model_id = setup_model::<T>(&user, b"model".to_vec());
+        fund_treasury::<T>(T::TreasuryModuleId::get());
+
+        // appellant reporter wins, takes the model slash path
+        let reporter: T::AccountId = account("reporter", 0, SEED);
+        let mut case = model_dispute_case::<T>(&model_id, &reporter, &account_of::<T>(&user));
+        T::Currency::reserve(&reporter, T::ModelDisputeBond::get())?;
+        case.status = ModelDisputeStatus::Appealed;
+        case.appellant = Some(reporter);
+        case.appeal_bond = T::ModelDisputeBond::get();
+        let key = T::Hashing::hash_of(&(APP_ID, &case.comment_id));
+        <ModelDisputeCases<T>>::insert(&key, case);
+
+        let origin = T::TechMemberOrigin::successful_origin();
+        let call = Call::<T>::democracy_judge_model_dispute(APP_ID, b"comment".to_vec(), true);
+    }: { call.dispatch_bypass_filter(origin)? }
+    verify {
+        assert_eq!(<ModelDisputeCases<T>>::get(&key).status, ModelDisputeStatus::Resolved);
+    }
+
+    finalize_model_dispute {
+        let _ = setup_app::<T>();
+        let user = new_signer();
+        let model_id = setup_model::<T>(&user, b"model".to_vec());
+        fund_treasury::<T>(T::TreasuryModuleId::get());
+
+        let caller: T::AccountId = whitelisted_caller();
+        let reporter: T::AccountId = account("reporter", 0, SEED);
+        let mut case = model_dispute_case::<T>(&model_id, &reporter, &account_of::<T>(&user));
+        case.status = ModelDisputeStatus::Decided;
+        case.upheld = true;
+        let key = T::Hashing::hash_of(&(APP_ID, &case.comment_id));
+        <ModelDisputeCases<T>>::insert(&key, case);
+        frame_system::Module::<T>::set_block_number(frame_system::Module::<T>::block_number() + 1u32.into());
+    }: _(RawOrigin::Signed(caller), APP_ID, b"comment".to_vec())
+    verify {
+        assert_eq!(<ModelDisputeCases<T>>::get(&key).status, ModelDisputeStatus::Resolved);
+    }
+
+    revise_document {
+        let b in 1 .. T::AppLeaderBoardMaxPos::get();
+        let s in 1 .. AUTH_SIGNS_MAX as u32;
+
+        let caller: T::AccountId = whitelisted_caller();
+        let (_, auth) = setup_app::<T>();
+        let user = new_signer();
+        let model_id = setup_model::<T>(&user, b"model".to_vec());
+        create_publish_doc::<T>(&user, &auth, &model_id)?;
+        fill_leader_boards::<T>(&model_id, &account_of::<T>(&user), b);
+        create_identify_doc::<T>(&user, &auth, 0)?;
+
+        let identify = identify_params::<T>(0);
+        let key = T::Hashing::hash_of(&(APP_ID, &identify.document_id));
+        let client_params = ClientParamsReviseDoc {
+            app_id: APP_ID,
+            document_id: identify.document_id,
+            content_hash: T::Hashing::hash_of(b"revised"),
+            document_data: DocumentSpecificData::ProductIdentify(KPProductIdentifyData {
+                goods_price: 200,
+                ident_rate: 60,
+                ident_consistence: 60,
+                seller_consistence: 60,
+                cart_id: identify.cart_id,
+            }),
+        };
+        let (envelope, encode) = enveloped::<T>(&user, b"kp/revise_document", &client_params.encode());
+        let user_sign = sign(&user, &encode);
+        let auth_signs = app_key_signs::<T>(&auth, s, &encode);
+    }: _(RawOrigin::Signed(caller), client_params, envelope, user.into(), user_sign, auth_signs)
+    verify {
+        assert_eq!(<KPDocumentVersions<T>>::get(&key).len(), 1);
+    }
+
+    retract_document {
+        let b in 1 .. T::AppLeaderBoardMaxPos::get();
+        let s in 1 .. AUTH_SIGNS_MAX as u32;
+
+        let caller: T::AccountId = whitelisted_caller();
+        let (_, auth) = setup_app::<T>();
+        let user = new_signer();
+        let model_id = setup_model::<T>(&user, b"model".to_vec());
+        create_publish_doc::<T>(&user, &auth, &model_id)?;
+        fill_leader_boards::<T>(&model_id, &account_of::<T>(&user), b);
+        create_identify_doc::<T>(&user, &auth, 0)?;
+
+        // last document of the commodity, it leaves both leader boards
+        let document_id = identify_params::<T>(0).document_id;
+        let key = T::Hashing::hash_of(&(APP_ID, &document_id));
+        let client_params = ClientParamsRetractDoc {
+            app_id: APP_ID,
+            document_id,
+        };
+        let (envelope, encode) = enveloped::<T>(&user, b"kp/retract_document", &client_params.encode());
+        let user_sign = sign(&user, &encode);
+        let auth_signs = app_key_signs::<T>(&auth, s, &encode);
+    }: _(RawOrigin::Signed(caller), client_params, envelope, user.into(), user_sign, auth_signs)
+    verify {
+        assert!(<KPDocumentRetracted<T>>::contains_key(&key));
+    }
+
+    claim_leader_board_reward {
+        let _ = setup_app::<T>();
+        let caller: T::AccountId = whitelisted_caller();
+        let model_id = b"model".to_vec();
+        let block: T::BlockNumber = 1u32.into();
+        frame_system::Module::<T>::set_block_number(block);
+
+        let key = Kp::<T>::leader_record_key(APP_ID, block, &model_id);
+        <AppLeaderBoardRcord<T>>::insert(&key, LeaderBoardResult {
+            accounts: vec![caller.clone()],
+            board: vec![],
+        });
+        let reward = dollars::<T>(1);
+        <LeaderBoardRewardRecords<T>>::insert(&key, LeaderBoardRewardRecord {
+            app_id: APP_ID,
+            model_id: model_id.clone(),
+            block,
+            total: reward,
+            per_hit: reward,
+            claimed: 0u32.into(),
+            end_block: block + T::LeaderBoardRewardClaimPeriod::get(),
+            is_released: false,
+        });
+        fund_treasury::<T>(T::LeaderBoardRewardModuleId::get());
+    }: _(RawOrigin::Signed(caller.clone()), APP_ID, model_id, block)
+    verify {
+        assert_eq!(<LeaderBoardRewardClaimed<T>>::get(&key, &caller), reward);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{new_test_ext, Test};
+    use frame_support::assert_ok;
+    use sp_core::{testing::KeyStore, traits::KeystoreExt};
+
+    #[test]
+    fn test_benchmarks() {
+        let mut ext = new_test_ext();
+        ext.register_extension(KeystoreExt(KeyStore::new()));
+        ext.execute_with(|| {
+            assert_ok!(test_benchmark_create_model::<Test>());
+            assert_ok!(test_benchmark_model_owner_release::<Test>());
+            assert_ok!(test_benchmark_add_model_deposit::<Test>());
+            assert_ok!(test_benchmark_retire_model::<Test>());
+            assert_ok!(test_benchmark_create_product_publish_document::<Test>());
+            assert_ok!(test_benchmark_create_product_identify_document::<Test>());
+            assert_ok!(test_benchmark_create_product_try_document::<Test>());
+            assert_ok!(test_benchmark_create_product_choose_document::<Test>());
+            assert_ok!(test_benchmark_create_model_create_document::<Test>());
+            assert_ok!(test_benchmark_create_comment::<Test>());
+            assert_ok!(test_benchmark_moderate_comment::<Test>());
+            assert_ok!(test_benchmark_create_commodity_type::<Test>());
+            assert_ok!(test_benchmark_set_app_model_total::<Test>());
+            assert_ok!(test_benchmark_set_model_income::<Test>());
+            assert_ok!(test_benchmark_democracy_slash_commodity_power::<Test>());
+            assert_ok!(test_benchmark_democracy_model_dispute::<Test>());
+            assert_ok!(test_benchmark_open_model_dispute::<Test>());
+            assert_ok!(test_benchmark_submit_model_dispute_evidence::<Test>());
+            assert_ok!(test_benchmark_vote_model_dispute::<Test>());
+            assert_ok!(test_benchmark_decide_model_dispute::<Test>());
+            assert_ok!(test_benchmark_appeal_model_dispute::<Test>());
+            assert_ok!(test_benchmark_democracy_judge_model_dispute::<Test>());
+            assert_ok!(test_benchmark_finalize_model_dispute::<Test>());
+            assert_ok!(test_benchmark_democracy_add_app::<Test>());
+            assert_ok!(test_benchmark_democracy_suspend_app::<Test>());
+            assert_ok!(test_benchmark_democracy_resume_app::<Test>());
+            assert_ok!(test_benchmark_democracy_deregister_app::<Test>());
+            assert_ok!(test_benchmark_democracy_set_economic_params::<Test>());
+            assert_ok!(test_benchmark_propose_power_weight_profile::<Test>());
+            assert_ok!(test_benchmark_democracy_review_power_weight_profile::<Test>());
+            assert_ok!(test_benchmark_democracy_set_leader_board_reward::<Test>());
+            assert_ok!(test_benchmark_create_power_leader_board::<Test>());
+            assert_ok!(test_benchmark_claim_leader_board_reward::<Test>());
+            assert_ok!(test_benchmark_revise_document::<Test>());
+            assert_ok!(test_benchmark_retract_document::<Test>());
+            assert_ok!(test_benchmark_submit_batch::<Test>());
+        });
+    }
+}
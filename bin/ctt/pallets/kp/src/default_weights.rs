@@ -0,0 +1,278 @@
+//! Default weights of the kp pallet.
+//!
+//! These are hand estimated values, not benchmark output. Replace them with the output of
+//! `ctt benchmark --chain dev --execution wasm --wasm-execution compiled --pallet kp
+//! --extrinsic '*' --steps 50 --repeat 20` (node built with `--features runtime-benchmarks`)
+//! once the benchmarks are run on reference hardware. Benchmark components are bounded by
+//! the runtime constants the calls check.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
+
+impl crate::WeightInfo for () {
+    fn create_model(s: u32, ) -> Weight {
+        (133000000 as Weight)
+            .saturating_add((52000000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(DbWeight::get().reads(12 as Weight))
+            .saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+            .saturating_add(DbWeight::get().writes(13 as Weight))
+    }
+    fn model_owner_release() -> Weight {
+        (160000000 as Weight)
+            .saturating_add(DbWeight::get().reads(6 as Weight))
+            .saturating_add(DbWeight::get().writes(4 as Weight))
+    }
+    fn add_model_deposit() -> Weight {
+        (62000000 as Weight)
+            .saturating_add(DbWeight::get().reads(5 as Weight))
+            .saturating_add(DbWeight::get().writes(3 as Weight))
+    }
+    fn create_product_publish_document(s: u32, ) -> Weight {
+        (158000000 as Weight)
+            .saturating_add((52000000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(DbWeight::get().reads(11 as Weight))
+            .saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+            .saturating_add(DbWeight::get().writes(8 as Weight))
+    }
+    fn create_product_identify_document(b: u32, s: u32, ) -> Weight {
+        (193000000 as Weight)
+            .saturating_add((1240000 as Weight).saturating_mul(b as Weight))
+            .saturating_add((52000000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(DbWeight::get().reads(15 as Weight))
+            .saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+            .saturating_add(DbWeight::get().writes(14 as Weight))
+    }
+    fn create_product_try_document(b: u32, s: u32, ) -> Weight {
+        (193000000 as Weight)
+            .saturating_add((1240000 as Weight).saturating_mul(b as Weight))
+            .saturating_add((52000000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(DbWeight::get().reads(15 as Weight))
+            .saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+            .saturating_add(DbWeight::get().writes(14 as Weight))
+    }
+    fn create_product_choose_document(s: u32, ) -> Weight {
+        (123000000 as Weight)
+            .saturating_add((52000000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(DbWeight::get().reads(8 as Weight))
+            .saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+            .saturating_add(DbWeight::get().writes(5 as Weight))
+    }
+    fn create_model_create_document(s: u32, ) -> Weight {
+        (123000000 as Weight)
+            .saturating_add((52000000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(DbWeight::get().reads(8 as Weight))
+            .saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+            .saturating_add(DbWeight::get().writes(5 as Weight))
+    }
+    fn create_comment(p: u32, b: u32, s: u32, ) -> Weight {
+        (238000000 as Weight)
+            .saturating_add((96000 as Weight).saturating_mul(p as Weight))
+            .saturating_add((1240000 as Weight).saturating_mul(b as Weight))
+            .saturating_add((52000000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(DbWeight::get().reads(21 as Weight))
+            .saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+            .saturating_add(DbWeight::get().writes(16 as Weight))
+    }
+    fn create_commodity_type() -> Weight {
+        (38000000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn set_app_model_total() -> Weight {
+        (14000000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn set_model_income(n: u32, ) -> Weight {
+        (72000000 as Weight)
+            .saturating_add((31500000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(DbWeight::get().reads(4 as Weight))
+            .saturating_add(DbWeight::get().reads((4 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(DbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(DbWeight::get().writes(0 as Weight))
+    }
+    fn request_model_reward(e: u32, ) -> Weight {
+        (168000000 as Weight)
+            .saturating_add((42000000 as Weight).saturating_mul(e as Weight))
+            .saturating_add(DbWeight::get().reads(10 as Weight))
+            .saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(e as Weight)))
+            .saturating_add(DbWeight::get().writes(5 as Weight))
+            .saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(e as Weight)))
+    }
+    fn app_income_redeem_request() -> Weight {
+        (182000000 as Weight)
+            .saturating_add(DbWeight::get().reads(11 as Weight))
+            .saturating_add(DbWeight::get().writes(6 as Weight))
+    }
+    fn app_income_redeem_confirm() -> Weight {
+        (196000000 as Weight)
+            .saturating_add(DbWeight::get().reads(8 as Weight))
+            .saturating_add(DbWeight::get().writes(5 as Weight))
+    }
+    fn app_income_redeem_compensate() -> Weight {
+        (154000000 as Weight)
+            .saturating_add(DbWeight::get().reads(7 as Weight))
+            .saturating_add(DbWeight::get().writes(4 as Weight))
+    }
+    fn democracy_slash_commodity_power(b: u32, ) -> Weight {
+        (132000000 as Weight)
+            .saturating_add((1180000 as Weight).saturating_mul(b as Weight))
+            .saturating_add(DbWeight::get().reads(11 as Weight))
+            .saturating_add(DbWeight::get().writes(7 as Weight))
+    }
+    fn democracy_model_dispute() -> Weight {
+        (148000000 as Weight)
+            .saturating_add(DbWeight::get().reads(8 as Weight))
+            .saturating_add(DbWeight::get().writes(6 as Weight))
+    }
+    fn democracy_add_app() -> Weight {
+        (118000000 as Weight)
+            .saturating_add(DbWeight::get().reads(4 as Weight))
+            .saturating_add(DbWeight::get().writes(7 as Weight))
+    }
+    fn democracy_app_financed(r: u32, ) -> Weight {
+        (174000000 as Weight)
+            .saturating_add((118000 as Weight).saturating_mul(r as Weight))
+            .saturating_add(DbWeight::get().reads(10 as Weight))
+            .saturating_add(DbWeight::get().writes(5 as Weight))
+    }
+    fn app_financed_user_exchange_request() -> Weight {
+        (189000000 as Weight)
+            .saturating_add(DbWeight::get().reads(10 as Weight))
+            .saturating_add(DbWeight::get().writes(5 as Weight))
+    }
+    fn app_financed_user_exchange_confirm() -> Weight {
+        (193000000 as Weight)
+            .saturating_add(DbWeight::get().reads(7 as Weight))
+            .saturating_add(DbWeight::get().writes(4 as Weight))
+    }
+    fn app_finance_redeem_compensate() -> Weight {
+        (151000000 as Weight)
+            .saturating_add(DbWeight::get().reads(7 as Weight))
+            .saturating_add(DbWeight::get().writes(4 as Weight))
+    }
+    fn create_power_leader_board(b: u32, c: u32, ) -> Weight {
+        (96000000 as Weight)
+            .saturating_add((24600000 as Weight).saturating_mul(b as Weight))
+            .saturating_add((1870000 as Weight).saturating_mul(c as Weight))
+            .saturating_add(DbWeight::get().reads(6 as Weight))
+            .saturating_add(DbWeight::get().reads((6 as Weight).saturating_mul(b as Weight)))
+            .saturating_add(DbWeight::get().writes(3 as Weight))
+    }
+    fn democracy_tech_fund_withdraw() -> Weight {
+        (87000000 as Weight)
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn claim_leader_board_reward() -> Weight {
+        (82000000 as Weight)
+            .saturating_add(DbWeight::get().reads(6 as Weight))
+            .saturating_add(DbWeight::get().writes(4 as Weight))
+    }
+    fn retire_model() -> Weight {
+        (58000000 as Weight)
+            .saturating_add(DbWeight::get().reads(5 as Weight))
+            .saturating_add(DbWeight::get().writes(3 as Weight))
+    }
+    fn open_model_dispute() -> Weight {
+        (62000000 as Weight)
+            .saturating_add(DbWeight::get().reads(5 as Weight))
+            .saturating_add(DbWeight::get().writes(3 as Weight))
+    }
+    fn submit_model_dispute_evidence() -> Weight {
+        (36000000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn vote_model_dispute() -> Weight {
+        (48000000 as Weight)
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn decide_model_dispute() -> Weight {
+        (34000000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn appeal_model_dispute() -> Weight {
+        (44000000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn democracy_judge_model_dispute() -> Weight {
+        (186000000 as Weight)
+            .saturating_add(DbWeight::get().reads(12 as Weight))
+            .saturating_add(DbWeight::get().writes(10 as Weight))
+    }
+    fn finalize_model_dispute() -> Weight {
+        (184000000 as Weight)
+            .saturating_add(DbWeight::get().reads(12 as Weight))
+            .saturating_add(DbWeight::get().writes(9 as Weight))
+    }
+    fn revise_document(b: u32, s: u32, ) -> Weight {
+        (180000000 as Weight)
+            .saturating_add((1240000 as Weight).saturating_mul(b as Weight))
+            .saturating_add((52000000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(DbWeight::get().reads(15 as Weight))
+            .saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+            .saturating_add(DbWeight::get().writes(10 as Weight))
+    }
+    fn retract_document(b: u32, s: u32, ) -> Weight {
+        (196000000 as Weight)
+            .saturating_add((2480000 as Weight).saturating_mul(b as Weight))
+            .saturating_add((52000000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(DbWeight::get().reads(17 as Weight))
+            .saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+            .saturating_add(DbWeight::get().writes(17 as Weight))
+    }
+    fn moderate_comment(p: u32, b: u32, ) -> Weight {
+        (236000000 as Weight)
+            .saturating_add((96000 as Weight).saturating_mul(p as Weight))
+            .saturating_add((1240000 as Weight).saturating_mul(b as Weight))
+            .saturating_add(DbWeight::get().reads(18 as Weight))
+            .saturating_add(DbWeight::get().writes(14 as Weight))
+    }
+    fn democracy_suspend_app() -> Weight {
+        (31000000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn democracy_resume_app() -> Weight {
+        (28000000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn democracy_deregister_app() -> Weight {
+        (92000000 as Weight)
+            .saturating_add(DbWeight::get().reads(8 as Weight))
+            .saturating_add(DbWeight::get().writes(12 as Weight))
+    }
+    fn democracy_set_economic_params() -> Weight {
+        (26000000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn propose_power_weight_profile() -> Weight {
+        (38000000 as Weight)
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn democracy_review_power_weight_profile() -> Weight {
+        (27000000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn democracy_set_leader_board_reward() -> Weight {
+        (24000000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn submit_batch(n: u32, ) -> Weight {
+        (20000000 as Weight)
+            .saturating_add((61000000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(DbWeight::get().reads((5 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+    }
+}
@@ -4,10 +4,13 @@
 use frame_support::{
     codec::{Decode, Encode},
     decl_error, decl_event, decl_module, decl_storage, dispatch, ensure,
+    storage::{unhashed, with_transaction, IterableStorageMap},
     traits::{
-        Contains, Currency, EnsureOrigin, ExistenceRequirement::KeepAlive, Get, LockableCurrency,
-        OnUnbalanced, Randomness, ReservableCurrency, WithdrawReason,
+        Contains, Currency, EnsureOrigin,
+        ExistenceRequirement::{AllowDeath, KeepAlive},
+        Get, LockableCurrency, OnUnbalanced, Randomness, ReservableCurrency, WithdrawReason,
     },
+    transactional,
     weights::Weight,
 };
 use rand_chacha::{
@@ -20,7 +23,7 @@ use serde::{Deserialize, Serialize};
 // #[macro_use]
 // extern crate sp_std;
 
-use core::convert::TryInto;
+use core::convert::{TryFrom, TryInto};
 use sp_std::cmp::*;
 use sp_std::collections::btree_map::BTreeMap;
 use sp_std::convert::From;
@@ -36,15 +39,28 @@ use sp_std::prelude::*;
 /// For more guidance on Substrate FRAME, see the example pallet
 /// https://github.com/paritytech/substrate/blob/master/frame/example/src/lib.rs
 use frame_system::{self as system, ensure_root, ensure_signed};
-use primitives::{AuthAccountId, Membership, PowerSize};
+use primitives::{
+    AuthAccountId, AuthSignature, Membership, PowerSize, SignEnvelope, SignEnvelopeError,
+};
 use sp_core::sr25519;
 use sp_runtime::{
     print,
-    traits::{AccountIdConversion, Hash, SaturatedConversion, TrailingZeroInput, Verify},
-    ModuleId, MultiSignature, Perbill, Percent, Permill, RuntimeDebug,
+    traits::{
+        AccountIdConversion, Hash, SaturatedConversion, Saturating, TrailingZeroInput, Verify, Zero,
+    },
+    ModuleId, MultiSignature, Perbill, Percent, Permill, RuntimeDebug, TransactionOutcome,
 };
 
 const FLOAT_COMPUTE_PRECISION: PowerSize = 10000;
+// max items of one leader board history query page
+const LEADER_BOARD_HISTORY_PAGE_MAX: u32 = 100;
+// max evidences and votes kept by one model dispute
+const MODEL_DISPUTE_EVIDENCE_MAX: usize = 20;
+const MODEL_DISPUTE_VOTE_MAX: usize = 100;
+// max replaced versions kept for one document
+const DOCUMENT_VERSION_MAX: usize = 20;
+// keep in step with MAX_APP_KEYS of members
+const AUTH_SIGNS_MAX: usize = 16;
 
 type BalanceOf<T> =
     <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
@@ -104,6 +120,9 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+mod benchmarking;
+mod default_weights;
+
 #[derive(Encode, Decode, PartialEq, Clone, RuntimeDebug)]
 pub enum ModelStatus {
     ENABLED = 0,
@@ -126,7 +145,7 @@ impl From<u8> for ModelStatus {
     }
 }
 
-#[derive(Encode, Decode, PartialEq, Clone, RuntimeDebug)]
+#[derive(Encode, Decode, PartialEq, Eq, Clone, RuntimeDebug)]
 pub enum DocumentType {
     ProductPublish = 0,
     ProductIdentify,
@@ -172,7 +191,7 @@ impl From<DocumentType> for u8 {
 }
 
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-#[derive(Encode, Decode, PartialEq, Clone, Copy, RuntimeDebug)]
+#[derive(Encode, Decode, PartialEq, Eq, Clone, Copy, RuntimeDebug)]
 pub enum ModelDisputeType {
     NoneIntendNormal = 0,
     IntendNormal,
@@ -303,6 +322,23 @@ pub struct CommentData<Hash> {
     comment_trend: u8,
 }
 
+// one user submission of a batch, the high volume identify, try documents and comments
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+pub enum BatchItemParams<Hash> {
+    ProductIdentify(ClientParamsCreateIdentifyDoc<Hash>),
+    ProductTry(ClientParamsCreateTryDoc<Hash>),
+    Comment(CommentData<Hash>),
+}
+
+// app user signs `params` in its envelope, same message as the single item call
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+pub struct BatchItem<Hash, BlockNumber> {
+    pub params: BatchItemParams<Hash>,
+    pub envelope: SignEnvelope<BlockNumber>,
+    pub app_user_account: AuthAccountId,
+    pub app_user_sign: AuthSignature,
+}
+
 // account comment action record
 #[derive(Encode, Decode, Clone, PartialEq, Default, RuntimeDebug)]
 pub struct KPCommentAccountRecord {
@@ -311,6 +347,17 @@ pub struct KPCommentAccountRecord {
     positive_count: PowerSize,
 }
 
+// what a comment changed, kept to reverse it when moderated
+#[derive(Encode, Decode, Clone, PartialEq, Default, RuntimeDebug)]
+pub struct KPCommentEffect<Balance> {
+    // comment position in document comment pool
+    pub position: PowerSize,
+    pub is_expert: bool,
+    pub expert_trend_set: bool,
+    pub platform_trend_set: bool,
+    pub reward: Balance,
+}
+
 #[derive(Encode, Decode, Clone, PartialEq, Default, RuntimeDebug)]
 pub struct CommentMaxRecord {
     max_count: PowerSize,
@@ -460,6 +507,22 @@ pub struct AppFinancedData<Balance, BlockNumber> {
     pub total_balance: Balance,
     pub exchanged: Balance,
     pub exchange_end_block: BlockNumber,
+    // confirm and compensate stages follow the exchange period in force when the round opened
+    pub confirm_end_block: BlockNumber,
+    pub compensate_end_block: BlockNumber,
+}
+
+// financed record layout of storage version V8_0_0 and before, also returned by KpApi version 1
+#[derive(Encode, Decode)]
+pub struct AppFinancedDataV8<Balance, BlockNumber> {
+    pub app_id: u32,
+    pub proposal_id: Vec<u8>,
+    pub amount: Balance,
+    pub exchange: Balance,
+    pub block: BlockNumber,
+    pub total_balance: Balance,
+    pub exchanged: Balance,
+    pub exchange_end_block: BlockNumber,
 }
 
 #[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug)]
@@ -479,14 +542,82 @@ pub struct AppFinancedUserExchangeParams<AccountId, Balance> {
     exchange_amount: Balance,
 }
 
+// encoded as the u8 status kept before, values must not be reordered
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, PartialEq, Clone, Copy, RuntimeDebug)]
+pub enum ExchangeStatus {
+    Initial = 0,
+    // user balance reserved, waiting finance member to pay cash
+    Reserved,
+    // received cash and burned
+    Confirmed,
+    // not receive cash but got slash from finance member
+    Compensated,
+    // not receive cash and finance member has nothing left to slash, only unreserved
+    CompensateFailed,
+}
+
+impl ExchangeStatus {
+    pub fn can_transit_to(&self, next: ExchangeStatus) -> bool {
+        match (self, next) {
+            (ExchangeStatus::Initial, ExchangeStatus::Reserved) => true,
+            (ExchangeStatus::Reserved, ExchangeStatus::Confirmed) => true,
+            (ExchangeStatus::Reserved, ExchangeStatus::Compensated) => true,
+            (ExchangeStatus::Reserved, ExchangeStatus::CompensateFailed) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Default for ExchangeStatus {
+    fn default() -> Self {
+        ExchangeStatus::Initial
+    }
+}
+
+impl From<ExchangeStatus> for u8 {
+    fn from(orig: ExchangeStatus) -> Self {
+        orig as u8
+    }
+}
+
+impl TryFrom<u8> for ExchangeStatus {
+    type Error = ();
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(ExchangeStatus::Initial),
+            1 => Ok(ExchangeStatus::Reserved),
+            2 => Ok(ExchangeStatus::Confirmed),
+            3 => Ok(ExchangeStatus::Compensated),
+            4 => Ok(ExchangeStatus::CompensateFailed),
+            _ => Err(()),
+        }
+    }
+}
+
 #[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug)]
-pub struct AppFinancedUserExchangeData<Balance> {
+pub struct AppFinancedUserExchangeData<Balance, BlockNumber> {
+    pub exchange_amount: Balance,
+    pub status: ExchangeStatus,
+    pub pay_id: Vec<u8>,
+    // block of every status change, empty for records exchanged before it was kept
+    pub transitions: Vec<(ExchangeStatus, BlockNumber)>,
+}
+
+impl<Balance, BlockNumber> AppFinancedUserExchangeData<Balance, BlockNumber> {
+    // callers check can_transit_to first
+    fn transit(&mut self, status: ExchangeStatus, block: BlockNumber) {
+        self.status = status;
+        self.transitions.push((status, block));
+    }
+}
+
+// exchange record layout of storage version V3_0_0 and before, also returned by KpApi version 1
+#[derive(Encode, Decode)]
+pub struct AppFinancedUserExchangeDataV3<Balance> {
     pub exchange_amount: Balance,
-    // 0: initial state,
-    // 1: reserved,
-    // 2: received cash and burned,
-    // 3: not receive cash but got slash from finance member
-    pub status: u8,
+    pub status: ExchangeStatus,
     pub pay_id: Vec<u8>,
 }
 
@@ -543,6 +674,51 @@ pub struct LeaderBoardItem<AccountId> {
     pub owner: AccountId,
 }
 
+// for RPC query using
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct LeaderBoardHistoryItem<BlockNumber> {
+    pub model_id: Vec<u8>,
+    pub block: BlockNumber,
+}
+
+// for RPC query using
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct LeaderBoardHistoryPage<BlockNumber> {
+    pub items: Vec<LeaderBoardHistoryItem<BlockNumber>>,
+    // matched items count of all pages
+    pub total: u32,
+    // last item of this page, next page starts after it
+    pub next_cursor: Option<LeaderBoardHistoryItem<BlockNumber>>,
+}
+
+// leader board lottery reward pool, record key is same as AppLeaderBoardRcord
+#[derive(Encode, Decode, Default, Clone, PartialEq, RuntimeDebug)]
+pub struct LeaderBoardRewardRecord<Balance, BlockNumber> {
+    pub app_id: u32,
+    pub model_id: Vec<u8>,
+    pub block: BlockNumber,
+    pub total: Balance,
+    // reward of each lottery hit, account may hit more than once
+    pub per_hit: Balance,
+    pub claimed: Balance,
+    // last block for winners to claim
+    pub end_block: BlockNumber,
+    // unclaimed part was returned to treasury
+    pub is_released: bool,
+}
+
+// for RPC query using
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct LeaderBoardRewardInfo<Balance, BlockNumber> {
+    pub reward: Balance,
+    pub is_claimed: bool,
+    pub end_block: BlockNumber,
+    pub is_released: bool,
+}
+
 #[derive(Encode, Decode, Default, Clone, Eq, RuntimeDebug)]
 pub struct CommodityLeaderBoardData<T: Trait> {
     cart_id: Vec<u8>,
@@ -594,6 +770,15 @@ impl<T: Trait> PartialOrd for CommentWeightData<T> {
     }
 }
 
+impl<T: Trait> CommentWeightData<T> {
+    // rank in document comment pool, higher cash cost first then earlier position
+    fn pool_rank(&self, other: &Self) -> Ordering {
+        self.cash_cost
+            .cmp(&other.cash_cost)
+            .then_with(|| other.position.cmp(&self.position))
+    }
+}
+
 #[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug)]
 pub struct AddAppParams<AccountId> {
     app_type: Vec<u8>,
@@ -603,6 +788,14 @@ pub struct AddAppParams<AccountId> {
     return_rate: u32,
 }
 
+// stake reserved when app added, released or slashed when app deregistered
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug)]
+pub struct AppStakeRecord<AccountId, Balance> {
+    app_type: Vec<u8>,
+    owner: AccountId,
+    stake: Balance,
+}
+
 /// create_model params
 #[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug)]
 pub struct ClientParamsCreateModel<Hash> {
@@ -683,6 +876,28 @@ pub struct ClientParamsCreateModelDoc<Hash> {
     product_count: PowerSize,
 }
 
+// document_data must keep its document type, cart id of identify and try document can not change
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug)]
+pub struct ClientParamsReviseDoc<Hash> {
+    app_id: u32,
+    document_id: Vec<u8>,
+    content_hash: Hash,
+    document_data: DocumentSpecificData,
+}
+
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug)]
+pub struct ClientParamsRetractDoc {
+    app_id: u32,
+    document_id: Vec<u8>,
+}
+
+// content hash replaced by a revision at block
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug)]
+pub struct KPDocumentVersion<Hash, BlockNumber> {
+    pub content_hash: Hash,
+    pub block: BlockNumber,
+}
+
 #[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug)]
 pub struct AppFinancedUserExchangeConfirmParams<AccountId> {
     account: AccountId,
@@ -726,12 +941,94 @@ pub struct ModelIncomeCurrentStage<Block> {
     pub left: Block,
 }
 
+/// Power weights of an app approved by governance, every group sums up to 100
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug)]
+pub struct PowerWeightProfile {
+    /// (product publish, document identify, document try, account attend, account stake)
+    pub top: (u8, u8, u8, u8, u8),
+    /// document power (attend, content, judge)
+    pub document: (u8, u8, u8),
+    /// comment attend power (count, cost, cost per unit, positive)
+    pub comment: (u8, u8, u8, u8),
+    /// publish content (params rate, self rate, attend rate)
+    pub publish: (u8, u8, u8),
+    /// identify content (params rate, check rate, consistent rate)
+    pub identify: (u8, u8, u8),
+    /// try content (bias rate, true rate, consistent rate)
+    pub product_try: (u8, u8, u8),
+    /// choose content (sell count, try count)
+    pub choose: (u8, u8),
+    /// model content (producer count, product count)
+    pub model: (u8, u8),
+    /// choose & model power (account attend, attend, content, judge)
+    pub cm: (u8, u8, u8, u8),
+    /// choose & model comment attend power (count, cost, cost per unit, positive)
+    pub comment_cm: (u8, u8, u8, u8),
+    /// comment share of publish, identify and try document power, at most 100
+    pub comment_power: u8,
+}
+
+// power weight profile layout of storage version V11_0_0 and before
+#[derive(Encode, Decode)]
+struct PowerWeightProfileV11 {
+    top: (u8, u8, u8, u8, u8),
+    document: (u8, u8, u8),
+    comment: (u8, u8, u8, u8),
+    publish: (u8, u8, u8),
+    identify: (u8, u8, u8),
+    product_try: (u8, u8, u8),
+    choose: (u8, u8),
+    model: (u8, u8),
+    cm: (u8, u8, u8, u8),
+}
+
+impl PowerWeightProfile {
+    fn is_valid(&self) -> bool {
+        let sums: [u32; 10] = [
+            self.top.0 as u32
+                + self.top.1 as u32
+                + self.top.2 as u32
+                + self.top.3 as u32
+                + self.top.4 as u32,
+            self.document.0 as u32 + self.document.1 as u32 + self.document.2 as u32,
+            self.comment.0 as u32
+                + self.comment.1 as u32
+                + self.comment.2 as u32
+                + self.comment.3 as u32,
+            self.publish.0 as u32 + self.publish.1 as u32 + self.publish.2 as u32,
+            self.identify.0 as u32 + self.identify.1 as u32 + self.identify.2 as u32,
+            self.product_try.0 as u32 + self.product_try.1 as u32 + self.product_try.2 as u32,
+            self.choose.0 as u32 + self.choose.1 as u32,
+            self.model.0 as u32 + self.model.1 as u32,
+            self.cm.0 as u32 + self.cm.1 as u32 + self.cm.2 as u32 + self.cm.3 as u32,
+            self.comment_cm.0 as u32
+                + self.comment_cm.1 as u32
+                + self.comment_cm.2 as u32
+                + self.comment_cm.3 as u32,
+        ];
+
+        sums.iter().all(|sum| *sum == 100) && self.comment_power <= 100
+    }
+}
+
+/// Cycle and reward parameters, seeded from the runtime constants and tunable by governance
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug)]
+pub struct KPEconomicParams<Balance, BlockNumber> {
+    pub model_income_cycle_period: BlockNumber,
+    pub model_income_collecting_period: BlockNumber,
+    pub model_income_rewarding_period: BlockNumber,
+    pub model_cycle_income_reward_total: Balance,
+    pub app_leader_board_interval: BlockNumber,
+    pub app_finance_exchange_period: BlockNumber,
+    pub model_dispute_delay_time: BlockNumber,
+}
+
 #[derive(Encode, Decode, PartialEq, Clone, RuntimeDebug)]
 pub struct ModelCycleIncomeReward<Account, Balance> {
-    account: Account,
-    app_id: u32,
-    model_id: Vec<u8>,
-    reward: Balance,
+    pub account: Account,
+    pub app_id: u32,
+    pub model_id: Vec<u8>,
+    pub reward: Balance,
 }
 
 #[derive(Encode, Decode, PartialEq, Clone, Copy, RuntimeDebug)]
@@ -761,6 +1058,71 @@ pub struct TechFundWithdrawData<Account, Balance, Hash> {
     reason: Hash,
 }
 
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, PartialEq, Clone, Copy, RuntimeDebug)]
+pub enum ModelDisputeStatus {
+    // collecting evidence then votes
+    Open = 0,
+    // votes counted, waiting appeal
+    Decided,
+    // waiting tech committee judgement
+    Appealed,
+    Resolved,
+}
+
+impl Default for ModelDisputeStatus {
+    fn default() -> Self {
+        ModelDisputeStatus::Open
+    }
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, PartialEq, Clone, Default, RuntimeDebug)]
+pub struct ModelDisputeCase<AccountId, Balance, BlockNumber, Hash> {
+    pub app_id: u32,
+    pub model_id: Vec<u8>,
+    pub comment_id: Vec<u8>,
+    pub dispute_type: ModelDisputeType,
+    pub reporter: AccountId,
+    pub owner: AccountId,
+    pub bond: Balance,
+    pub status: ModelDisputeStatus,
+    // evidence accepted until this block, then voting
+    pub evidence_end: BlockNumber,
+    pub vote_end: BlockNumber,
+    pub appeal_end: BlockNumber,
+    // (submitter, evidence content hash)
+    pub evidences: Vec<(AccountId, Hash)>,
+    pub ayes: Vec<AccountId>,
+    pub nays: Vec<AccountId>,
+    // dispute is valid, by votes or by appeal judgement after Resolved
+    pub upheld: bool,
+    pub appellant: Option<AccountId>,
+    // reserved from appellant, the bond constant may change before settlement
+    pub appeal_bond: Balance,
+}
+
+// dispute case layout of storage version V10_0_0 and before
+#[derive(Encode, Decode)]
+struct ModelDisputeCaseV10<AccountId, Balance, BlockNumber, Hash> {
+    app_id: u32,
+    model_id: Vec<u8>,
+    comment_id: Vec<u8>,
+    dispute_type: ModelDisputeType,
+    reporter: AccountId,
+    owner: AccountId,
+    bond: Balance,
+    status: ModelDisputeStatus,
+    evidence_end: BlockNumber,
+    vote_end: BlockNumber,
+    appeal_end: BlockNumber,
+    evidences: Vec<(AccountId, Hash)>,
+    ayes: Vec<AccountId>,
+    nays: Vec<AccountId>,
+    upheld: bool,
+    appellant: Option<AccountId>,
+}
+
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Encode, Decode, PartialEq, Clone, Default, RuntimeDebug)]
 pub struct ModelDisputeRecord<Block> {
@@ -780,6 +1142,92 @@ pub struct CommoditySlashRecord<Block> {
     pub block: Block,
 }
 
+pub trait WeightInfo {
+    fn create_model(s: u32, ) -> Weight;
+    fn model_owner_release() -> Weight;
+    fn add_model_deposit() -> Weight;
+    fn create_product_publish_document(s: u32, ) -> Weight;
+    fn create_product_identify_document(b: u32, s: u32, ) -> Weight;
+    fn create_product_try_document(b: u32, s: u32, ) -> Weight;
+    fn create_product_choose_document(s: u32, ) -> Weight;
+    fn create_model_create_document(s: u32, ) -> Weight;
+    fn create_comment(p: u32, b: u32, s: u32, ) -> Weight;
+    fn create_commodity_type() -> Weight;
+    fn set_app_model_total() -> Weight;
+    fn set_model_income(n: u32, ) -> Weight;
+    fn request_model_reward(e: u32, ) -> Weight;
+    fn app_income_redeem_request() -> Weight;
+    fn app_income_redeem_confirm() -> Weight;
+    fn app_income_redeem_compensate() -> Weight;
+    fn democracy_slash_commodity_power(b: u32, ) -> Weight;
+    fn democracy_model_dispute() -> Weight;
+    fn democracy_add_app() -> Weight;
+    fn democracy_app_financed(r: u32, ) -> Weight;
+    fn app_financed_user_exchange_request() -> Weight;
+    fn app_financed_user_exchange_confirm() -> Weight;
+    fn app_finance_redeem_compensate() -> Weight;
+    fn create_power_leader_board(b: u32, c: u32, ) -> Weight;
+    fn democracy_tech_fund_withdraw() -> Weight;
+    fn claim_leader_board_reward() -> Weight;
+    fn retire_model() -> Weight;
+    fn open_model_dispute() -> Weight;
+    fn submit_model_dispute_evidence() -> Weight;
+    fn vote_model_dispute() -> Weight;
+    fn decide_model_dispute() -> Weight;
+    fn appeal_model_dispute() -> Weight;
+    fn democracy_judge_model_dispute() -> Weight;
+    fn finalize_model_dispute() -> Weight;
+    fn revise_document(b: u32, s: u32, ) -> Weight;
+    fn retract_document(b: u32, s: u32, ) -> Weight;
+    fn moderate_comment(p: u32, b: u32, ) -> Weight;
+    fn democracy_suspend_app() -> Weight;
+    fn democracy_resume_app() -> Weight;
+    fn democracy_deregister_app() -> Weight;
+    fn democracy_set_economic_params() -> Weight;
+    fn propose_power_weight_profile() -> Weight;
+    fn democracy_review_power_weight_profile() -> Weight;
+    fn democracy_set_leader_board_reward() -> Weight;
+    fn submit_batch(n: u32, ) -> Weight;
+}
+
+// A value placed in storage that represents the current version of the Kp storage.
+// This value is used by the `on_runtime_upgrade` logic to determine whether we run
+// storage migration logic.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+enum Releases {
+    V1_0_0,
+    // document comment pools bounded by DocumentCommentPoolMax
+    V2_0_0,
+    // app financing rounds tracked per app
+    V3_0_0,
+    // exchange records with status transitions
+    V4_0_0,
+    // leader boards of all active models scheduled by index
+    V5_0_0,
+    // identify and try documents indexed by referred product
+    V6_0_0,
+    // existing commodity, miner and total power start decaying from upgrade block
+    V7_0_0,
+    // stake records of apps added before they were kept
+    V8_0_0,
+    // financed records keep their confirm and compensate end blocks
+    V9_0_0,
+    // killed models leave the app model count
+    V10_0_0,
+    // dispute cases keep the appeal bond reserved
+    V11_0_0,
+    // power weight profiles carry the comment weights
+    V12_0_0,
+    // leader board sequence keys moved into the leader board history
+    V13_0_0,
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V1_0_0
+    }
+}
+
 /// The pallet's configuration trait.
 pub trait Trait: system::Trait {
     // Add other types and constants required to configure this pallet.
@@ -805,9 +1253,21 @@ pub trait Trait: system::Trait {
     /// Something that provides randomness in the runtime.
     type Randomness: Randomness<Self::Hash>;
 
+    /// VRF based randomness, such as BABE epoch randomness mixed with the current block VRF output
+    type VrfRandomness: Randomness<Self::Hash>;
+
+    /// Use `VrfRandomness` for lottery and finance member choosing, otherwise fall back to `Randomness`
+    type UseVrfRandomness: Get<bool>;
+
     /// Finance treasury model id
     type FinTreasuryModuleId: Get<ModuleId>;
 
+    /// Max amount of all unsettled app financing rounds, a round counts until its compensate end
+    type AppFinancedExposureMax: Get<BalanceOf<Self>>;
+
+    /// Max app financing rounds not settled yet
+    type AppFinancedOpenRoundsMax: Get<u32>;
+
     /// Model treasury model id
     type ModTreasuryModuleId: Get<ModuleId>;
 
@@ -821,6 +1281,7 @@ pub trait Trait: system::Trait {
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 
     /// 5 dimensions weight config
+    /// Power weights below are the defaults of apps without an approved `PowerWeightProfile`
     type TopWeightProductPublish: Get<u8>;
     type TopWeightDocumentIdentify: Get<u8>;
     type TopWeightDocumentTry: Get<u8>;
@@ -848,6 +1309,7 @@ pub trait Trait: system::Trait {
     /// Comment Power positive weight
     type CommentPowerWeightPositive: Get<u8>;
 
+    /// Comment share of publish, identify and try document power
     type CommentPowerWeight: Get<u8>;
 
     /// Document Publish content weight
@@ -898,21 +1360,51 @@ pub trait Trait: system::Trait {
     type CMPowerAccountAttend: Get<u8>;
 
     type ModelCreateDeposit: Get<BalanceOf<Self>>;
+
+    /// Genesis value of the model cycle reward, see `KPEconomicParams`
     type ModelCycleIncomeRewardTotal: Get<BalanceOf<Self>>;
 
     /// App financed purpose minimal exchange rate
     type KptExchangeMinRate: Get<Permill>;
 
+    /// Genesis value of the leader board interval
     type AppLeaderBoardInterval: Get<Self::BlockNumber>;
 
     type AppLeaderBoardMaxPos: Get<u32>;
 
+    /// Max comments kept in one document comment pool for leader board lottery
+    type DocumentCommentPoolMax: Get<u32>;
+
+    /// Max leader boards checked for automatic snapshot in one block
+    type AppLeaderBoardSnapshotsPerBlock: Get<u32>;
+
+    /// Max document and comment items of one submit_batch
+    type SubmitBatchMax: Get<u32>;
+
+    /// Default leader board reward pool, transferred from treasury and split among lottery winners,
+    /// boards with a pool set by governance use their own
+    type LeaderBoardReward: Get<BalanceOf<Self>>;
+
+    /// Blocks for winners to claim leader board reward, unclaimed part goes back to treasury
+    type LeaderBoardRewardClaimPeriod: Get<Self::BlockNumber>;
+
+    /// Leader board reward pot module id
+    type LeaderBoardRewardModuleId: Get<ModuleId>;
+
+    /// Genesis value of the app finance exchange period
     type AppFinanceExchangePeriod: Get<Self::BlockNumber>;
 
+    /// Genesis values of the model income cycle and its stages
     type ModelIncomeCyclePeriod: Get<Self::BlockNumber>;
     type ModelIncomeCollectingPeriod: Get<Self::BlockNumber>;
     type ModelIncomeRewardingPeriod: Get<Self::BlockNumber>;
 
+    /// Max models of one income collecting call
+    type ModelIncomeParamsMax: Get<u32>;
+
+    /// Max experts of one model, keep in step with the members module
+    type ModelExpertsMax: Get<u32>;
+
     type ModelDisputeCycleCount: Get<u32>;
     type ModelDisputeCycleLv2IncreaseCount: Get<u32>;
     type ModelDisputeCycleLv3IncreaseCount: Get<u32>;
@@ -923,8 +1415,20 @@ pub trait Trait: system::Trait {
 
     // Model dispute slash config
     type ModelDisputeLv1Slash: Get<BalanceOf<Self>>;
+    /// Genesis value of the model dispute delay
     type ModelDisputeDelayTime: Get<Self::BlockNumber>;
 
+    /// Reporter bond of a model dispute, also required for appeal
+    type ModelDisputeBond: Get<BalanceOf<Self>>;
+
+    /// Model dispute stage periods
+    type ModelDisputeEvidencePeriod: Get<Self::BlockNumber>;
+    type ModelDisputeVotePeriod: Get<Self::BlockNumber>;
+    type ModelDisputeAppealPeriod: Get<Self::BlockNumber>;
+
+    /// Blocks between model retired by owner and its deposit returned
+    type ModelRetireCoolDown: Get<Self::BlockNumber>;
+
     // Base Balance of tech fund
     type TechFundBase: Get<BalanceOf<Self>>;
 
@@ -932,6 +1436,15 @@ pub trait Trait: system::Trait {
 
     type CommentRewardNormalRate: Get<u32>;
     type CommentRewardExpertRate: Get<u32>;
+
+    /// Blocks of one power decay period, 0 disables decay
+    type PowerDecayPeriod: Get<Self::BlockNumber>;
+
+    /// Share of power kept after each decay period, 50% gives a half-life of one period
+    type PowerDecayRatio: Get<Perbill>;
+
+    /// Weight information for extrinsics in this pallet.
+    type WeightInfo: WeightInfo;
 }
 
 // This pallet's storage items.
@@ -943,6 +1456,10 @@ decl_storage! {
         AppIdRange get(fn app_id_range) config():
             map hasher(twox_64_concat) Vec<u8> => (u32, BalanceOf<T>, u32, u32, u32);
 
+        // AppId -> app type, stake owner and amount
+        AppStakes get(fn app_stakes):
+            map hasher(twox_64_concat) u32 => AppStakeRecord<T::AccountId, BalanceOf<T>>;
+
         // (AppId, ModelId) -> KPModelData
         KPModelDataByIdHash get(fn kp_model_data_by_idhash):
             map hasher(twox_64_concat) T::Hash => KPModelDataOf<T>;
@@ -955,6 +1472,18 @@ decl_storage! {
         KPCommentAccountRecordMap get(fn kp_comment_account_record_map):
             map hasher(twox_64_concat) T::Hash => KPCommentAccountRecord;
 
+        // (AppId, CommentId) -> comment effect
+        KPCommentEffects get(fn kp_comment_effects):
+            map hasher(twox_64_concat) T::Hash => KPCommentEffect<BalanceOf<T>>;
+
+        // (AppId, CommentId) -> block number the comment was hidden by app admin
+        KPCommentHidden get(fn kp_comment_hidden):
+            map hasher(twox_64_concat) T::Hash => T::BlockNumber;
+
+        // (AppId, CommentId) -> reward moderation could not reclaim from the commenter
+        KPCommentRewardShortfall get(fn kp_comment_reward_shortfall):
+            map hasher(twox_64_concat) T::Hash => BalanceOf<T>;
+
         // AuthAccountId -> PowerSize max goods_price
         KPAccountMaxPurchaseByIdHash get(fn kp_account_max_purchase_by_idhash):
             map hasher(twox_64_concat) AuthAccountId => PowerSize;
@@ -980,10 +1509,22 @@ decl_storage! {
         KPDocumentPowerByIdHash get(fn kp_document_power_by_idhash):
             map hasher(twox_64_concat) T::Hash => DocumentPower;
 
+        // (AppId, DocumentId) -> replaced content hashes, oldest first
+        KPDocumentVersions get(fn kp_document_versions):
+            map hasher(twox_64_concat) T::Hash => Vec<KPDocumentVersion<T::Hash, T::BlockNumber>>;
+
+        // (AppId, DocumentId) -> retract block, document id of retracted document can not be used again
+        KPDocumentRetracted get(fn kp_document_retracted):
+            map hasher(twox_64_concat) T::Hash => T::BlockNumber;
+
         // (AppId, ProductId) -> DocumentId document index map
         KPDocumentProductIndexByIdHash get(fn kp_document_product_index_by_idhash):
             map hasher(twox_64_concat) T::Hash => Vec<u8>;
 
+        // (AppId, ProductId), (AppId, DocumentId) -> () identify and try documents referring the product
+        KPProductReferDocuments get(fn kp_product_refer_documents):
+            double_map hasher(twox_64_concat) T::Hash, hasher(twox_64_concat) T::Hash => ();
+
         // (AppId, CartId) -> Vec<u8> cartid -> product identify document id
         KPCartProductIdentifyIndexByIdHash get(fn kp_cart_product_identify_index_by_idhash):
             map hasher(twox_64_concat) T::Hash => Vec<u8>;
@@ -999,10 +1540,28 @@ decl_storage! {
         // global total knowledge power (only for commodity power)
         TotalPower get(fn total_power): PowerSize;
 
+        // block TotalPower decay was last applied at
+        TotalPowerSettledAt get(fn total_power_settled_at): Option<T::BlockNumber>;
+
+        // global total knowledge power without decay
+        TotalRawPower get(fn total_raw_power): PowerSize;
+
         // miner power table
         MinerPowerByAccount get(fn miner_power_by_account):
             map hasher(blake2_128_concat) T::AccountId => PowerSize;
 
+        // block miner power decay was last applied at
+        MinerPowerSettledAt get(fn miner_power_settled_at):
+            map hasher(blake2_128_concat) T::AccountId => Option<T::BlockNumber>;
+
+        // miner power without decay
+        MinerRawPowerByAccount get(fn miner_raw_power_by_account):
+            map hasher(blake2_128_concat) T::AccountId => PowerSize;
+
+        // (AppId, CartId) -> block the commodity power was added to total and miner power
+        KPPurchasePowerSettledAt get(fn kp_purchase_power_settled_at):
+            map hasher(twox_64_concat) T::Hash => Option<T::BlockNumber>;
+
         // miner documents power (accumulation) (app_id account_id) -> DocumentPower
         // MinerDocumentsAccumulationPower get(fn miner_documents_accumulation_power):
         //    map hasher(twox_64_concat) T::Hash => DocumentPower;
@@ -1060,7 +1619,7 @@ decl_storage! {
         AppModelTotalConfig get(fn app_model_total_config):
             map hasher(twox_64_concat) u32 => u32;
 
-        // app id => u32
+        // app id => models not killed or retired yet
         AppModelCount get(fn app_model_count):
             map hasher(twox_64_concat) u32 => u32;
 
@@ -1073,7 +1632,7 @@ decl_storage! {
 
         // app_id, cycle, account
         AppCycleIncomeExchangeRecords get(fn app_cycle_income_exchange_records):
-            map hasher(twox_64_concat) T::Hash => AppFinancedUserExchangeData<BalanceOf<T>>;
+            map hasher(twox_64_concat) T::Hash => AppFinancedUserExchangeData<BalanceOf<T>, T::BlockNumber>;
 
         // (AppId & cycle index) -> user accounts set
         AppCycleIncomeExchangeSet get(fn app_cycle_income_exchange_set):
@@ -1112,12 +1671,20 @@ decl_storage! {
         AppFinancedRecord get(fn app_financed_record):
             map hasher(twox_64_concat) T::Hash => AppFinancedData<BalanceOf<T>, T::BlockNumber>;
 
-        // Last time app financed record key
+        // Last time app financed record key, replaced by AppFinancedLastOfApp and only kept for migration
         AppFinancedLast get(fn app_financed_last): T::Hash;
 
+        // AppId -> last app financed record key of the app
+        AppFinancedLastOfApp get(fn app_financed_last_of_app):
+            map hasher(twox_64_concat) u32 => T::Hash;
+
+        // (app_id, compensate_end_block, amount) of rounds not settled yet, the treasury exposure
+        AppFinancedOpenRounds get(fn app_financed_open_rounds):
+            Vec<(u32, T::BlockNumber, BalanceOf<T>)>;
+
         // App financed user exchange record (AppId & ProposalId & AccountId -> AppFinancedUserExchangeData)
         AppFinancedUserExchangeRecord get(fn app_financed_user_exchange_record):
-            map hasher(twox_64_concat) T::Hash => AppFinancedUserExchangeData<BalanceOf<T>>;
+            map hasher(twox_64_concat) T::Hash => AppFinancedUserExchangeData<BalanceOf<T>, T::BlockNumber>;
 
         // (AppId & ProposalId) -> user accounts set
         AppFinancedUserExchangeSet get(fn app_financed_user_exchange_set):
@@ -1148,17 +1715,63 @@ decl_storage! {
         AppLeaderBoardRcord get(fn app_leader_board_record):
             map hasher(twox_64_concat) T::Hash => LeaderBoardResult<T::AccountId>;
 
-        // Store AppLeaderBoardRcord keys, for load
+        // Lottery rng seed of AppLeaderBoardRcord, same key, for off-chain verify
+        AppLeaderBoardSeed get(fn app_leader_board_seed):
+            map hasher(twox_64_concat) T::Hash => [u8; 32];
+
+        // Store AppLeaderBoardRcord keys, replaced by AppLeaderBoardHistory, only read by runtime upgrade
         AppLeaderBoardSequenceKeys get(fn app_leader_board_sequence_keys): Vec<(u32, T::BlockNumber, Vec<u8>)>;
 
+        // Leader board record blocks in ascending order (AppId, ModelId) -> Vec<BlockNumber>
+        AppLeaderBoardHistory get(fn app_leader_board_history):
+            map hasher(twox_64_concat) T::Hash => Vec<T::BlockNumber>;
+
+        // AppId -> models which have leader board record, empty model id is app board
+        AppLeaderBoardModels get(fn app_leader_board_models):
+            map hasher(twox_64_concat) u32 => Vec<Vec<u8>>;
+
+        // Index -> leader board (AppId, ModelId) taking automatic snapshot, empty model id means app board
+        AppLeaderBoardSchedule get(fn app_leader_board_schedule):
+            map hasher(twox_64_concat) u32 => Option<(u32, Vec<u8>)>;
+
+        // Number of boards in AppLeaderBoardSchedule, indexes are 0..count
+        AppLeaderBoardScheduleCount get(fn app_leader_board_schedule_count): u32;
+
+        // (AppId, ModelId) hash -> index of board in AppLeaderBoardSchedule
+        AppLeaderBoardScheduled get(fn app_leader_board_scheduled):
+            map hasher(twox_64_concat) T::Hash => Option<u32>;
+
+        // Next AppLeaderBoardSchedule index to be checked in on_initialize
+        AppLeaderBoardScheduleCursor get(fn app_leader_board_schedule_cursor): u32;
+
+        // Leader board lottery reward (AppId, ModelId, BlockNumber) => LeaderBoardRewardRecord
+        LeaderBoardRewardRecords get(fn leader_board_reward_records):
+            map hasher(twox_64_concat) T::Hash => LeaderBoardRewardRecord<BalanceOf<T>, T::BlockNumber>;
+
+        // Leader board reward pool set by governance AppId, ModelId (empty for app board) => Balance
+        LeaderBoardRewardPools get(fn leader_board_reward_pool):
+            double_map hasher(twox_64_concat) u32, hasher(blake2_128_concat) Vec<u8> => Option<BalanceOf<T>>;
+
+        // Leader board reward claimed by account, group key: (AppId, ModelId, BlockNumber) hash
+        LeaderBoardRewardClaimed get(fn leader_board_reward_claimed):
+            double_map hasher(twox_64_concat) T::Hash, hasher(blake2_128_concat) T::AccountId => BalanceOf<T>;
+
+        // Block number -> leader board reward keys to be released at this block
+        LeaderBoardRewardExpiry get(fn leader_board_reward_expiry):
+            map hasher(twox_64_concat) T::BlockNumber => Vec<T::Hash>;
+
         // Leader board last record (AppId, ModelId) -> BlockNumber
         AppLeaderBoardLastTime get(fn app_leader_board_last_time):
             map hasher(twox_64_concat) T::Hash => T::BlockNumber;
 
-        // Document comment order pool (AppId, DocumentId) -> Vec<CommentWeightData>
+        // Document comment order pool (AppId, DocumentId) -> Vec<CommentWeightData>, bounded by DocumentCommentPoolMax
         DocumentCommentsAccountPool get(fn document_comments_account_pool):
             map hasher(twox_64_concat) T::Hash => Vec<CommentWeightData<T>>;
 
+        // (AppId, DocumentId) -> last comment position, unlike comment count it never goes back
+        DocumentCommentPosition get(fn document_comment_position):
+            map hasher(twox_64_concat) T::Hash => Option<PowerSize>;
+
         // Account action statistics
         AccountStatisticsMap get(fn account_statistics_map):
             map hasher(twox_64_concat) T::AccountId => AccountStatistics;
@@ -1173,6 +1786,14 @@ decl_storage! {
 
         TechFundWithdrawRecords get(fn tech_fund_withdraw_records): Vec<TechFundWithdrawData<T::AccountId, BalanceOf<T>, T::Hash>>;
 
+        // (AppId, ModelId) -> (owner, cool-down end block) of retiring model
+        ModelRetireRecords get(fn model_retire_records):
+            map hasher(twox_64_concat) T::Hash => (T::AccountId, T::BlockNumber);
+
+        // Block number -> retiring models whose cool-down ends at this block
+        ModelRetireExpiry get(fn model_retire_expiry):
+            map hasher(twox_64_concat) T::BlockNumber => Vec<(u32, Vec<u8>)>;
+
         // pre-black list of model dispute, this is a collection which reserved balance lower than required 50%
         ModelPreBlackList get(fn model_black_list_pre): Vec<(u32, Vec<u8>, T::AccountId, T::BlockNumber)>;
 
@@ -1183,9 +1804,40 @@ decl_storage! {
         ModelDisputeRecords get(fn model_dispute_records):
             map hasher(twox_64_concat) T::Hash => ModelDisputeRecord<T::BlockNumber>;
 
+        // (app_id, comment_id) -> dispute in progress or resolved
+        ModelDisputeCases get(fn model_dispute_cases):
+            map hasher(twox_64_concat) T::Hash => ModelDisputeCase<T::AccountId, BalanceOf<T>, T::BlockNumber, T::Hash>;
+
+        // (app_id, model_id) -> count of dispute cases not resolved yet
+        ModelOpenDisputeCount get(fn model_open_dispute_count):
+            map hasher(twox_64_concat) T::Hash => u32;
+
         // (app_id, comment_id)
         CommoditySlashRecords get(fn commodity_slash_record):
             map hasher(twox_64_concat) T::Hash => CommoditySlashRecord<T::BlockNumber>;
+
+        // power weights approved by governance, apps without one use the runtime weights
+        AppPowerWeightProfiles get(fn app_power_weight_profile):
+            map hasher(twox_64_concat) u32 => Option<PowerWeightProfile>;
+
+        // power weight profiles proposed by app admins, waiting for governance review
+        PendingPowerWeightProfiles get(fn pending_power_weight_profile):
+            map hasher(twox_64_concat) u32 => Option<PowerWeightProfile>;
+
+        // economic params in force, chains started before they were stored fall back to the constants
+        EconomicParams get(fn economic_params)
+            build(|_: &GenesisConfig<T>| Some(Module::<T>::default_economic_params())):
+            Option<KPEconomicParams<BalanceOf<T>, T::BlockNumber>>;
+
+        // (start block, cycle index) of the model income cycles run by the params in force
+        EconomicParamsAnchor get(fn economic_params_anchor): (T::BlockNumber, T::BlockNumber);
+
+        // params waiting for the next model income cycle, with the block they take effect
+        PendingEconomicParams get(fn pending_economic_params):
+            Option<(T::BlockNumber, KPEconomicParams<BalanceOf<T>, T::BlockNumber>)>;
+
+        // Storage version of the pallet, new networks start with the last version
+        StorageVersion build(|_: &GenesisConfig<T>| Releases::V13_0_0): Releases;
     }
 }
 
@@ -1195,33 +1847,106 @@ decl_event!(
     where
         AccountId = <T as system::Trait>::AccountId,
         BlockNumber = <T as system::Trait>::BlockNumber,
+        Balance = BalanceOf<T>,
     {
         /// Just a dummy event.
         /// Event `Something` is declared with a parameter of the type `u32` and `AccountId`
         /// To emit this event, we call the deposit function, from our runtime functions
         // SomethingStored(u32, AccountId),
-        KnowledgeCreated(AccountId),
-        CommentCreated(AccountId),
-        ModelCreated(AccountId),
-        ModelOwnerTransfered(AccountId),
+        /// (sender, owner, app_id, document_id, document_type)
+        KnowledgeCreated(AccountId, AccountId, u32, Vec<u8>, DocumentType),
+        /// (sender, owner, app_id, comment_id, document_id)
+        CommentCreated(AccountId, AccountId, u32, Vec<u8>, Vec<u8>),
+        /// (app admin, app_id, comment_id, reclaimed reward, reward left unreclaimed)
+        CommentModerated(AccountId, u32, Vec<u8>, Balance, Balance),
+        /// (sender, owner, app_id, document_id, versions count)
+        DocumentRevised(AccountId, AccountId, u32, Vec<u8>, u32),
+        /// (sender, owner, app_id, document_id)
+        DocumentRetracted(AccountId, AccountId, u32, Vec<u8>),
+        /// (sender, owner, app_id, model_id, deposit)
+        ModelCreated(AccountId, AccountId, u32, Vec<u8>, Balance),
+        /// (old owner, new owner, app_id, model_id)
+        ModelOwnerTransfered(AccountId, AccountId, u32, Vec<u8>),
         CommodityTypeCreated(u32),
-        AppModelTotal(u32),
-        ModelCycleIncome(AccountId),
-        PowerSlashed(AccountId),
-        AppAdded(u32),
-        AppFinanced(u32),
+        /// (app_id, max models)
+        AppModelTotal(u32, u32),
+        /// (sender, app_id, cycle)
+        ModelCycleIncome(AccountId, u32, BlockNumber),
+        /// (owner, app_id, comment_id, cart_id, slashed power)
+        PowerSlashed(AccountId, u32, Vec<u8>, Vec<u8>, PowerSize),
+        /// (owner, app_id, cart_id, commodity power, owner account power)
+        PurchasePowerUpdated(AccountId, u32, Vec<u8>, PowerSize, PowerSize),
+        /// (app_id, admin, stake)
+        AppAdded(u32, AccountId, Balance),
+        AppSuspended(u32),
+        AppResumed(u32),
+        /// (app_id, stake owner, stake, is slashed)
+        AppDeregistered(u32, AccountId, Balance, bool),
+        /// (app_id, proposal_id, receiver, amount)
+        AppFinanced(u32, Vec<u8>, AccountId, Balance),
         LeaderBoardsCreated(BlockNumber, u32, Vec<u8>),
-        ModelDisputed(AccountId),
+        /// (owner, reporter, app_id, model_id, dispute_type, reporter reward)
+        ModelDisputed(
+            AccountId,
+            AccountId,
+            u32,
+            Vec<u8>,
+            ModelDisputeType,
+            Balance,
+        ),
         AppRedeemed(AccountId),
-        AppFinanceUserExchangeStart(AccountId, AccountId),
-        AppFinanceUserExchangeConfirmed(AccountId),
-        AppFinanceUserExchangeCompensated(AccountId),
-        AppCycleIncomeUserExchangeConfirmed(AccountId),
-        ModelIncomeRewarded(AccountId),
-        AppCycleIncomeRedeem(AccountId, AccountId),
-        AppIncomeUserExchangeCompensated(AccountId),
-        TechFundWithdrawed(AccountId),
-        ModelDepositAdded(AccountId),
+        /// (account, finance member, app_id, proposal_id, exchange amount)
+        AppFinanceUserExchangeStart(AccountId, AccountId, u32, Vec<u8>, Balance),
+        /// (account, app_id, proposal_id, burned amount)
+        AppFinanceUserExchangeConfirmed(AccountId, u32, Vec<u8>, Balance),
+        /// (account, app_id, proposal_id, compensated amount)
+        AppFinanceUserExchangeCompensated(AccountId, u32, Vec<u8>, Balance),
+        /// (account, app_id, cycle, burned amount)
+        AppCycleIncomeUserExchangeConfirmed(AccountId, u32, BlockNumber, Balance),
+        /// (account, app_id, model_id, cycle, reward)
+        ModelIncomeRewarded(AccountId, u32, Vec<u8>, BlockNumber, Balance),
+        /// (account, finance member, app_id, cycle, exchange amount)
+        AppCycleIncomeRedeem(AccountId, AccountId, u32, BlockNumber, Balance),
+        /// (account, app_id, cycle, compensated amount)
+        AppIncomeUserExchangeCompensated(AccountId, u32, BlockNumber, Balance),
+        /// (receiver, amount)
+        TechFundWithdrawed(AccountId, Balance),
+        /// (owner, app_id, model_id, amount)
+        ModelDepositAdded(AccountId, u32, Vec<u8>, Balance),
+        /// Model deposit lower than half required after lv1 slash (app_id, model_id, grace end block)
+        ModelPreBlackListed(u32, Vec<u8>, BlockNumber),
+        ModelPreBlackListRemoved(u32, Vec<u8>),
+        ModelDisabledByDispute(u32, Vec<u8>),
+        /// Model disabled by owner, deposit returned at cool-down end block (owner, app_id, model_id, end_block)
+        ModelRetiring(AccountId, u32, Vec<u8>, BlockNumber),
+        /// Retired model left deposit unreserved (owner, app_id, model_id, amount)
+        ModelRetired(AccountId, u32, Vec<u8>, Balance),
+        /// (account, app_id, comment_id)
+        ModelDisputeOpened(AccountId, u32, Vec<u8>),
+        ModelDisputeEvidenceSubmitted(AccountId, u32, Vec<u8>),
+        /// (voter, app_id, comment_id, approve)
+        ModelDisputeVoted(AccountId, u32, Vec<u8>, bool),
+        /// (app_id, comment_id, upheld)
+        ModelDisputeDecided(u32, Vec<u8>, bool),
+        ModelDisputeAppealed(AccountId, u32, Vec<u8>),
+        ModelDisputeResolved(u32, Vec<u8>, bool),
+        /// (item index in batch)
+        BatchItemSucceeded(u32),
+        BatchItemFailed(u32, dispatch::DispatchError),
+        /// (sender, succeeded count, failed count)
+        BatchSubmitted(AccountId, u32, u32),
+        LeaderBoardRewardClaimed(AccountId, Balance),
+        /// Unclaimed leader board reward returned to treasury (block, app_id, model_id, amount)
+        LeaderBoardRewardReleased(BlockNumber, u32, Vec<u8>, Balance),
+        /// New economic params take effect at (block)
+        EconomicParamsScheduled(BlockNumber),
+        EconomicParamsApplied(BlockNumber),
+        /// (app admin, app_id)
+        PowerWeightProfileProposed(AccountId, u32),
+        /// (app_id, is approved)
+        PowerWeightProfileReviewed(u32, bool),
+        /// (app_id, model_id, reward pool)
+        LeaderBoardRewardPoolSet(u32, Vec<u8>, Balance),
     }
 );
 
@@ -1234,6 +1959,8 @@ decl_error! {
         DocumentAlreadyExisted,
         ProductAlreadyExisted,
         CommentAlreadyExisted,
+        CommentAlreadyHidden,
+        CommentEffectNotRecorded,
         ModelAlreadyExisted,
         ModelTypeInvalid,
         ModelNotFoundOrDisabled,
@@ -1242,14 +1969,24 @@ decl_error! {
         NotAppAdmin,
         CommentNotFound,
         DocumentNotFound,
+        DocumentNotOwner,
+        DocumentRevisionInvalid,
+        DocumentVersionFull,
+        DocumentStillReferred,
         ProductNotFound,
         AppTypeInvalid,
         ReturnRateInvalid,
         AppAdminNotMatchUser,
         AppIdInvalid,
         AppIdReachMax,
+        AppAlreadySuspended,
+        AppNotSuspended,
+        AppHasModels,
+        AppHasOpenFinancedRounds,
         AppAlreadyFinanced,
         AppFinancedLastExchangeNotEnd,
+        AppFinancedExposureExceeded,
+        AppFinancedOpenRoundsFull,
         AppFinancedNotInvestor,
         AppFinancedExchangeRateTooLow,
         AppFinancedParamsInvalid,
@@ -1268,6 +2005,13 @@ decl_error! {
         AppRedeemTransactionIdRepeat,
         SignVerifyErrorUser,
         SignVerifyErrorAuth,
+        SignEnvelopeExpired,
+        SignNonceInvalid,
+        AuthSignsDuplicated,
+        AuthSignsNotEnough,
+        AuthSignsTooMany,
+        BatchSizeInvalid,
+        BatchItemAppIdMismatch,
         AuthIdentityNotAppKey,
         AuthIdentityNotTechMember,
         AuthIdentityNotFinanceMember,
@@ -1276,6 +2020,7 @@ decl_error! {
         ModelCycleRewardAlreadyExisted,
         ModelCycleRewardSlashed,
         ModelIncomeParamsTooLarge,
+        ModelExpertCountTooSmall,
         ModelIncomeNotInCollectingStage,
         ModelIncomeNotInRewardingStage,
         ModelIncomeNotInConfirmingStage,
@@ -1290,6 +2035,23 @@ decl_error! {
         TechFundAmountComputeError,
         CartIdInBalckList,
         NotFoundValidFinanceMember,
+        LeaderBoardRewardNotFound,
+        LeaderBoardRewardClaimEnded,
+        LeaderBoardRewardAlreadyClaimed,
+        LeaderBoardRewardNotFunded,
+        ModelDisputeInProgress,
+        NotLeaderBoardWinner,
+        ModelDisputeExisted,
+        ModelDisputeNotFound,
+        ModelDisputeStageInvalid,
+        ModelDisputeNotParty,
+        ModelDisputeNotVoter,
+        ModelDisputeAlreadyVoted,
+        ModelDisputeEvidenceFull,
+        ModelDisputeVoteFull,
+        EconomicParamsInvalid,
+        PowerWeightProfileInvalid,
+        PowerWeightProfileNotFound,
     }
 }
 
@@ -1347,28 +2109,31 @@ decl_module! {
 
         const ModelCreateDeposit: BalanceOf<T> = T::ModelCreateDeposit::get();
         const KptExchangeMinRate: Permill = T::KptExchangeMinRate::get();
-        const AppLeaderBoardInterval: T::BlockNumber = T::AppLeaderBoardInterval::get();
-        const ModelIncomeCyclePeriod: T::BlockNumber = T::ModelIncomeCyclePeriod::get();
-        const ModelIncomeCollectingPeriod: T::BlockNumber = T::ModelIncomeCollectingPeriod::get();
-        const ModelIncomeRewardingPeriod: T::BlockNumber = T::ModelIncomeRewardingPeriod::get();
-        const AppFinanceExchangePeriod: T::BlockNumber = T::AppFinanceExchangePeriod::get();
+        const AppLeaderBoardSnapshotsPerBlock: u32 = T::AppLeaderBoardSnapshotsPerBlock::get();
+        const DocumentCommentPoolMax: u32 = T::DocumentCommentPoolMax::get();
+        const SubmitBatchMax: u32 = T::SubmitBatchMax::get();
+        const LeaderBoardReward: BalanceOf<T> = T::LeaderBoardReward::get();
+        const LeaderBoardRewardClaimPeriod: T::BlockNumber = T::LeaderBoardRewardClaimPeriod::get();
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::create_model(auth_signs.len() as u32)]
         pub fn create_model(origin,
             client_params: ClientParamsCreateModel<T::Hash>,
             auth_params: AuthParamsCreateModel,
+            envelope: SignEnvelope<T::BlockNumber>,
 
             app_user_account: AuthAccountId,
-            app_user_sign: sr25519::Signature,
+            app_user_sign: AuthSignature,
 
-            auth_server: AuthAccountId,
-            auth_sign: sr25519::Signature
+            auth_signs: Vec<(AuthAccountId, AuthSignature)>
 
             )-> dispatch::DispatchResult {
             let who = ensure_signed(origin)?;
 
-            ensure!(Self::verify_sign(&app_user_account, app_user_sign, &client_params.encode()), Error::<T>::SignVerifyErrorUser);
-            ensure!(Self::verify_sign(&auth_server, auth_sign, &auth_params.encode()), Error::<T>::SignVerifyErrorAuth);
+            let client_buf = Self::sign_envelope_message(b"kp/create_model", &app_user_account, &envelope, &client_params.encode())?;
+            let auth_buf = Self::sign_envelope_message(b"kp/create_model", &app_user_account, &envelope, &auth_params.encode())?;
+            ensure!(Self::verify_sign(&app_user_account, app_user_sign, &client_buf), Error::<T>::SignVerifyErrorUser);
+            Self::verify_app_key_signs(client_params.app_id, &auth_signs, &auth_buf)?;
+            T::Membership::inc_sign_nonce(&app_user_account);
 
             let ClientParamsCreateModel {
                 app_id,
@@ -1384,9 +2149,6 @@ decl_module! {
 
             ensure!(T::Membership::is_valid_app(app_id), Error::<T>::AppIdInvalid);
 
-            // check if valid auth server
-            ensure!(T::Membership::is_valid_app_key(app_id, &Self::convert_account(&auth_server)), Error::<T>::AuthIdentityNotAppKey);
-
             let key = T::Hashing::hash_of(&(app_id, &model_id));
             ensure!(!<KPModelDataByIdHash<T>>::contains_key(&key), Error::<T>::ModelAlreadyExisted);
 
@@ -1432,28 +2194,35 @@ decl_module! {
             <KPModelDataByIdHash<T>>::insert(&key, &model);
             <AppModelCount>::insert(app_id, count + 1);
 
-            Self::deposit_event(RawEvent::ModelCreated(who));
+            // model board and its app board take automatic snapshot from now on
+            Self::schedule_leader_board(app_id, &model.model_id);
+            Self::schedule_leader_board(app_id, &vec![]);
+
+            Self::deposit_event(RawEvent::ModelCreated(who, user_account, app_id, model.model_id, value));
             Ok(())
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::model_owner_release()]
         pub fn model_owner_release(origin, params: ModelKeyParams,
+            envelope: SignEnvelope<T::BlockNumber>,
             app_user_account: AuthAccountId,
-            app_user_sign: sr25519::Signature,
+            app_user_sign: AuthSignature,
 
             auth_server: AuthAccountId,
-            auth_sign: sr25519::Signature) -> dispatch::DispatchResult {
+            auth_sign: AuthSignature) -> dispatch::DispatchResult {
             let _who = ensure_signed(origin)?;
 
-            let encode = params.encode();
+            let encode = Self::sign_envelope_message(b"kp/model_owner_release", &app_user_account, &envelope, &params.encode())?;
             ensure!(Self::verify_sign(&app_user_account, app_user_sign, &encode), Error::<T>::SignVerifyErrorUser);
             ensure!(Self::verify_sign(&auth_server, auth_sign, &encode), Error::<T>::SignVerifyErrorAuth);
+            T::Membership::inc_sign_nonce(&app_user_account);
 
             let ModelKeyParams {
                 app_id,
                 model_id
             } = params;
 
+            ensure!(T::Membership::is_valid_app(app_id), Error::<T>::AppIdInvalid);
             let owner = Self::convert_account(&app_user_account);
             ensure!(T::Membership::is_model_creator(&owner, app_id, &model_id),  Error::<T>::NotModelCreator);
             // check if valid auth server
@@ -1479,13 +2248,14 @@ decl_module! {
                 model.owner = auth_server;
             });
 
-            Self::deposit_event(RawEvent::ModelOwnerTransfered(owner));
+            Self::deposit_event(RawEvent::ModelOwnerTransfered(owner, admin, app_id, model_id));
             Ok(())
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::add_model_deposit()]
         pub fn add_model_deposit(origin, app_id: u32, model_id: Vec<u8>, amount: BalanceOf<T>) -> dispatch::DispatchResult {
             let who = ensure_signed(origin)?;
+            ensure!(T::Membership::is_valid_app(app_id), Error::<T>::AppIdInvalid);
 
             // make sure who is model creator
             ensure!(T::Membership::is_model_creator(&who, app_id, &model_id),  Error::<T>::NotModelCreator);
@@ -1498,26 +2268,62 @@ decl_module! {
                 *value += amount;
             });
 
-            Self::deposit_event(RawEvent::ModelDepositAdded(who));
+            // deposit recovered during grace period, model is safe now
+            if Self::is_model_deposit_enough(app_id, &model_id) {
+                let mut pre_black_list = <ModelPreBlackList<T>>::get();
+                let len = pre_black_list.len();
+                pre_black_list.retain(|x| !(x.0 == app_id && x.1 == model_id));
+                if pre_black_list.len() < len {
+                    <ModelPreBlackList<T>>::put(pre_black_list);
+                    Self::deposit_event(RawEvent::ModelPreBlackListRemoved(app_id, model_id.clone()));
+                }
+            }
+
+            Self::deposit_event(RawEvent::ModelDepositAdded(who, app_id, model_id, amount));
+            Ok(())
+        }
+
+        #[weight = T::WeightInfo::retire_model()]
+        pub fn retire_model(origin, app_id: u32, model_id: Vec<u8>) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(T::Membership::is_valid_app(app_id), Error::<T>::AppIdInvalid);
+
+            // make sure who is model creator
+            ensure!(T::Membership::is_model_creator(&who, app_id, &model_id),  Error::<T>::NotModelCreator);
+            ensure!(Self::is_valid_model(app_id, &model_id), Error::<T>::ModelNotFoundOrDisabled);
+
+            let key = T::Hashing::hash_of(&(app_id, &model_id));
+            ensure!(<ModelOpenDisputeCount<T>>::get(&key) == 0, Error::<T>::ModelDisputeInProgress);
+            <KPModelDataByIdHash<T>>::mutate(&key, |model| {
+                model.status = ModelStatus::DISABLED;
+            });
+
+            // deposit is returned after cool-down, model can still be disputed before it
+            let end_block = <system::Module<T>>::block_number() + T::ModelRetireCoolDown::get();
+            <ModelRetireRecords<T>>::insert(&key, (who.clone(), end_block));
+            <ModelRetireExpiry<T>>::append(end_block, (app_id, model_id.clone()));
+
+            Self::deposit_event(RawEvent::ModelRetiring(who, app_id, model_id, end_block));
             Ok(())
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::create_product_publish_document(auth_signs.len() as u32)]
         pub fn create_product_publish_document(origin,
             client_params: ClientParamsCreatePublishDoc<T::Hash>,
+            envelope: SignEnvelope<T::BlockNumber>,
 
             app_user_account: AuthAccountId,
-            app_user_sign: sr25519::Signature,
+            app_user_sign: AuthSignature,
 
-            auth_server: AuthAccountId,
-            auth_sign: sr25519::Signature) -> dispatch::DispatchResult {
+            auth_signs: Vec<(AuthAccountId, AuthSignature)>) -> dispatch::DispatchResult {
 
             // Check it was signed and get the signer. See also: ensure_root and ensure_none
             let who = ensure_signed(origin)?;
 
-            let encode = client_params.encode();
+            let encode = Self::sign_envelope_message(b"kp/create_product_publish_document", &app_user_account, &envelope, &client_params.encode())?;
             ensure!(Self::verify_sign(&app_user_account, app_user_sign, &encode), Error::<T>::SignVerifyErrorUser);
-            ensure!(Self::verify_sign(&auth_server, auth_sign, &encode), Error::<T>::SignVerifyErrorAuth);
+            Self::verify_app_key_signs(client_params.app_id, &auth_signs, &encode)?;
+            T::Membership::inc_sign_nonce(&app_user_account);
 
             let ClientParamsCreatePublishDoc {
                 app_id,
@@ -1531,11 +2337,8 @@ decl_module! {
 
             ensure!(T::Membership::is_valid_app(app_id), Error::<T>::AppIdInvalid);
 
-            // check if valid auth server
-            ensure!(T::Membership::is_valid_app_key(app_id, &Self::convert_account(&auth_server)), Error::<T>::AuthIdentityNotAppKey);
-
             let doc_key_hash = T::Hashing::hash_of(&(app_id, &document_id));
-            ensure!(!<KPDocumentDataByIdHash<T>>::contains_key(&doc_key_hash), Error::<T>::DocumentAlreadyExisted);
+            ensure!(!Self::is_document_id_used(&doc_key_hash), Error::<T>::DocumentAlreadyExisted);
 
             // extract percent rates data
 
@@ -1569,186 +2372,74 @@ decl_module! {
             // create product id -> document id record
             <KPDocumentProductIndexByIdHash<T>>::insert(&product_key_hash, &document_id);
 
-            Self::deposit_event(RawEvent::KnowledgeCreated(who));
+            Self::deposit_event(RawEvent::KnowledgeCreated(who, Self::convert_account(&doc.owner), app_id, document_id, doc.document_type));
             Ok(())
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::create_product_identify_document(T::AppLeaderBoardMaxPos::get(), auth_signs.len() as u32)]
         pub fn create_product_identify_document(origin,
             client_params: ClientParamsCreateIdentifyDoc<T::Hash>,
+            envelope: SignEnvelope<T::BlockNumber>,
 
             app_user_account: AuthAccountId,
-            app_user_sign: sr25519::Signature,
+            app_user_sign: AuthSignature,
 
-            auth_server: AuthAccountId,
-            auth_sign: sr25519::Signature) -> dispatch::DispatchResult {
+            auth_signs: Vec<(AuthAccountId, AuthSignature)>) -> dispatch::DispatchResultWithPostInfo {
 
             let who = ensure_signed(origin)?;
 
-            let encode = client_params.encode();
+            let encode = Self::sign_envelope_message(b"kp/create_product_identify_document", &app_user_account, &envelope, &client_params.encode())?;
             ensure!(Self::verify_sign(&app_user_account, app_user_sign, &encode), Error::<T>::SignVerifyErrorUser);
-            ensure!(Self::verify_sign(&auth_server, auth_sign, &encode), Error::<T>::SignVerifyErrorAuth);
+            Self::verify_app_key_signs(client_params.app_id, &auth_signs, &encode)?;
+            T::Membership::inc_sign_nonce(&app_user_account);
 
-            let ClientParamsCreateIdentifyDoc {
-                app_id,
-                document_id,
-                product_id,
-                content_hash,
-                goods_price,
-                ident_rate,
-                ident_consistence,
-                seller_consistence,
-                cart_id,
-            } = client_params;
+            let (app_id, document_id) = (client_params.app_id, client_params.document_id.clone());
+            Self::add_identify_document(&who, &app_user_account, client_params)?;
 
-            ensure!(T::Membership::is_valid_app(app_id), Error::<T>::AppIdInvalid);
+            let board_len = Self::document_leader_board_len(app_id, &document_id);
+            Ok(Some(T::WeightInfo::create_product_identify_document(board_len, auth_signs.len() as u32)).into())
+        }
 
-            // check if valid auth server
-            ensure!(T::Membership::is_valid_app_key(app_id, &Self::convert_account(&auth_server)), Error::<T>::AuthIdentityNotAppKey);
+        #[weight = T::WeightInfo::create_product_try_document(T::AppLeaderBoardMaxPos::get(), auth_signs.len() as u32)]
+        pub fn create_product_try_document(origin,
+            client_params: ClientParamsCreateTryDoc<T::Hash>,
+            envelope: SignEnvelope<T::BlockNumber>,
 
-            let doc_key_hash = T::Hashing::hash_of(&(app_id, &document_id));
-            ensure!(!<KPDocumentDataByIdHash<T>>::contains_key(&doc_key_hash), Error::<T>::DocumentAlreadyExisted);
+            app_user_account: AuthAccountId,
+            app_user_sign: AuthSignature,
 
-            let product_key_hash = T::Hashing::hash_of(&(app_id, &product_id));
-            ensure!(<KPDocumentProductIndexByIdHash<T>>::contains_key(&product_key_hash), Error::<T>::ProductNotFound);
+            auth_signs: Vec<(AuthAccountId, AuthSignature)>) -> dispatch::DispatchResultWithPostInfo {
 
-            let key = T::Hashing::hash_of(&(app_id, &cart_id));
-            ensure!(!<KPCartProductIdentifyIndexByIdHash<T>>::contains_key(&key), Error::<T>::DocumentIdentifyAlreadyExisted);
+            let who = ensure_signed(origin)?;
 
-            let model_id = Self::get_model_id_from_product(app_id, &product_id).unwrap_or_default();
+            let encode = Self::sign_envelope_message(b"kp/create_product_try_document", &app_user_account, &envelope, &client_params.encode())?;
+            ensure!(Self::verify_sign(&app_user_account, app_user_sign, &encode), Error::<T>::SignVerifyErrorUser);
+            Self::verify_app_key_signs(client_params.app_id, &auth_signs, &encode)?;
+            T::Membership::inc_sign_nonce(&app_user_account);
 
-            // create doc
-            let doc = KPDocumentData {
-                sender: who.clone(),
-                owner: app_user_account.clone(),
-                document_type: DocumentType::ProductIdentify,
-                app_id,
-                document_id: document_id.clone(),
-                model_id,
-                product_id,
-                content_hash,
-                document_data: DocumentSpecificData::ProductIdentify(KPProductIdentifyData {goods_price, ident_rate, ident_consistence, seller_consistence, cart_id: cart_id.clone()}),
-                ..Default::default()
-            };
-
-            // process content power
-            Self::process_document_content_power(&doc);
-            Self::process_commodity_power(&doc);
-
-            // create cartid -> product identify document id record
-            <KPCartProductIdentifyIndexByIdHash<T>>::insert(&key, &document_id);
-
-            // create document record
-            <KPDocumentDataByIdHash<T>>::insert(&doc_key_hash, &doc);
-
-            Self::increase_commodity_count(
-                app_id,
-                &doc.model_id,
-                &cart_id,
-                DocumentType::ProductIdentify,
-                &Self::convert_account(&doc.owner),
-            );
-
-            Self::deposit_event(RawEvent::KnowledgeCreated(who));
-            Ok(())
-        }
-
-        #[weight = 0]
-        pub fn create_product_try_document(origin,
-            client_params: ClientParamsCreateTryDoc<T::Hash>,
-
-            app_user_account: AuthAccountId,
-            app_user_sign: sr25519::Signature,
-
-            auth_server: AuthAccountId,
-            auth_sign: sr25519::Signature) -> dispatch::DispatchResult {
-
-            let who = ensure_signed(origin)?;
-
-            let encode = client_params.encode();
-            ensure!(Self::verify_sign(&app_user_account, app_user_sign, &encode), Error::<T>::SignVerifyErrorUser);
-            ensure!(Self::verify_sign(&auth_server, auth_sign, &encode), Error::<T>::SignVerifyErrorAuth);
-
-            let ClientParamsCreateTryDoc {
-                app_id,
-                document_id,
-                product_id,
-                content_hash,
-                goods_price,
-                offset_rate,
-                true_rate,
-                seller_consistence,
-                cart_id,
-            } = client_params;
-
-            ensure!(T::Membership::is_valid_app(app_id), Error::<T>::AppIdInvalid);
-
-            // check if valid auth server
-            ensure!(T::Membership::is_valid_app_key(app_id, &Self::convert_account(&auth_server)), Error::<T>::AuthIdentityNotAppKey);
-
-            let doc_key_hash = T::Hashing::hash_of(&(app_id, &document_id));
-            ensure!(!<KPDocumentDataByIdHash<T>>::contains_key(&doc_key_hash), Error::<T>::DocumentAlreadyExisted);
-
-            let product_key_hash = T::Hashing::hash_of(&(app_id, &product_id));
-            ensure!(<KPDocumentProductIndexByIdHash<T>>::contains_key(&product_key_hash), Error::<T>::ProductNotFound);
-
-            let key = T::Hashing::hash_of(&(app_id, &cart_id));
-            ensure!(!<KPCartProductTryIndexByIdHash<T>>::contains_key(&key), Error::<T>::DocumentTryAlreadyExisted);
-
-            let model_id = Self::get_model_id_from_product(app_id, &product_id).unwrap_or_default();
-
-            // create doc
-            let doc = KPDocumentData {
-                sender: who.clone(),
-                owner: app_user_account.clone(),
-                document_type: DocumentType::ProductTry,
-                app_id,
-                document_id: document_id.clone(),
-                model_id,
-                product_id,
-                content_hash,
-                document_data: DocumentSpecificData::ProductTry(KPProductTryData {goods_price, offset_rate, true_rate, seller_consistence, cart_id: cart_id.clone()}),
-                ..Default::default()
-            };
-
-            // process content power
-            Self::process_document_content_power(&doc);
-            Self::process_commodity_power(&doc);
-
-            // create cartid -> product identify document id record
-
-            <KPCartProductTryIndexByIdHash<T>>::insert(&key, &document_id);
-
-            // create document record
-            <KPDocumentDataByIdHash<T>>::insert(&doc_key_hash, &doc);
-
-            Self::increase_commodity_count(
-                app_id,
-                &doc.model_id,
-                &cart_id,
-                DocumentType::ProductTry,
-                &Self::convert_account(&doc.owner),
-            );
+            let (app_id, document_id) = (client_params.app_id, client_params.document_id.clone());
+            Self::add_try_document(&who, &app_user_account, client_params)?;
 
-            Self::deposit_event(RawEvent::KnowledgeCreated(who));
-            Ok(())
+            let board_len = Self::document_leader_board_len(app_id, &document_id);
+            Ok(Some(T::WeightInfo::create_product_try_document(board_len, auth_signs.len() as u32)).into())
         }
 
-         #[weight = 0]
+        #[weight = T::WeightInfo::create_product_choose_document(auth_signs.len() as u32)]
         pub fn create_product_choose_document(origin,
             client_params: ClientParamsCreateChooseDoc<T::Hash>,
+            envelope: SignEnvelope<T::BlockNumber>,
 
             app_user_account: AuthAccountId,
-            app_user_sign: sr25519::Signature,
+            app_user_sign: AuthSignature,
 
-            auth_server: AuthAccountId,
-            auth_sign: sr25519::Signature) -> dispatch::DispatchResult {
+            auth_signs: Vec<(AuthAccountId, AuthSignature)>) -> dispatch::DispatchResult {
 
             let who = ensure_signed(origin)?;
 
-            let encode = client_params.encode();
+            let encode = Self::sign_envelope_message(b"kp/create_product_choose_document", &app_user_account, &envelope, &client_params.encode())?;
             ensure!(Self::verify_sign(&app_user_account, app_user_sign, &encode), Error::<T>::SignVerifyErrorUser);
-            ensure!(Self::verify_sign(&auth_server, auth_sign, &encode), Error::<T>::SignVerifyErrorAuth);
+            Self::verify_app_key_signs(client_params.app_id, &auth_signs, &encode)?;
+            T::Membership::inc_sign_nonce(&app_user_account);
 
             let ClientParamsCreateChooseDoc {
                 app_id,
@@ -1762,12 +2453,9 @@ decl_module! {
 
             ensure!(T::Membership::is_valid_app(app_id), Error::<T>::AppIdInvalid);
 
-            // check if valid auth server
-            ensure!(T::Membership::is_valid_app_key(app_id, &Self::convert_account(&auth_server)), Error::<T>::AuthIdentityNotAppKey);
-
             let doc_key_hash = T::Hashing::hash_of(&(app_id, &document_id));
 
-            ensure!(!<KPDocumentDataByIdHash<T>>::contains_key(&doc_key_hash), Error::<T>::DocumentAlreadyExisted);
+            ensure!(!Self::is_document_id_used(&doc_key_hash), Error::<T>::DocumentAlreadyExisted);
 
             // create doc
             let doc = KPDocumentData {
@@ -1790,25 +2478,26 @@ decl_module! {
             // create document record
             <KPDocumentDataByIdHash<T>>::insert(&doc_key_hash, &doc);
 
-            Self::deposit_event(RawEvent::KnowledgeCreated(who));
+            Self::deposit_event(RawEvent::KnowledgeCreated(who, Self::convert_account(&doc.owner), app_id, document_id, doc.document_type));
             Ok(())
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::create_model_create_document(auth_signs.len() as u32)]
         pub fn create_model_create_document(origin,
             client_params: ClientParamsCreateModelDoc<T::Hash>,
+            envelope: SignEnvelope<T::BlockNumber>,
 
             app_user_account: AuthAccountId,
-            app_user_sign: sr25519::Signature,
+            app_user_sign: AuthSignature,
 
-            auth_server: AuthAccountId,
-            auth_sign: sr25519::Signature) -> dispatch::DispatchResult {
+            auth_signs: Vec<(AuthAccountId, AuthSignature)>) -> dispatch::DispatchResult {
 
             let who = ensure_signed(origin)?;
 
-            let encode = client_params.encode();
+            let encode = Self::sign_envelope_message(b"kp/create_model_create_document", &app_user_account, &envelope, &client_params.encode())?;
             ensure!(Self::verify_sign(&app_user_account, app_user_sign, &encode), Error::<T>::SignVerifyErrorUser);
-            ensure!(Self::verify_sign(&auth_server, auth_sign, &encode), Error::<T>::SignVerifyErrorAuth);
+            Self::verify_app_key_signs(client_params.app_id, &auth_signs, &encode)?;
+            T::Membership::inc_sign_nonce(&app_user_account);
 
             let ClientParamsCreateModelDoc {
                 app_id,
@@ -1822,12 +2511,9 @@ decl_module! {
 
             ensure!(T::Membership::is_valid_app(app_id), Error::<T>::AppIdInvalid);
 
-            // check if valid auth server
-            ensure!(T::Membership::is_valid_app_key(app_id, &Self::convert_account(&auth_server)), Error::<T>::AuthIdentityNotAppKey);
-
             let doc_key_hash = T::Hashing::hash_of(&(app_id, &document_id));
 
-            ensure!(!<KPDocumentDataByIdHash<T>>::contains_key(&doc_key_hash), Error::<T>::DocumentAlreadyExisted);
+            ensure!(!Self::is_document_id_used(&doc_key_hash), Error::<T>::DocumentAlreadyExisted);
 
             ensure!(Self::is_valid_model(app_id, &model_id), Error::<T>::ModelNotFoundOrDisabled);
 
@@ -1852,73 +2538,166 @@ decl_module! {
             // create document record
             <KPDocumentDataByIdHash<T>>::insert(&doc_key_hash, &doc);
 
-            Self::deposit_event(RawEvent::KnowledgeCreated(who));
+            Self::deposit_event(RawEvent::KnowledgeCreated(who, Self::convert_account(&doc.owner), app_id, document_id, doc.document_type));
             Ok(())
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::create_comment(T::DocumentCommentPoolMax::get(), T::AppLeaderBoardMaxPos::get(), auth_signs.len() as u32)]
         pub fn create_comment(origin,
             comment_data: CommentData<T::Hash>,
+            envelope: SignEnvelope<T::BlockNumber>,
 
             app_user_account: AuthAccountId,
-            app_user_sign: sr25519::Signature,
+            app_user_sign: AuthSignature,
 
-            auth_server: AuthAccountId,
-            auth_sign: sr25519::Signature) -> dispatch::DispatchResult {
+            auth_signs: Vec<(AuthAccountId, AuthSignature)>) -> dispatch::DispatchResultWithPostInfo {
 
             let who = ensure_signed(origin)?;
 
-            let buf = comment_data.encode();
+            let buf = Self::sign_envelope_message(b"kp/create_comment", &app_user_account, &envelope, &comment_data.encode())?;
             ensure!(Self::verify_sign(&app_user_account, app_user_sign, &buf), Error::<T>::SignVerifyErrorUser);
-            ensure!(Self::verify_sign(&auth_server, auth_sign, &buf), Error::<T>::SignVerifyErrorAuth);
+            Self::verify_app_key_signs(comment_data.app_id, &auth_signs, &buf)?;
+            T::Membership::inc_sign_nonce(&app_user_account);
 
-            let CommentData {
-              app_id,
-              document_id,
-              comment_id,
-              comment_hash,
-              comment_fee,
-              comment_trend,
-            } = comment_data;
+            let (app_id, document_id) = (comment_data.app_id, comment_data.document_id.clone());
+            Self::add_comment(&who, &app_user_account, comment_data)?;
 
-            ensure!(T::Membership::is_valid_app(app_id), Error::<T>::AppIdInvalid);
+            Ok(Some(T::WeightInfo::create_comment(
+                Self::comment_pool_len(app_id, &document_id),
+                Self::document_leader_board_len(app_id, &document_id),
+                auth_signs.len() as u32,
+            )).into())
+        }
 
-            // check if valid auth server
-            ensure!(T::Membership::is_valid_app_key(app_id, &Self::convert_account(&auth_server)), Error::<T>::AuthIdentityNotAppKey);
+        #[weight = <Module<T>>::revise_document_weight(client_params.app_id, &client_params.document_id, T::AppLeaderBoardMaxPos::get(), auth_signs.len() as u32)]
+        pub fn revise_document(origin,
+            client_params: ClientParamsReviseDoc<T::Hash>,
+            envelope: SignEnvelope<T::BlockNumber>,
+
+            app_user_account: AuthAccountId,
+            app_user_sign: AuthSignature,
+
+            auth_signs: Vec<(AuthAccountId, AuthSignature)>) -> dispatch::DispatchResultWithPostInfo {
+
+            let who = ensure_signed(origin)?;
 
-            // TODO: check platform & expert member role
+            let encode = Self::sign_envelope_message(b"kp/revise_document", &app_user_account, &envelope, &client_params.encode())?;
+            ensure!(Self::verify_sign(&app_user_account, app_user_sign, &encode), Error::<T>::SignVerifyErrorUser);
+            Self::verify_app_key_signs(client_params.app_id, &auth_signs, &encode)?;
+            T::Membership::inc_sign_nonce(&app_user_account);
+
+            let (app_id, document_id) = (client_params.app_id, client_params.document_id.clone());
+            Self::revise_document_content(&who, &app_user_account, client_params)?;
+
+            let board_len = Self::document_leader_board_len(app_id, &document_id);
+            Ok(Some(Self::revise_document_weight(app_id, &document_id, board_len, auth_signs.len() as u32)).into())
+        }
+
+        #[weight = T::WeightInfo::retract_document(T::AppLeaderBoardMaxPos::get(), auth_signs.len() as u32)]
+        pub fn retract_document(origin,
+            client_params: ClientParamsRetractDoc,
+            envelope: SignEnvelope<T::BlockNumber>,
+
+            app_user_account: AuthAccountId,
+            app_user_sign: AuthSignature,
+
+            auth_signs: Vec<(AuthAccountId, AuthSignature)>) -> dispatch::DispatchResultWithPostInfo {
+
+            let who = ensure_signed(origin)?;
+
+            let encode = Self::sign_envelope_message(b"kp/retract_document", &app_user_account, &envelope, &client_params.encode())?;
+            ensure!(Self::verify_sign(&app_user_account, app_user_sign, &encode), Error::<T>::SignVerifyErrorUser);
+            Self::verify_app_key_signs(client_params.app_id, &auth_signs, &encode)?;
+            T::Membership::inc_sign_nonce(&app_user_account);
+
+            // document is removed by retract, measure its boards first
+            let board_len = Self::document_leader_board_len(client_params.app_id, &client_params.document_id);
+            Self::retract_document_content(&who, &app_user_account, client_params)?;
+
+            Ok(Some(T::WeightInfo::retract_document(board_len, auth_signs.len() as u32)).into())
+        }
+
+        /// App admin hides an abusive comment, power and reward it brought are reversed.
+        #[weight = T::WeightInfo::moderate_comment(T::DocumentCommentPoolMax::get(), T::AppLeaderBoardMaxPos::get())]
+        pub fn moderate_comment(origin, app_id: u32, comment_id: Vec<u8>) -> dispatch::DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            ensure!(T::Membership::is_valid_app(app_id), Error::<T>::AppIdInvalid);
+            ensure!(T::Membership::is_app_admin(&who, app_id), Error::<T>::NotAppAdmin);
 
-            // make sure this comment not exist
             let key = T::Hashing::hash_of(&(app_id, &comment_id));
-            ensure!(!<KPCommentDataByIdHash<T>>::contains_key(&key), Error::<T>::CommentAlreadyExisted);
+            ensure!(<KPCommentDataByIdHash<T>>::contains_key(&key), Error::<T>::CommentNotFound);
+            ensure!(!<KPCommentHidden<T>>::contains_key(&key), Error::<T>::CommentAlreadyHidden);
+            // comments created before effects were recorded can not be reversed
+            ensure!(<KPCommentEffects<T>>::contains_key(&key), Error::<T>::CommentEffectNotRecorded);
+
+            let comment = <KPCommentDataByIdHash<T>>::get(&key);
+            let effect = <KPCommentEffects<T>>::take(&key);
+            let reclaimed = Self::reverse_comment_effect(&comment, &effect);
+            let shortfall = effect.reward.saturating_sub(reclaimed);
+            if shortfall > 0u32.into() {
+                <KPCommentRewardShortfall<T>>::insert(&key, shortfall);
+            }
 
-            let doc_key_hash = T::Hashing::hash_of(&(app_id, &document_id));
+            <KPCommentHidden<T>>::insert(&key, <system::Module<T>>::block_number());
 
-            let comment = KPCommentData {
-                sender: who.clone(),
-                owner: app_user_account.clone(),
-                app_id,
-                document_id: document_id.clone(),
-                comment_id: comment_id.clone(),
-                comment_fee,
-                comment_trend,
-                comment_hash,
-            };
+            Self::deposit_event(RawEvent::CommentModerated(who, app_id, comment_id, reclaimed, shortfall));
+            Ok(Some(T::WeightInfo::moderate_comment(
+                Self::comment_pool_len(app_id, &comment.document_id),
+                Self::document_leader_board_len(app_id, &comment.document_id),
+            )).into())
+        }
 
-            Self::process_comment_power(&comment);
+        /// Submit documents and comments of one app, auth servers sign the batch once.
+        /// Failed items are rolled back and reported by event, others still apply.
+        #[weight = <Module<T>>::submit_batch_weight(&items, auth_signs.len() as u32)]
+        pub fn submit_batch(origin,
+            app_id: u32,
+            items: Vec<BatchItem<T::Hash, T::BlockNumber>>,
+            envelope: SignEnvelope<T::BlockNumber>,
 
-            // read out related document, trigger account power update
-            let doc = Self::kp_document_data_by_idhash(&doc_key_hash);
-            Self::process_commodity_power(&doc);
+            auth_signs: Vec<(AuthAccountId, AuthSignature)>) -> dispatch::DispatchResult {
+
+            let who = ensure_signed(origin)?;
 
-            // create comment record
-            <KPCommentDataByIdHash<T>>::insert(&key, &comment);
+            ensure!(!items.is_empty() && items.len() <= T::SubmitBatchMax::get() as usize, Error::<T>::BatchSizeInvalid);
+            ensure!(T::Membership::is_valid_app(app_id), Error::<T>::AppIdInvalid);
+
+            // batch envelope nonce belongs to the first auth server
+            let auth_server = auth_signs.first().map(|(auth_server, _)| auth_server.clone()).ok_or(Error::<T>::AuthSignsNotEnough)?;
+            let buf = Self::sign_envelope_message(b"kp/submit_batch", &auth_server, &envelope, &(app_id, &items).encode())?;
+            Self::verify_app_key_signs(app_id, &auth_signs, &buf)?;
+            T::Membership::inc_sign_nonce(&auth_server);
+
+            let mut succeeded: u32 = 0;
+            let mut failed: u32 = 0;
+            for (index, item) in items.into_iter().enumerate() {
+                let result = with_transaction(|| {
+                    let result = Self::submit_batch_item(&who, app_id, item);
+                    if result.is_ok() {
+                        TransactionOutcome::Commit(result)
+                    } else {
+                        TransactionOutcome::Rollback(result)
+                    }
+                });
+
+                match result {
+                    Ok(_) => {
+                        succeeded += 1;
+                        Self::deposit_event(RawEvent::BatchItemSucceeded(index as u32));
+                    },
+                    Err(e) => {
+                        failed += 1;
+                        Self::deposit_event(RawEvent::BatchItemFailed(index as u32, e));
+                    },
+                }
+            }
 
-            Self::deposit_event(RawEvent::CommentCreated(who));
+            Self::deposit_event(RawEvent::BatchSubmitted(who, succeeded, failed));
             Ok(())
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::create_commodity_type()]
         pub fn create_commodity_type(origin, type_id: u32, type_desc: Vec<u8>) -> dispatch::DispatchResult {
             ensure_root(origin)?;
             ensure!(!<CommodityTypeMap>::contains_key(type_id), Error::<T>::CommodityTypeExisted);
@@ -1945,28 +2724,31 @@ decl_module! {
             }
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::set_app_model_total()]
         pub fn set_app_model_total(origin, app_id: u32, total: u32) -> dispatch::DispatchResult {
             ensure_root(origin)?;
+            ensure!(T::Membership::is_valid_app(app_id), Error::<T>::AppIdInvalid);
 
             <AppModelTotalConfig>::insert(app_id, total);
 
-            Self::deposit_event(RawEvent::AppModelTotal(total));
+            Self::deposit_event(RawEvent::AppModelTotal(app_id, total));
             Ok(())
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::set_model_income(params.incomes.len() as u32)]
         pub fn set_model_income(origin, params: ModelIncomeCollectingParam,
+            envelope: SignEnvelope<T::BlockNumber>,
             user_key: AuthAccountId,
-            user_sign: sr25519::Signature,
+            user_sign: AuthSignature,
             auth_key: AuthAccountId,
-            auth_sign: sr25519::Signature) -> dispatch::DispatchResult {
+            auth_sign: AuthSignature) -> dispatch::DispatchResult {
             let who = ensure_signed(origin)?;
             ensure!(T::Membership::is_finance_member(&Self::convert_account(&auth_key)), Error::<T>::AuthIdentityNotFinanceMember);
 
-            let buf = params.encode();
+            let buf = Self::sign_envelope_message(b"kp/set_model_income", &user_key, &envelope, &params.encode())?;
             ensure!(Self::verify_sign(&user_key, user_sign, &buf), Error::<T>::SignVerifyErrorUser);
             ensure!(Self::verify_sign(&auth_key, auth_sign, &buf), Error::<T>::SignVerifyErrorAuth);
+            T::Membership::inc_sign_nonce(&user_key);
 
             let ModelIncomeCollectingParam {
                 app_id,
@@ -1974,8 +2756,9 @@ decl_module! {
                 incomes,
             } = params;
 
+            ensure!(T::Membership::is_valid_app(app_id), Error::<T>::AppIdInvalid);
             ensure!(T::Membership::is_app_admin(&Self::convert_account(&user_key), app_id), Error::<T>::NotAppAdmin);
-            ensure!(incomes.len() <= 100, Error::<T>::ModelIncomeParamsTooLarge);
+            ensure!(incomes.len() <= T::ModelIncomeParamsMax::get() as usize, Error::<T>::ModelIncomeParamsTooLarge);
 
             let block = <system::Module<T>>::block_number();
             ensure!(Self::model_income_stage(block).0 == ModelIncomeStage::COLLECTING, Error::<T>::ModelIncomeNotInCollectingStage);
@@ -2020,14 +2803,17 @@ decl_module! {
                 });
             }
 
-            Self::deposit_event(RawEvent::ModelCycleIncome(who));
+            Self::deposit_event(RawEvent::ModelCycleIncome(who, app_id, cycle_index));
             Ok(())
         }
 
-        #[weight = 0]
-        pub fn request_model_reward(origin, app_id: u32, model_id: Vec<u8>) -> dispatch::DispatchResult {
+        /// `expert_count` is the number of experts sharing the reward, it bounds the call weight.
+        #[weight = T::WeightInfo::request_model_reward(*expert_count)]
+        #[transactional]
+        pub fn request_model_reward(origin, app_id: u32, model_id: Vec<u8>, expert_count: u32) -> dispatch::DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
 
+            ensure!(T::Membership::is_valid_app(app_id), Error::<T>::AppIdInvalid);
             ensure!(Self::is_valid_model(app_id, &model_id), Error::<T>::ModelNotFoundOrDisabled);
             // make sure who is creaor of this model id
             ensure!(T::Membership::is_model_creator(&who, app_id, &model_id),  Error::<T>::NotModelCreator);
@@ -2045,7 +2831,7 @@ decl_module! {
             }
 
             // now compute reward
-            let total_reward = T::ModelCycleIncomeRewardTotal::get();
+            let total_reward = Self::active_economic_params().model_cycle_income_reward_total;
 
             let cycle_income_total = <ModelCycleIncomeTotal<T>>::get(cycle_index);
             ensure!(cycle_income_total > 0, Error::<T>::ModelCycleIncomeTotalZero);
@@ -2056,14 +2842,34 @@ decl_module! {
             let per = Permill::from_rational_approximation(cycle_income, cycle_income_total);
             let reward = per * total_reward;
 
-            // transfer now
+            // experts get their profit rate of reward, creator gets the rest,
+            // rates summing over 100 percent are paid in order until reward runs out
+            let experts = T::Membership::model_expert_profit_rates(app_id, &model_id);
+            let actual_expert_count = experts.len() as u32;
+            ensure!(actual_expert_count <= expert_count, Error::<T>::ModelExpertCountTooSmall);
+
+            let mut payouts: Vec<(T::AccountId, BalanceOf<T>)> = vec![];
+            let mut creator_reward = reward;
+            for (expert, rate) in experts {
+                let expert_reward = min(Percent::from_percent(min(rate, 100) as u8) * reward, creator_reward);
+                creator_reward = creator_reward.saturating_sub(expert_reward);
+                payouts.push((expert, expert_reward));
+            }
+            payouts.insert(0, (who, creator_reward));
+
+            // transfer now, any failed transfer reverts the whole call
             let treasury_account: T::AccountId = T::ModTreasuryModuleId::get().into_account();
-            T::Currency::transfer(
-                &treasury_account,
-                &who,
-                reward,
-                KeepAlive,
-            )?;
+            for (account, amount) in payouts.iter() {
+                if amount.is_zero() {
+                    continue;
+                }
+                T::Currency::transfer(
+                    &treasury_account,
+                    account,
+                    *amount,
+                    KeepAlive,
+                )?;
+            }
 
             // update global total reward
             let total = <ModelIncomeRewardTotal<T>>::get() + reward;
@@ -2073,31 +2879,37 @@ decl_module! {
             <ModelCycleIncomeRewardRecords<T>>::insert(cycle_index, &sub_key, reward);
 
             <ModelCycleIncomeRewardStore<T>>::mutate(cycle_index, |store| {
-                store.push(ModelCycleIncomeReward {
-                    account: who.clone(),
-                    app_id,
-                    model_id: model_id.clone(),
-                    reward
-                })
+                for (account, amount) in payouts.iter() {
+                    store.push(ModelCycleIncomeReward {
+                        account: account.clone(),
+                        app_id,
+                        model_id: model_id.clone(),
+                        reward: *amount,
+                    })
+                }
             });
 
-            Self::deposit_event(RawEvent::ModelIncomeRewarded(who));
-            Ok(())
+            for (account, amount) in payouts {
+                Self::deposit_event(RawEvent::ModelIncomeRewarded(account, app_id, model_id.clone(), cycle_index, amount));
+            }
+            Ok(Some(T::WeightInfo::request_model_reward(actual_expert_count)).into())
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::app_income_redeem_request()]
         pub fn app_income_redeem_request(origin, params: AppIncomeRedeemParams<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+            envelope: SignEnvelope<T::BlockNumber>,
             app_user_account: AuthAccountId,
-            app_user_sign: sr25519::Signature,
+            app_user_sign: AuthSignature,
 
             auth_server: AuthAccountId,
-            auth_sign: sr25519::Signature) -> dispatch::DispatchResult {
+            auth_sign: AuthSignature) -> dispatch::DispatchResult {
 
-            let who = ensure_signed(origin)?;
+            let _who = ensure_signed(origin)?;
 
-            let buf = params.encode();
+            let buf = Self::sign_envelope_message(b"kp/app_income_redeem_request", &app_user_account, &envelope, &params.encode())?;
             ensure!(Self::verify_sign(&app_user_account, app_user_sign, &buf), Error::<T>::SignVerifyErrorUser);
             ensure!(Self::verify_sign(&auth_server, auth_sign, &buf), Error::<T>::SignVerifyErrorAuth);
+            T::Membership::inc_sign_nonce(&app_user_account);
 
             let AppIncomeRedeemParams {
                 account,
@@ -2123,7 +2935,7 @@ decl_module! {
             // check if we have specified a finance member to do confirm
             if !<AppCycleIncomeFinanceMember<T>>::contains_key(&fkey) {
                 // random choose one
-                finance_member = Self::choose_finance_member()?;
+                finance_member = Self::choose_finance_member(&fkey)?;
                 <AppCycleIncomeFinanceMember<T>>::insert(&fkey, finance_member.clone());
             } else {
                 finance_member = <AppCycleIncomeFinanceMember<T>>::get(&fkey);
@@ -2166,21 +2978,22 @@ decl_module! {
             <AppCycleIncome<T>>::insert(cycle, app_id, &record);
 
             // record user exchange record AppCycleIncomeExchangeRecords
-            <AppCycleIncomeExchangeRecords<T>>::insert(&ukey, AppFinancedUserExchangeData {
+            let mut exchange_record = AppFinancedUserExchangeData {
                 exchange_amount,
-                status: 1,
                 ..Default::default()
-            });
+            };
+            exchange_record.transit(ExchangeStatus::Reserved, block);
+            <AppCycleIncomeExchangeRecords<T>>::insert(&ukey, exchange_record);
 
             let mut accounts = <AppCycleIncomeExchangeSet<T>>::get(&fkey);
             accounts.push(account.clone());
             <AppCycleIncomeExchangeSet<T>>::insert(&fkey, accounts);
 
-            Self::deposit_event(RawEvent::AppCycleIncomeRedeem(who, finance_member));
+            Self::deposit_event(RawEvent::AppCycleIncomeRedeem(account, finance_member, app_id, cycle, exchange_amount));
             Ok(())
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::app_income_redeem_confirm()]
         pub fn app_income_redeem_confirm(origin, params: AppIncomeRedeemConfirmParams<T::AccountId, T::BlockNumber>) -> dispatch::DispatchResult {
 
             let who = ensure_signed(origin)?;
@@ -2204,9 +3017,9 @@ decl_module! {
             ensure!(<AppCycleIncomeExchangeRecords<T>>::contains_key(&ukey),
                 Error::<T>::AppFinancedUserExchangeRecordNotExist);
 
-            // make sure state is 1
+            // make sure state is reserved
             let record = <AppCycleIncomeExchangeRecords<T>>::get(&ukey);
-            ensure!(record.status == 1, Error::<T>::AppFinancedUserExchangeStateWrong);
+            ensure!(record.status.can_transit_to(ExchangeStatus::Confirmed), Error::<T>::AppFinancedUserExchangeStateWrong);
 
             // check if current model cycle match
             let block = <system::Module<T>>::block_number();
@@ -2242,17 +3055,17 @@ decl_module! {
 
             // update store
             <AppCycleIncomeExchangeRecords<T>>::mutate(&ukey, |record| {
-                record.status = 2;
+                record.transit(ExchangeStatus::Confirmed, block);
                 record.pay_id = pay_id;
             });
 
             <AppCycleIncomeBurnTotal<T>>::put(<AppCycleIncomeBurnTotal<T>>::get() + record.exchange_amount);
 
-            Self::deposit_event(RawEvent::AppCycleIncomeUserExchangeConfirmed(account));
+            Self::deposit_event(RawEvent::AppCycleIncomeUserExchangeConfirmed(account, app_id, cycle, record.exchange_amount));
             Ok(())
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::app_income_redeem_compensate()]
         pub fn app_income_redeem_compensate(origin, app_id: u32, cycle: T::BlockNumber) -> dispatch::DispatchResult {
             let who = ensure_signed(origin)?;
             let fkey = T::Hashing::hash_of(&(app_id, cycle));
@@ -2262,7 +3075,7 @@ decl_module! {
                 Error::<T>::AppFinancedUserExchangeRecordNotExist);
 
             let record = <AppCycleIncomeExchangeRecords<T>>::get(&ukey);
-            ensure!(record.status == 1, Error::<T>::AppFinancedUserExchangeStateWrong);
+            ensure!(record.status.can_transit_to(ExchangeStatus::Compensated), Error::<T>::AppFinancedUserExchangeStateWrong);
 
             let block = <system::Module<T>>::block_number();
             let stage = Self::model_income_stage(block);
@@ -2277,26 +3090,26 @@ decl_module! {
             let finance_member = <AppCycleIncomeFinanceMember<T>>::get(&fkey);
 
             let status = if T::Membership::slash_finance_member(&finance_member, &who, record.exchange_amount).is_ok() {
-                3
+                ExchangeStatus::Compensated
             } else {
-                4
+                ExchangeStatus::CompensateFailed
             };
 
             <AppCycleIncomeExchangeRecords<T>>::mutate(&ukey, |record| {
-                record.status = status;
+                record.transit(status, block);
             });
 
-            Self::deposit_event(RawEvent::AppIncomeUserExchangeCompensated(who));
+            Self::deposit_event(RawEvent::AppIncomeUserExchangeCompensated(who, app_id, cycle, record.exchange_amount));
             Ok(())
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::democracy_slash_commodity_power(T::AppLeaderBoardMaxPos::get())]
         pub fn democracy_slash_commodity_power(origin,
             app_id: u32,
             cart_id: Vec<u8>,
             comment_id: Vec<u8>,
             reporter_account: T::AccountId
-            ) -> dispatch::DispatchResult {
+            ) -> dispatch::DispatchResultWithPostInfo {
             print("enter democracy_slash_commodity_power");
             ensure_root(origin)?;
             print("root check pass");
@@ -2324,16 +3137,18 @@ decl_module! {
             // perform slash
             let key_hash = T::Hashing::hash_of(&(app_id, &cart_id));
             let owner_account = Self::convert_account(&doc.owner);
-            Self::slash_power(&key_hash, &owner_account);
+            let board_len = Self::leader_board_len(app_id, &model_id);
+            let slashed = Self::slash_power(&key_hash, &owner_account);
             Self::remove_leader_board_item(app_id, &model_id, &cart_id);
 
             Self::add_commodity_power_slash_record(app_id, &comment_id, &cart_id);
 
-            Self::deposit_event(RawEvent::PowerSlashed(owner_account));
-            Ok(())
+            Self::deposit_event(RawEvent::PowerSlashed(owner_account, app_id, comment_id, cart_id, slashed));
+            Ok(Some(T::WeightInfo::democracy_slash_commodity_power(board_len)).into())
         }
 
-        #[weight = 0]
+        /// Governance override, resolves a dispute immediately without the open/vote/appeal flow
+        #[weight = T::WeightInfo::democracy_model_dispute()]
         pub fn democracy_model_dispute(origin,
             app_id: u32,
             model_id: Vec<u8>,
@@ -2346,10 +3161,17 @@ decl_module! {
 
             ensure!(T::Membership::is_valid_app(app_id), Error::<T>::AppIdInvalid);
 
-            // get model creator account
-            ensure!(Self::is_valid_model(app_id, &model_id), Error::<T>::ModelNotFoundOrDisabled);
-
+            // retiring model still accepts dispute during cool-down
             let key = T::Hashing::hash_of(&(app_id, &model_id));
+            ensure!(Self::is_valid_model(app_id, &model_id) || <ModelRetireRecords<T>>::contains_key(&key),
+                Error::<T>::ModelNotFoundOrDisabled);
+
+            // comment already disputed, by governance or by an open/vote/appeal case
+            let dispute_key = T::Hashing::hash_of(&(app_id, &comment_id));
+            ensure!(!<ModelDisputeCases<T>>::contains_key(&dispute_key) && !<ModelDisputeRecords<T>>::contains_key(&dispute_key),
+                Error::<T>::ModelDisputeExisted);
+
+            // get model creator account
             let model = <KPModelDataByIdHash<T>>::get(&key);
             // according dispute type to decide slash
             let owner_account = Self::convert_account(&model.owner);
@@ -2358,22 +3180,204 @@ decl_module! {
 
             // update store
             Self::add_model_dispute_record(app_id, &model_id, &comment_id, dispute_type);
+            Ok(())
+        }
+
+        #[weight = T::WeightInfo::open_model_dispute()]
+        pub fn open_model_dispute(origin,
+            app_id: u32,
+            model_id: Vec<u8>,
+            dispute_type: ModelDisputeType,
+            comment_id: Vec<u8>) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(T::Membership::is_valid_app(app_id), Error::<T>::AppIdInvalid);
+
+            let model_key = T::Hashing::hash_of(&(app_id, &model_id));
+            ensure!(Self::is_valid_model(app_id, &model_id) || <ModelRetireRecords<T>>::contains_key(&model_key),
+                Error::<T>::ModelNotFoundOrDisabled);
+
+            let key = T::Hashing::hash_of(&(app_id, &comment_id));
+            ensure!(!<ModelDisputeCases<T>>::contains_key(&key) && !<ModelDisputeRecords<T>>::contains_key(&key),
+                Error::<T>::ModelDisputeExisted);
+
+            let owner = Self::convert_account(&<KPModelDataByIdHash<T>>::get(&model_key).owner);
+            ensure!(owner != who, Error::<T>::ModelDisputeNotParty);
+
+            let bond = T::ModelDisputeBond::get();
+            T::Currency::reserve(&who, bond)?;
+
+            let evidence_end = <system::Module<T>>::block_number() + T::ModelDisputeEvidencePeriod::get();
+            <ModelDisputeCases<T>>::insert(&key, ModelDisputeCase {
+                app_id,
+                model_id,
+                comment_id: comment_id.clone(),
+                dispute_type,
+                reporter: who.clone(),
+                owner,
+                bond,
+                status: ModelDisputeStatus::Open,
+                evidence_end,
+                vote_end: evidence_end + T::ModelDisputeVotePeriod::get(),
+                ..Default::default()
+            });
+            <ModelOpenDisputeCount<T>>::mutate(&model_key, |count| *count += 1);
+
+            Self::deposit_event(RawEvent::ModelDisputeOpened(who, app_id, comment_id));
+            Ok(())
+        }
 
-            Self::deposit_event(RawEvent::ModelDisputed(owner_account));
+        #[weight = T::WeightInfo::submit_model_dispute_evidence()]
+        pub fn submit_model_dispute_evidence(origin, app_id: u32, comment_id: Vec<u8>, evidence: T::Hash) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let key = T::Hashing::hash_of(&(app_id, &comment_id));
+            ensure!(<ModelDisputeCases<T>>::contains_key(&key), Error::<T>::ModelDisputeNotFound);
+
+            let mut case = <ModelDisputeCases<T>>::get(&key);
+            ensure!(case.status == ModelDisputeStatus::Open && <system::Module<T>>::block_number() <= case.evidence_end,
+                Error::<T>::ModelDisputeStageInvalid);
+            ensure!(who == case.reporter || who == case.owner, Error::<T>::ModelDisputeNotParty);
+            ensure!(case.evidences.len() < MODEL_DISPUTE_EVIDENCE_MAX, Error::<T>::ModelDisputeEvidenceFull);
+
+            case.evidences.push((who.clone(), evidence));
+            <ModelDisputeCases<T>>::insert(&key, case);
+
+            Self::deposit_event(RawEvent::ModelDisputeEvidenceSubmitted(who, app_id, comment_id));
+            Ok(())
+        }
+
+        #[weight = T::WeightInfo::vote_model_dispute()]
+        pub fn vote_model_dispute(origin, app_id: u32, comment_id: Vec<u8>, approve: bool) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let key = T::Hashing::hash_of(&(app_id, &comment_id));
+            ensure!(<ModelDisputeCases<T>>::contains_key(&key), Error::<T>::ModelDisputeNotFound);
+
+            let mut case = <ModelDisputeCases<T>>::get(&key);
+            let current_block = <system::Module<T>>::block_number();
+            ensure!(case.status == ModelDisputeStatus::Open && current_block > case.evidence_end && current_block <= case.vote_end,
+                Error::<T>::ModelDisputeStageInvalid);
+
+            // tech committee or model experts, parties can not vote
+            ensure!(who != case.reporter && who != case.owner, Error::<T>::ModelDisputeNotVoter);
+            ensure!(T::TechMembers::contains(&who) || T::Membership::is_expert(&who, app_id, &case.model_id),
+                Error::<T>::ModelDisputeNotVoter);
+            ensure!(!case.ayes.contains(&who) && !case.nays.contains(&who), Error::<T>::ModelDisputeAlreadyVoted);
+            ensure!(case.ayes.len() + case.nays.len() < MODEL_DISPUTE_VOTE_MAX, Error::<T>::ModelDisputeVoteFull);
+
+            if approve {
+                case.ayes.push(who.clone());
+            } else {
+                case.nays.push(who.clone());
+            }
+            <ModelDisputeCases<T>>::insert(&key, case);
+
+            Self::deposit_event(RawEvent::ModelDisputeVoted(who, app_id, comment_id, approve));
+            Ok(())
+        }
+
+        /// Count votes after voting period, anyone can trigger
+        #[weight = T::WeightInfo::decide_model_dispute()]
+        pub fn decide_model_dispute(origin, app_id: u32, comment_id: Vec<u8>) -> dispatch::DispatchResult {
+            let _who = ensure_signed(origin)?;
+
+            let key = T::Hashing::hash_of(&(app_id, &comment_id));
+            ensure!(<ModelDisputeCases<T>>::contains_key(&key), Error::<T>::ModelDisputeNotFound);
+
+            let mut case = <ModelDisputeCases<T>>::get(&key);
+            let current_block = <system::Module<T>>::block_number();
+            ensure!(case.status == ModelDisputeStatus::Open && current_block > case.vote_end,
+                Error::<T>::ModelDisputeStageInvalid);
+
+            // tie goes to model owner
+            case.upheld = case.ayes.len() > case.nays.len();
+            case.status = ModelDisputeStatus::Decided;
+            case.appeal_end = current_block + T::ModelDisputeAppealPeriod::get();
+            let upheld = case.upheld;
+            <ModelDisputeCases<T>>::insert(&key, case);
+
+            Self::deposit_event(RawEvent::ModelDisputeDecided(app_id, comment_id, upheld));
+            Ok(())
+        }
+
+        /// Losing party of the votes asks tech committee for final judgement
+        #[weight = T::WeightInfo::appeal_model_dispute()]
+        pub fn appeal_model_dispute(origin, app_id: u32, comment_id: Vec<u8>) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let key = T::Hashing::hash_of(&(app_id, &comment_id));
+            ensure!(<ModelDisputeCases<T>>::contains_key(&key), Error::<T>::ModelDisputeNotFound);
+
+            let mut case = <ModelDisputeCases<T>>::get(&key);
+            ensure!(case.status == ModelDisputeStatus::Decided && <system::Module<T>>::block_number() <= case.appeal_end,
+                Error::<T>::ModelDisputeStageInvalid);
+
+            let loser = if case.upheld { &case.owner } else { &case.reporter };
+            ensure!(&who == loser, Error::<T>::ModelDisputeNotParty);
+
+            let appeal_bond = T::ModelDisputeBond::get();
+            T::Currency::reserve(&who, appeal_bond)?;
+
+            case.status = ModelDisputeStatus::Appealed;
+            case.appellant = Some(who.clone());
+            case.appeal_bond = appeal_bond;
+            <ModelDisputeCases<T>>::insert(&key, case);
+
+            Self::deposit_event(RawEvent::ModelDisputeAppealed(who, app_id, comment_id));
+            Ok(())
+        }
+
+        #[weight = T::WeightInfo::democracy_judge_model_dispute()]
+        pub fn democracy_judge_model_dispute(origin, app_id: u32, comment_id: Vec<u8>, upheld: bool) -> dispatch::DispatchResult {
+            T::TechMemberOrigin::ensure_origin(origin)?;
+
+            let key = T::Hashing::hash_of(&(app_id, &comment_id));
+            ensure!(<ModelDisputeCases<T>>::contains_key(&key), Error::<T>::ModelDisputeNotFound);
+
+            let mut case = <ModelDisputeCases<T>>::get(&key);
+            ensure!(case.status == ModelDisputeStatus::Appealed, Error::<T>::ModelDisputeStageInvalid);
+
+            Self::settle_model_dispute(&mut case, upheld);
+            <ModelDisputeCases<T>>::insert(&key, case);
+
+            Self::deposit_event(RawEvent::ModelDisputeResolved(app_id, comment_id, upheld));
+            Ok(())
+        }
+
+        /// Apply voted result when nobody appealed, anyone can trigger
+        #[weight = T::WeightInfo::finalize_model_dispute()]
+        pub fn finalize_model_dispute(origin, app_id: u32, comment_id: Vec<u8>) -> dispatch::DispatchResult {
+            let _who = ensure_signed(origin)?;
+
+            let key = T::Hashing::hash_of(&(app_id, &comment_id));
+            ensure!(<ModelDisputeCases<T>>::contains_key(&key), Error::<T>::ModelDisputeNotFound);
+
+            let mut case = <ModelDisputeCases<T>>::get(&key);
+            ensure!(case.status == ModelDisputeStatus::Decided && <system::Module<T>>::block_number() > case.appeal_end,
+                Error::<T>::ModelDisputeStageInvalid);
+
+            let upheld = case.upheld;
+            Self::settle_model_dispute(&mut case, upheld);
+            <ModelDisputeCases<T>>::insert(&key, case);
+
+            Self::deposit_event(RawEvent::ModelDisputeResolved(app_id, comment_id, upheld));
             Ok(())
         }
 
         /// Register new app
-        #[weight = 0]
+        #[weight = T::WeightInfo::democracy_add_app()]
         pub fn democracy_add_app(origin, params: AddAppParams<T::AccountId>,
+            envelope: SignEnvelope<T::BlockNumber>,
             app_user_account: AuthAccountId,
-            app_user_sign: sr25519::Signature) -> dispatch::DispatchResult {
+            app_user_sign: AuthSignature) -> dispatch::DispatchResult {
             print("democracy_add_app enter");
             ensure_root(origin)?;
 
             print("democracy_add_app pass root check");
-            let buf = params.encode();
+            let buf = Self::sign_envelope_message(b"kp/democracy_add_app", &app_user_account, &envelope, &params.encode())?;
             ensure!(Self::verify_sign(&app_user_account, app_user_sign, &buf), Error::<T>::SignVerifyErrorUser);
+            T::Membership::inc_sign_nonce(&app_user_account);
 
             let AddAppParams {
               app_type,
@@ -2417,31 +3421,162 @@ decl_module! {
             // config max model
             <AppModelTotalConfig>::insert(app_id, max_models);
 
+            <AppStakes<T>>::insert(app_id, AppStakeRecord {
+                app_type: app_type.clone(),
+                owner: app_admin_key.clone(),
+                stake,
+            });
+
             // update app_id range store
             <AppIdRange<T>>::mutate(&app_type, |info| {
               info.0 = app_id;
               info.3 += 1;
             });
             print("democracy_add_app done");
-            Self::deposit_event(RawEvent::AppAdded(app_id));
+            Self::deposit_event(RawEvent::AppAdded(app_id, app_admin_key, stake));
             Ok(())
         }
 
-        #[weight = 0]
-        pub fn democracy_app_financed(origin,
-            params: AppFinancedProposalParams<T::AccountId, BalanceOf<T>>,
-            app_user_account: AuthAccountId,
-            app_user_sign: sr25519::Signature,
-
-            auth_server: AuthAccountId,
-            auth_sign: sr25519::Signature) -> dispatch::DispatchResult {
+        #[weight = T::WeightInfo::democracy_suspend_app()]
+        pub fn democracy_suspend_app(origin, app_id: u32) -> dispatch::DispatchResult {
             ensure_root(origin)?;
-            print("pass root check");
+            ensure!(!T::Membership::is_app_suspended(app_id), Error::<T>::AppAlreadySuspended);
+            ensure!(T::Membership::is_valid_app(app_id), Error::<T>::AppIdInvalid);
+
+            T::Membership::set_app_suspended(app_id, true);
+            Self::deposit_event(RawEvent::AppSuspended(app_id));
+            Ok(())
+        }
+
+        #[weight = T::WeightInfo::democracy_resume_app()]
+        pub fn democracy_resume_app(origin, app_id: u32) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            ensure!(T::Membership::is_app_suspended(app_id), Error::<T>::AppNotSuspended);
+
+            T::Membership::set_app_suspended(app_id, false);
+            Self::deposit_event(RawEvent::AppResumed(app_id));
+            Ok(())
+        }
+
+        /// Remove app admins, keys and settings, its stake is unreserved or slashed.
+        #[weight = T::WeightInfo::democracy_deregister_app()]
+        pub fn democracy_deregister_app(origin, app_id: u32, is_slash: bool) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            ensure!(
+                T::Membership::is_valid_app(app_id) || T::Membership::is_app_suspended(app_id),
+                Error::<T>::AppIdInvalid
+            );
+            // deposits of its models and financed rounds are only released while the app is valid
+            ensure!(<AppModelCount>::get(app_id) == 0, Error::<T>::AppHasModels);
+            let current_block = <system::Module<T>>::block_number();
+            ensure!(
+                !<AppFinancedOpenRounds<T>>::get().iter().any(|round| round.0 == app_id && round.1 >= current_block),
+                Error::<T>::AppHasOpenFinancedRounds
+            );
+
+            T::Membership::remove_app(app_id);
+            <AppModelTotalConfig>::remove(app_id);
+            <AppPowerWeightProfiles>::remove(app_id);
+            <PendingPowerWeightProfiles>::remove(app_id);
+            <LeaderBoardRewardPools<T>>::remove_prefix(app_id);
+
+            let AppStakeRecord { app_type, owner, stake } = <AppStakes<T>>::take(app_id);
+            <AppIdRange<T>>::mutate(&app_type, |info| {
+                info.3 = info.3.saturating_sub(1);
+            });
+
+            if is_slash {
+                T::Slash::on_unbalanced(T::Currency::slash_reserved(&owner, stake).0);
+            } else {
+                T::Currency::unreserve(&owner, stake);
+            }
+
+            Self::deposit_event(RawEvent::AppDeregistered(app_id, owner, stake, is_slash));
+            Ok(())
+        }
+
+        // the running model income cycle keeps its params, new ones start with the next cycle
+        #[weight = T::WeightInfo::democracy_set_economic_params()]
+        pub fn democracy_set_economic_params(origin,
+            params: KPEconomicParams<BalanceOf<T>, T::BlockNumber>
+        ) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            ensure!(Self::is_economic_params_valid(&params), Error::<T>::EconomicParamsInvalid);
+
+            let current_block = <system::Module<T>>::block_number();
+            let start = Self::next_model_income_cycle_start(current_block);
+            <PendingEconomicParams<T>>::put((start, params));
+
+            Self::deposit_event(RawEvent::EconomicParamsScheduled(start));
+            Ok(())
+        }
+
+        #[weight = T::WeightInfo::propose_power_weight_profile()]
+        pub fn propose_power_weight_profile(origin,
+            app_id: u32,
+            profile: PowerWeightProfile
+        ) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(T::Membership::is_valid_app(app_id), Error::<T>::AppIdInvalid);
+            ensure!(T::Membership::is_app_admin(&who, app_id), Error::<T>::NotAppAdmin);
+            ensure!(profile.is_valid(), Error::<T>::PowerWeightProfileInvalid);
+
+            // a newer proposal replaces the one under review
+            <PendingPowerWeightProfiles>::insert(app_id, profile);
+            Self::deposit_event(RawEvent::PowerWeightProfileProposed(who, app_id));
+            Ok(())
+        }
+
+        // approved weights apply to powers computed afterwards, existing powers are kept
+        #[weight = T::WeightInfo::democracy_review_power_weight_profile()]
+        pub fn democracy_review_power_weight_profile(origin,
+            app_id: u32,
+            is_approved: bool
+        ) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+
+            let profile = <PendingPowerWeightProfiles>::take(app_id)
+                .ok_or(Error::<T>::PowerWeightProfileNotFound)?;
+            if is_approved {
+                <AppPowerWeightProfiles>::insert(app_id, profile);
+            }
+
+            Self::deposit_event(RawEvent::PowerWeightProfileReviewed(app_id, is_approved));
+            Ok(())
+        }
+
+        /// Governance sets reward pool of one leader board, empty model id for the app board.
+        #[weight = T::WeightInfo::democracy_set_leader_board_reward()]
+        pub fn democracy_set_leader_board_reward(origin,
+            app_id: u32,
+            model_id: Vec<u8>,
+            reward: BalanceOf<T>
+        ) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            ensure!(T::Membership::is_valid_app(app_id), Error::<T>::AppIdInvalid);
+
+            <LeaderBoardRewardPools<T>>::insert(app_id, &model_id, reward);
+            Self::deposit_event(RawEvent::LeaderBoardRewardPoolSet(app_id, model_id, reward));
+            Ok(())
+        }
+
+        #[weight = T::WeightInfo::democracy_app_financed(<AppFinancedOpenRounds<T>>::decode_len().unwrap_or_default() as u32)]
+        pub fn democracy_app_financed(origin,
+            params: AppFinancedProposalParams<T::AccountId, BalanceOf<T>>,
+            envelope: SignEnvelope<T::BlockNumber>,
+            app_user_account: AuthAccountId,
+            app_user_sign: AuthSignature,
+
+            auth_server: AuthAccountId,
+            auth_sign: AuthSignature) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            print("pass root check");
 
             let current_block = <system::Module<T>>::block_number();
 
-            // check if last exchange cycle ended
-            let last_key = <AppFinancedLast<T>>::get();
+            // check if last exchange cycle of the app ended, other apps may be financed meanwhile
+            let last_key = <AppFinancedLastOfApp<T>>::get(params.app_id);
             if <AppFinancedRecord<T>>::contains_key(&last_key) {
                 let last_record = <AppFinancedRecord<T>>::get(&last_key);
                 ensure!(last_record.exchange_end_block < current_block, Error::<T>::AppFinancedLastExchangeNotEnd);
@@ -2452,7 +3587,7 @@ decl_module! {
             ensure!(T::Membership::is_finance_member(&Self::convert_account(&auth_server)), Error::<T>::AuthIdentityNotFinanceMember);
             print("pass tech member check");
 
-            let buf = params.encode();
+            let buf = Self::sign_envelope_message(b"kp/democracy_app_financed", &app_user_account, &envelope, &params.encode())?;
             ensure!(Self::verify_sign(&app_user_account, app_user_sign, &buf), Error::<T>::SignVerifyErrorUser);
             print("pass user sign check");
             ensure!(Self::verify_sign(&auth_server, auth_sign, &buf), Error::<T>::SignVerifyErrorAuth);
@@ -2481,9 +3616,16 @@ decl_module! {
             print("pass exist check");
 
             // start transfer amount
-            ensure!(T::Membership::is_investor(&account), Error::<T>::AppFinancedNotInvestor);
+            ensure!(T::Membership::is_investor(&account, app_id), Error::<T>::AppFinancedNotInvestor);
             print("pass investor check");
 
+            let mut open_rounds = <AppFinancedOpenRounds<T>>::get();
+            open_rounds.retain(|round| round.1 >= current_block);
+            ensure!((open_rounds.len() as u32) < T::AppFinancedOpenRoundsMax::get(), Error::<T>::AppFinancedOpenRoundsFull);
+            let exposure = open_rounds.iter().fold(amount, |total, round| total.saturating_add(round.2));
+            ensure!(exposure <= T::AppFinancedExposureMax::get(), Error::<T>::AppFinancedExposureExceeded);
+            print("pass exposure check");
+
             let total_balance = T::Currency::total_issuance_excluding_fund();
 
             let treasury_account: T::AccountId = T::FinTreasuryModuleId::get().into_account();
@@ -2493,42 +3635,53 @@ decl_module! {
                 amount,
                 KeepAlive,
             )?;
+            // nonce is used up only when the proposal is financed
+            T::Membership::inc_sign_nonce(&app_user_account);
 
+            let exchange_period = Self::active_economic_params().app_finance_exchange_period;
+            let exchange_end_block = current_block + exchange_period;
             <AppFinancedRecord<T>>::insert(&key, AppFinancedData::<BalanceOf<T>, T::BlockNumber> {
                 app_id,
-                proposal_id,
+                proposal_id: proposal_id.clone(),
                 amount,
                 exchange,
                 block: current_block,
                 total_balance,
                 exchanged: 0u32.into(),
-                exchange_end_block: current_block + T::AppFinanceExchangePeriod::get(),
+                exchange_end_block,
+                confirm_end_block: exchange_end_block + exchange_period / 2u32.into(),
+                compensate_end_block: exchange_end_block + exchange_period,
             });
 
-            // recrod it as last
-            <AppFinancedLast<T>>::put(&key);
+            // recrod it as last of the app
+            <AppFinancedLastOfApp<T>>::insert(app_id, &key);
+            // exchanges can be compensated until the end, the round stays exposed till then
+            open_rounds.push((app_id, exchange_end_block + exchange_period, amount));
+            <AppFinancedOpenRounds<T>>::put(open_rounds);
             // update count
             <AppFinancedCount>::put(<AppFinancedCount>::get() + 1);
 
             print("done");
-            Self::deposit_event(RawEvent::AppFinanced(app_id));
+            Self::deposit_event(RawEvent::AppFinanced(app_id, proposal_id, account, amount));
             Ok(())
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::app_financed_user_exchange_request()]
         pub fn app_financed_user_exchange_request(origin,
             params: AppFinancedUserExchangeParams<T::AccountId, BalanceOf<T>>,
+            envelope: SignEnvelope<T::BlockNumber>,
             app_user_account: AuthAccountId,
-            app_user_sign: sr25519::Signature,
+            app_user_sign: AuthSignature,
 
             auth_server: AuthAccountId,
-            auth_sign: sr25519::Signature) -> dispatch::DispatchResult {
+            auth_sign: AuthSignature) -> dispatch::DispatchResult {
 
             let _who = ensure_signed(origin)?;
 
-            let buf = params.encode();
+            let buf = Self::sign_envelope_message(b"kp/app_financed_user_exchange_request", &app_user_account, &envelope, &params.encode())?;
             ensure!(Self::verify_sign(&app_user_account, app_user_sign, &buf), Error::<T>::SignVerifyErrorUser);
             ensure!(Self::verify_sign(&auth_server, auth_sign, &buf), Error::<T>::SignVerifyErrorAuth);
+            T::Membership::inc_sign_nonce(&app_user_account);
 
             let AppFinancedUserExchangeParams {
                 account,
@@ -2564,7 +3717,7 @@ decl_module! {
             // check if we have specified a finance member to do confirm
             if !<AppFinanceFinanceMember<T>>::contains_key(&fkey) {
                 // random choose one
-                finance_member = Self::choose_finance_member()?;
+                finance_member = Self::choose_finance_member(&fkey)?;
                 <AppFinanceFinanceMember<T>>::insert(&fkey, finance_member.clone());
             } else {
                 finance_member = <AppFinanceFinanceMember<T>>::get(&fkey);
@@ -2581,21 +3734,22 @@ decl_module! {
             <AppFinancedRecord<T>>::insert(&fkey, financed_record);
 
             // AppFinancedUserExchangeData
-            <AppFinancedUserExchangeRecord<T>>::insert(&ukey, AppFinancedUserExchangeData {
+            let mut exchange_record = AppFinancedUserExchangeData {
                 exchange_amount,
-                status: 1,
                 ..Default::default()
-            });
+            };
+            exchange_record.transit(ExchangeStatus::Reserved, <system::Module<T>>::block_number());
+            <AppFinancedUserExchangeRecord<T>>::insert(&ukey, exchange_record);
 
             let mut accounts = <AppFinancedUserExchangeSet<T>>::get(&fkey);
             accounts.push(account.clone());
             <AppFinancedUserExchangeSet<T>>::insert(&fkey, accounts);
 
-            Self::deposit_event(RawEvent::AppFinanceUserExchangeStart(account, finance_member));
+            Self::deposit_event(RawEvent::AppFinanceUserExchangeStart(account, finance_member, app_id, proposal_id, exchange_amount));
             Ok(())
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::app_financed_user_exchange_confirm()]
         pub fn app_financed_user_exchange_confirm(origin, params: AppFinancedUserExchangeConfirmParams<T::AccountId>) -> dispatch::DispatchResult {
             let who = ensure_signed(origin)?;
 
@@ -2618,16 +3772,15 @@ decl_module! {
             ensure!(<AppFinancedUserExchangeRecord<T>>::contains_key(&ukey),
                 Error::<T>::AppFinancedUserExchangeRecordNotExist);
 
-            // make sure state is 1
+            // make sure state is reserved
             let record = <AppFinancedUserExchangeRecord<T>>::get(&ukey);
-            ensure!(record.status == 1, Error::<T>::AppFinancedUserExchangeStateWrong);
+            ensure!(record.status.can_transit_to(ExchangeStatus::Confirmed), Error::<T>::AppFinancedUserExchangeStateWrong);
 
             // make sure not over confirm end stage
             let fkey = T::Hashing::hash_of(&(app_id, &proposal_id));
             let financed_record = <AppFinancedRecord<T>>::get(&fkey);
             let current_block = <system::Module<T>>::block_number();
-            let end = financed_record.exchange_end_block + T::AppFinanceExchangePeriod::get() / 2u32.into();
-            ensure!(end >= current_block, Error::<T>::AppFinancedUserExchangeConfirmEnded);
+            ensure!(financed_record.confirm_end_block >= current_block, Error::<T>::AppFinancedUserExchangeConfirmEnded);
 
             let fee = Permill::from_rational_approximation(T::RedeemFeeRate::get(), 1000u32) * record.exchange_amount;
             // unreserve account balance
@@ -2658,17 +3811,17 @@ decl_module! {
 
             // update store
             <AppFinancedUserExchangeRecord<T>>::mutate(&ukey, |record| {
-                record.status = 2;
+                record.transit(ExchangeStatus::Confirmed, current_block);
                 record.pay_id = pay_id;
             });
 
             <AppFinancedBurnTotal<T>>::put(<AppFinancedBurnTotal<T>>::get() + record.exchange_amount);
 
-            Self::deposit_event(RawEvent::AppFinanceUserExchangeConfirmed(account));
+            Self::deposit_event(RawEvent::AppFinanceUserExchangeConfirmed(account, app_id, proposal_id, record.exchange_amount));
             Ok(())
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::app_finance_redeem_compensate()]
         pub fn app_finance_redeem_compensate(origin, app_id: u32, proposal_id: Vec<u8>) -> dispatch::DispatchResult {
             let who = ensure_signed(origin)?;
             let fkey = T::Hashing::hash_of(&(app_id, &proposal_id));
@@ -2678,17 +3831,15 @@ decl_module! {
                 Error::<T>::AppFinancedUserExchangeRecordNotExist);
 
             let record = <AppFinancedUserExchangeRecord<T>>::get(&ukey);
-            ensure!(record.status == 1, Error::<T>::AppFinancedUserExchangeStateWrong);
+            ensure!(record.status.can_transit_to(ExchangeStatus::Compensated), Error::<T>::AppFinancedUserExchangeStateWrong);
 
             let current_block = <system::Module<T>>::block_number();
             let financed_record = <AppFinancedRecord<T>>::get(&fkey);
-            // make sure current block over end + end
-            let confirm_end = financed_record.exchange_end_block + T::AppFinanceExchangePeriod::get() / 2u32.into();
-            ensure!(confirm_end < current_block, Error::<T>::AppFinancedUserExchangeConfirmNotEnd);
+            // make sure current block over confirm end stage
+            ensure!(financed_record.confirm_end_block < current_block, Error::<T>::AppFinancedUserExchangeConfirmNotEnd);
 
             // make sure not over compensate end stage
-            let end = financed_record.exchange_end_block + T::AppFinanceExchangePeriod::get();
-            ensure!(end >= current_block, Error::<T>::AppFinancedUserExchangeCompensateEnded);
+            ensure!(financed_record.compensate_end_block >= current_block, Error::<T>::AppFinancedUserExchangeCompensateEnded);
 
             // unlock balance
             T::Currency::unreserve(&who, record.exchange_amount);
@@ -2696,42 +3847,34 @@ decl_module! {
             // get slash from finance member
             let finance_member = <AppFinanceFinanceMember<T>>::get(&fkey);
             let status = if T::Membership::slash_finance_member(&finance_member, &who, record.exchange_amount).is_ok() {
-                3
+                ExchangeStatus::Compensated
             } else {
-                4
+                ExchangeStatus::CompensateFailed
             };
 
             <AppFinancedUserExchangeRecord<T>>::mutate(&ukey, |record| {
-                record.status = status;
+                record.transit(status, current_block);
             });
 
-            Self::deposit_event(RawEvent::AppFinanceUserExchangeCompensated(who));
+            Self::deposit_event(RawEvent::AppFinanceUserExchangeCompensated(who, app_id, proposal_id, record.exchange_amount));
             Ok(())
         }
 
-        #[weight = 0]
-        pub fn create_power_leader_board(origin, app_id: u32, model_id: Vec<u8>) -> dispatch::DispatchResult {
+        #[weight = T::WeightInfo::create_power_leader_board(T::AppLeaderBoardMaxPos::get(), <Module<T>>::leader_board_lottery_weight_bound())]
+        pub fn create_power_leader_board(origin, app_id: u32, model_id: Vec<u8>) -> dispatch::DispatchResultWithPostInfo {
             ensure_root(origin)?;
 
             ensure!(T::Membership::is_valid_app(app_id), Error::<T>::AppIdInvalid);
 
             let current_block = <system::Module<T>>::block_number();
             // read out last time block number and check distance
-            let last_key = T::Hashing::hash_of(&(app_id, &model_id));
-
-            if <AppLeaderBoardLastTime<T>>::contains_key(&last_key) {
-                let last_block = <AppLeaderBoardLastTime<T>>::get(&last_key);
-                let diff = current_block - last_block;
-                ensure!(diff > T::AppLeaderBoardInterval::get(), Error::<T>::LeaderBoardCreateNotPermit);
-            }
-
-            Self::leader_board_lottery(current_block, app_id, &model_id);
+            ensure!(Self::is_leader_board_due(current_block, app_id, &model_id), Error::<T>::LeaderBoardCreateNotPermit);
 
-            Self::deposit_event(RawEvent::LeaderBoardsCreated(current_block, app_id, model_id));
-            Ok(())
+            let weight = Self::create_leader_board_snapshot(current_block, app_id, model_id)?;
+            Ok(Some(weight).into())
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::democracy_tech_fund_withdraw()]
         pub fn democracy_tech_fund_withdraw(origin, receiver: T::AccountId, reason: T::Hash, dev_type: TechFundWithdrawType, dev_level: TechFundWithdrawLevel) -> dispatch::DispatchResult {
             print("democracy_tech_fund_withdraw enter");
             T::TechMemberOrigin::ensure_origin(origin)?;
@@ -2762,28 +3905,127 @@ decl_module! {
             });
             <TechFundWithdrawRecords<T>>::put(records);
 
-            Self::deposit_event(RawEvent::TechFundWithdrawed(receiver));
+            Self::deposit_event(RawEvent::TechFundWithdrawed(receiver, amount));
+            Ok(())
+        }
+
+        #[weight = T::WeightInfo::claim_leader_board_reward()]
+        pub fn claim_leader_board_reward(origin, app_id: u32, model_id: Vec<u8>, block: T::BlockNumber) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(T::Membership::is_valid_app(app_id), Error::<T>::AppIdInvalid);
+
+            let key = Self::leader_record_key(app_id, block, &model_id);
+            ensure!(<LeaderBoardRewardRecords<T>>::contains_key(&key), Error::<T>::LeaderBoardRewardNotFound);
+
+            let record = <LeaderBoardRewardRecords<T>>::get(&key);
+            ensure!(!record.is_released && <system::Module<T>>::block_number() <= record.end_block,
+                Error::<T>::LeaderBoardRewardClaimEnded);
+            ensure!(!<LeaderBoardRewardClaimed<T>>::contains_key(&key, &who), Error::<T>::LeaderBoardRewardAlreadyClaimed);
+
+            let reward = Self::leader_board_account_reward(&key, &record, &who);
+            ensure!(reward > 0u32.into(), Error::<T>::NotLeaderBoardWinner);
+
+            let pot_account: T::AccountId = T::LeaderBoardRewardModuleId::get().into_account();
+            T::Currency::transfer(
+                &pot_account,
+                &who,
+                reward,
+                AllowDeath,
+            )?;
+
+            <LeaderBoardRewardRecords<T>>::mutate(&key, |record| {
+                record.claimed += reward;
+            });
+            <LeaderBoardRewardClaimed<T>>::insert(&key, &who, reward);
+
+            Self::deposit_event(RawEvent::LeaderBoardRewardClaimed(who, reward));
             Ok(())
         }
 
+        fn on_runtime_upgrade() -> Weight {
+            let mut weight: Weight = 0;
+
+            if StorageVersion::get() == Releases::V1_0_0 {
+                weight = weight.saturating_add(Self::migrate_document_comment_pools());
+                StorageVersion::put(Releases::V2_0_0);
+            }
+
+            if StorageVersion::get() == Releases::V2_0_0 {
+                weight = weight.saturating_add(Self::migrate_app_financed_last());
+                StorageVersion::put(Releases::V3_0_0);
+            }
+
+            if StorageVersion::get() == Releases::V3_0_0 {
+                weight = weight.saturating_add(Self::migrate_exchange_records());
+                StorageVersion::put(Releases::V4_0_0);
+            }
+
+            if StorageVersion::get() == Releases::V4_0_0 {
+                weight = weight.saturating_add(Self::migrate_leader_board_schedule());
+                StorageVersion::put(Releases::V5_0_0);
+            }
+
+            if StorageVersion::get() == Releases::V5_0_0 {
+                weight = weight.saturating_add(Self::migrate_product_refer_documents());
+                StorageVersion::put(Releases::V6_0_0);
+            }
+
+            if StorageVersion::get() == Releases::V6_0_0 {
+                weight = weight.saturating_add(Self::migrate_power_settled_at());
+                StorageVersion::put(Releases::V7_0_0);
+            }
+
+            if StorageVersion::get() == Releases::V7_0_0 {
+                weight = weight.saturating_add(Self::migrate_app_stakes());
+                StorageVersion::put(Releases::V8_0_0);
+            }
+
+            if StorageVersion::get() == Releases::V8_0_0 {
+                weight = weight.saturating_add(Self::migrate_app_financed_end_blocks());
+                StorageVersion::put(Releases::V9_0_0);
+            }
+
+            if StorageVersion::get() == Releases::V9_0_0 {
+                weight = weight.saturating_add(Self::migrate_app_model_counts());
+                StorageVersion::put(Releases::V10_0_0);
+            }
+
+            if StorageVersion::get() == Releases::V10_0_0 {
+                weight = weight.saturating_add(Self::migrate_model_dispute_appeal_bonds());
+                StorageVersion::put(Releases::V11_0_0);
+            }
+
+            if StorageVersion::get() == Releases::V11_0_0 {
+                weight = weight.saturating_add(Self::migrate_power_weight_profiles());
+                StorageVersion::put(Releases::V12_0_0);
+            }
+
+            if StorageVersion::get() == Releases::V12_0_0 {
+                weight = weight.saturating_add(Self::migrate_leader_board_sequence_keys());
+                StorageVersion::put(Releases::V13_0_0);
+            }
+
+            weight.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+        }
+
         // regular timer based task here
         fn on_initialize(n: T::BlockNumber) -> Weight {
-            /*
-            print("kp pallet block on_initialize");
+            let db = T::DbWeight::get();
+            // params of a new cycle must be in force before any task below reads them
+            let mut weight = Self::apply_economic_params(n);
             let mut pre_black_list = <ModelPreBlackList<T>>::get();
+            weight = weight.saturating_add(db.reads(1));
+            let mut killed: Vec<(u32, Vec<u8>)> = vec![];
             let mut is_changed = false;
 
             pre_black_list.retain(|x| {
                 //let (app_id, model_id, owner, end_block) = x;
                 if x.3 < n {
-                    // check if account reserve balance recover
-                    let balance = T::Currency::reserved_balance(&x.2);
-                    if balance <  T::ModelCreateDeposit::get() / 2u32.into() {
+                    // check if model deposit recover during grace period
+                    if !Self::is_model_deposit_enough(x.0, &x.1) {
                         print("kill model");
-                        let key = T::Hashing::hash_of(&(x.0, &x.1));
-                        <KPModelDataByIdHash<T>>::mutate(&key, |model| {
-                            model.status = ModelStatus::DISABLED;
-                        });
+                        Self::disable_killed_model(x.0, &x.1);
+                        killed.push((x.0, x.1.clone()));
                     }
 
                     // remove item from list
@@ -2796,20 +4038,45 @@ decl_module! {
 
             if is_changed {
                 <ModelPreBlackList<T>>::put(pre_black_list);
-            }*/
+                weight = weight.saturating_add(db.writes(1));
+            }
 
-            0
+            weight = weight.saturating_add(db.reads_writes(3 * killed.len() as Weight, 2 * killed.len() as Weight));
+            for (app_id, model_id) in killed {
+                Self::deposit_event(RawEvent::ModelDisabledByDispute(app_id, model_id));
+            }
+
+            weight.saturating_add(Self::release_leader_board_rewards(n))
+                .saturating_add(Self::schedule_leader_board_snapshots(n))
+                .saturating_add(Self::finish_model_retirements(n))
         }
     }
 }
 
 impl<T: Trait> Module<T> {
     pub fn kp_total_power() -> PowerSize {
-        TotalPower::get()
+        Self::decay_power(TotalPower::get(), <TotalPowerSettledAt<T>>::get())
+    }
+
+    pub fn kp_total_raw_power() -> PowerSize {
+        if <TotalPowerSettledAt<T>>::get().is_none() {
+            return TotalPower::get();
+        }
+        TotalRawPower::get()
     }
 
     pub fn kp_account_power(account: T::AccountId) -> PowerSize {
-        <MinerPowerByAccount<T>>::get(account)
+        Self::decay_power(
+            <MinerPowerByAccount<T>>::get(&account),
+            <MinerPowerSettledAt<T>>::get(&account),
+        )
+    }
+
+    pub fn kp_account_raw_power(account: T::AccountId) -> PowerSize {
+        if !<MinerPowerSettledAt<T>>::contains_key(&account) {
+            return <MinerPowerByAccount<T>>::get(&account);
+        }
+        <MinerRawPowerByAccount<T>>::get(&account)
     }
 
     pub fn kp_auth_account_power(account: AuthAccountId) -> PowerSize {
@@ -2817,6 +4084,11 @@ impl<T: Trait> Module<T> {
         Self::kp_account_power(account_id)
     }
 
+    pub fn kp_auth_account_raw_power(account: AuthAccountId) -> PowerSize {
+        let account_id = Self::convert_account(&account);
+        Self::kp_account_raw_power(account_id)
+    }
+
     pub fn power_factor(p: u128) -> PowerRatioType {
         match p {
             0..=100000 => (
@@ -2847,7 +4119,7 @@ impl<T: Trait> Module<T> {
     }
 
     pub fn kp_account_power_ratio(account: &T::AccountId) -> PowerRatioType {
-        let p = <MinerPowerByAccount<T>>::get(account) as u128;
+        let p = Self::kp_account_power(account.clone()) as u128;
         Self::power_factor(p)
     }
 
@@ -2889,11 +4161,34 @@ impl<T: Trait> Module<T> {
         app_id: u32,
         proposal_id: Vec<u8>,
         account: T::AccountId,
-    ) -> AppFinancedUserExchangeData<BalanceOf<T>> {
+    ) -> AppFinancedUserExchangeData<BalanceOf<T>, T::BlockNumber> {
         let key = Self::app_financed_exchange_record_key(app_id, &proposal_id, &account);
         <AppFinancedUserExchangeRecord<T>>::get(&key)
     }
 
+    // exchange records of one financing round in given status, such as reserved ones to confirm
+    pub fn app_finance_exchange_records(
+        app_id: u32,
+        proposal_id: Vec<u8>,
+        status: ExchangeStatus,
+    ) -> Vec<(
+        T::AccountId,
+        AppFinancedUserExchangeData<BalanceOf<T>, T::BlockNumber>,
+    )> {
+        Self::app_finance_exchange_accounts(app_id, proposal_id.clone())
+            .into_iter()
+            .filter_map(|account| {
+                let record =
+                    Self::app_finance_exchange_data(app_id, proposal_id.clone(), account.clone());
+                if record.status == status {
+                    Some((account, record))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
     pub fn app_income_exchange_accounts(app_id: u32, cycle: T::BlockNumber) -> Vec<T::AccountId> {
         let key = T::Hashing::hash_of(&(app_id, cycle));
         <AppCycleIncomeExchangeSet<T>>::get(&key)
@@ -2903,11 +4198,33 @@ impl<T: Trait> Module<T> {
         app_id: u32,
         cycle: T::BlockNumber,
         account: T::AccountId,
-    ) -> AppFinancedUserExchangeData<BalanceOf<T>> {
+    ) -> AppFinancedUserExchangeData<BalanceOf<T>, T::BlockNumber> {
         let key = Self::app_income_exchange_record_key(app_id, cycle, &account);
         <AppCycleIncomeExchangeRecords<T>>::get(&key)
     }
 
+    // redeem records of one app income cycle in given status
+    pub fn app_income_exchange_records(
+        app_id: u32,
+        cycle: T::BlockNumber,
+        status: ExchangeStatus,
+    ) -> Vec<(
+        T::AccountId,
+        AppFinancedUserExchangeData<BalanceOf<T>, T::BlockNumber>,
+    )> {
+        Self::app_income_exchange_accounts(app_id, cycle)
+            .into_iter()
+            .filter_map(|account| {
+                let record = Self::app_income_exchange_data(app_id, cycle, account.clone());
+                if record.status == status {
+                    Some((account, record))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
     pub fn app_income_record(
         app_id: u32,
         cycle: T::BlockNumber,
@@ -2915,6 +4232,17 @@ impl<T: Trait> Module<T> {
         <AppCycleIncome<T>>::get(cycle, app_id)
     }
 
+    pub fn model_cycle_rewards(
+        app_id: u32,
+        model_id: Vec<u8>,
+        cycle: T::BlockNumber,
+    ) -> Vec<ModelCycleIncomeReward<T::AccountId, BalanceOf<T>>> {
+        <ModelCycleIncomeRewardStore<T>>::get(cycle)
+            .into_iter()
+            .filter(|record| record.app_id == app_id && record.model_id == model_id)
+            .collect()
+    }
+
     pub fn kp_commodity_power(app_id: u32, cart_id: Vec<u8>) -> PowerSize {
         let key = T::Hashing::hash_of(&(app_id, &cart_id));
         Self::get_purchase_power(&key)
@@ -3005,6 +4333,118 @@ impl<T: Trait> Module<T> {
         <AppLeaderBoardRcord<T>>::get(&lottery_record_key)
     }
 
+    // items are ordered by (block, model id), cursor is the last item of previous page
+    pub fn leader_board_history(
+        app_id: u32,
+        model_id: Option<Vec<u8>>,
+        start_block: Option<T::BlockNumber>,
+        end_block: Option<T::BlockNumber>,
+        cursor: Option<LeaderBoardHistoryItem<T::BlockNumber>>,
+        limit: u32,
+    ) -> LeaderBoardHistoryPage<T::BlockNumber> {
+        let model_ids = match model_id {
+            Some(model_id) => vec![model_id],
+            None => <AppLeaderBoardModels>::get(app_id),
+        };
+        let limit = if limit == 0 {
+            LEADER_BOARD_HISTORY_PAGE_MAX
+        } else {
+            min(limit, LEADER_BOARD_HISTORY_PAGE_MAX)
+        };
+        // first index of history not before block
+        let lower_bound = |history: &Vec<T::BlockNumber>, block: T::BlockNumber| {
+            history.binary_search(&block).unwrap_or_else(|index| index)
+        };
+
+        let mut total = 0u32;
+        let mut items: Vec<LeaderBoardHistoryItem<T::BlockNumber>> = vec![];
+        for model_id in model_ids {
+            let key = T::Hashing::hash_of(&(app_id, &model_id));
+            let history = <AppLeaderBoardHistory<T>>::get(&key);
+
+            let first = start_block.map_or(0, |start| lower_bound(&history, start));
+            let last = end_block.map_or(history.len(), |end| match history.binary_search(&end) {
+                Ok(index) => index + 1,
+                Err(index) => index,
+            });
+            if first >= last {
+                continue;
+            }
+            total += (last - first) as u32;
+
+            let from = match &cursor {
+                Some(cursor) => {
+                    let index = lower_bound(&history, cursor.block);
+                    // same block of a board not after cursor is already returned
+                    if index < history.len()
+                        && history[index] == cursor.block
+                        && model_id <= cursor.model_id
+                    {
+                        index + 1
+                    } else {
+                        index
+                    }
+                }
+                None => 0,
+            };
+
+            let from = max(first, from);
+            if from >= last {
+                continue;
+            }
+            // no more than one page from each board is needed
+            for block in history[from..last].iter().take(limit as usize + 1) {
+                items.push(LeaderBoardHistoryItem {
+                    model_id: model_id.clone(),
+                    block: *block,
+                });
+            }
+        }
+        items.sort_by(|a, b| (a.block, &a.model_id).cmp(&(b.block, &b.model_id)));
+
+        let has_more = items.len() > limit as usize;
+        items.truncate(limit as usize);
+        let next_cursor = if has_more {
+            items.last().cloned()
+        } else {
+            None
+        };
+
+        LeaderBoardHistoryPage {
+            items,
+            total,
+            next_cursor,
+        }
+    }
+
+    pub fn leader_board_seed(block: u32, app_id: u32, model_id: Vec<u8>) -> [u8; 32] {
+        let lottery_record_key = Self::leader_record_key(app_id, block.into(), &model_id);
+        <AppLeaderBoardSeed<T>>::get(&lottery_record_key)
+    }
+
+    pub fn leader_board_reward_info(
+        block: u32,
+        app_id: u32,
+        model_id: Vec<u8>,
+        account: T::AccountId,
+    ) -> LeaderBoardRewardInfo<BalanceOf<T>, T::BlockNumber> {
+        let key = Self::leader_record_key(app_id, block.into(), &model_id);
+        let record = <LeaderBoardRewardRecords<T>>::get(&key);
+        let is_claimed = <LeaderBoardRewardClaimed<T>>::contains_key(&key, &account);
+        let reward = if is_claimed {
+            <LeaderBoardRewardClaimed<T>>::get(&key, &account)
+        } else {
+            Self::leader_board_account_reward(&key, &record, &account)
+        };
+
+        LeaderBoardRewardInfo {
+            reward,
+            is_claimed,
+            end_block: record.end_block,
+            is_released: record.is_released,
+        }
+    }
+
     pub fn is_tech_member_sign(account: AuthAccountId, msg: Vec<u8>, sign: Vec<u8>) -> bool {
         // check account tech member
         if !T::TechMembers::contains(&Self::convert_account(&account)) {
@@ -3056,11 +4496,9 @@ impl<T: Trait> Module<T> {
     }
 
     // belows are internal using
-    fn choose_finance_member() -> Result<T::AccountId, sp_runtime::DispatchError> {
-        let seed = T::Randomness::random(b"ctt_finance");
-        // seed needs to be guaranteed to be 32 bytes.
-        let seed = <[u8; 32]>::decode(&mut TrailingZeroInput::new(seed.as_ref()))
-            .expect("input is padded with zeroes; qed");
+    // every financed round or income cycle draws its own member
+    fn choose_finance_member(key: &T::Hash) -> Result<T::AccountId, sp_runtime::DispatchError> {
+        let seed = Self::random_seed(&(b"ctt_finance", key).encode());
         let mut rng = ChaChaRng::from_seed(seed);
 
         let members = T::Membership::valid_finance_members();
@@ -3072,14 +4510,107 @@ impl<T: Trait> Module<T> {
         };
     }
 
+    // epoch randomness stays the same for many blocks, the same subject draws again in every block
+    fn random_seed(subject: &[u8]) -> [u8; 32] {
+        let subject = T::Hashing::hash_of(&(subject, <system::Module<T>>::block_number()));
+        let random = if T::UseVrfRandomness::get() {
+            T::VrfRandomness::random(subject.as_ref())
+        } else {
+            T::Randomness::random(subject.as_ref())
+        };
+
+        // seed needs to be guaranteed to be 32 bytes.
+        <[u8; 32]>::decode(&mut TrailingZeroInput::new(random.as_ref()))
+            .expect("input is padded with zeroes; qed")
+    }
+
     fn leader_record_key(app_id: u32, block: T::BlockNumber, model_id: &Vec<u8>) -> T::Hash {
         let buf: Vec<T::BlockNumber> = vec![app_id.into(), block];
         T::Hashing::hash_of(&(buf, model_id))
     }
 
-    fn verify_sign(pub_key: &AuthAccountId, sign: sr25519::Signature, msg: &[u8]) -> bool {
-        let ms: MultiSignature = sign.into();
-        ms.verify(msg, &pub_key)
+    // single document comment pool never grows over the app's max comment count or pool max
+    // every leader attends lottery with identify, try and publish documents' pools
+    fn leader_board_lottery_weight_bound() -> u32 {
+        T::DocumentCommentPoolMax::get()
+            .saturating_mul(T::AppLeaderBoardMaxPos::get())
+            .saturating_mul(3)
+    }
+
+    fn leader_board_len(app_id: u32, model_id: &Vec<u8>) -> u32 {
+        let key = T::Hashing::hash_of(&(app_id, model_id));
+        <AppModelCommodityLeaderBoards<T>>::decode_len(&key).unwrap_or_default() as u32
+    }
+
+    // commodity of a document sits in its model board and the app board
+    fn document_leader_board_len(app_id: u32, document_id: &Vec<u8>) -> u32 {
+        let doc_key = T::Hashing::hash_of(&(app_id, document_id));
+        let model_id = <KPDocumentDataByIdHash<T>>::get(&doc_key).model_id;
+        max(
+            Self::leader_board_len(app_id, &model_id),
+            Self::leader_board_len(app_id, &vec![]),
+        )
+    }
+
+    fn comment_pool_len(app_id: u32, document_id: &Vec<u8>) -> u32 {
+        let key = T::Hashing::hash_of(&(app_id, document_id));
+        <DocumentCommentsAccountPool<T>>::decode_len(&key).unwrap_or_default() as u32
+    }
+
+    // sr25519/ed25519 signers must be the account itself, ecdsa signers are recovered
+    // and must hash to the account, bare sr25519 signatures of older clients still decode
+    fn verify_sign(pub_key: &AuthAccountId, sign: AuthSignature, msg: &[u8]) -> bool {
+        sign.verify(msg, pub_key)
+    }
+
+    // distinct app keys signing `msg` must reach the app key threshold, calls co-signed by an app
+    // admin or a finance member check that role account instead, the threshold only counts app keys
+    fn verify_app_key_signs(
+        app_id: u32,
+        auth_signs: &[(AuthAccountId, AuthSignature)],
+        msg: &[u8],
+    ) -> dispatch::DispatchResult {
+        ensure!(
+            auth_signs.len() <= AUTH_SIGNS_MAX,
+            Error::<T>::AuthSignsTooMany
+        );
+
+        let mut signers: Vec<&AuthAccountId> = vec![];
+        for (auth_server, auth_sign) in auth_signs {
+            ensure!(
+                !signers.contains(&auth_server),
+                Error::<T>::AuthSignsDuplicated
+            );
+            ensure!(
+                T::Membership::is_valid_app_key(app_id, &Self::convert_account(auth_server)),
+                Error::<T>::AuthIdentityNotAppKey
+            );
+            ensure!(
+                Self::verify_sign(auth_server, auth_sign.clone(), msg),
+                Error::<T>::SignVerifyErrorAuth
+            );
+            signers.push(auth_server);
+        }
+
+        ensure!(
+            signers.len() as u32 >= T::Membership::app_key_threshold(app_id),
+            Error::<T>::AuthSignsNotEnough
+        );
+        Ok(())
+    }
+
+    // check envelope of the primary signer, returns the message all signers signed
+    fn sign_envelope_message(
+        tag: &[u8],
+        signer: &AuthAccountId,
+        envelope: &SignEnvelope<T::BlockNumber>,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, Error<T>> {
+        let nonce = T::Membership::sign_nonce(signer);
+        primitives::sign_envelope_message::<T>(tag, nonce, envelope, payload).map_err(|e| match e {
+            SignEnvelopeError::Expired => Error::<T>::SignEnvelopeExpired,
+            SignEnvelopeError::NonceInvalid => Error::<T>::SignNonceInvalid,
+        })
     }
 
     fn convert_account(origin: &AuthAccountId) -> T::AccountId {
@@ -3154,15 +4685,22 @@ impl<T: Trait> Module<T> {
         }
 
         let power = Self::compute_commodity_power(power_set);
-        // read out total power
-        let mut total_power = TotalPower::get();
-        let mut account_power = <MinerPowerByAccount<T>>::get(owner);
+        // read out total power, decayed to current block
+        let mut total_power = Self::settle_total_power();
+        let mut account_power = Self::settle_account_power(owner);
+        let mut total_raw_power = TotalRawPower::get();
+        let mut account_raw_power = <MinerRawPowerByAccount<T>>::get(owner);
 
         // check if this has been added to total power before
         if <KPPurchasePowerByIdHash<T>>::contains_key(&key) {
             let org_power_set = <KPPurchasePowerByIdHash<T>>::get(&key);
-            // only add a diff to total power
-            let org_power = Self::compute_commodity_power(&org_power_set);
+            let org_raw_power = Self::compute_commodity_power(&org_power_set);
+            total_raw_power = total_raw_power.saturating_sub(org_raw_power);
+            account_raw_power = account_raw_power.saturating_sub(org_raw_power);
+
+            // only add a diff to total power, old power has decayed as well
+            let org_power =
+                Self::decay_power(org_raw_power, <KPPurchasePowerSettledAt<T>>::get(&key));
 
             // for total power
             if total_power >= org_power {
@@ -3183,10 +4721,21 @@ impl<T: Trait> Module<T> {
 
         total_power += power;
         TotalPower::put(total_power);
+        TotalRawPower::put(total_raw_power + power);
         <KPPurchasePowerByIdHash<T>>::insert(&key, power_set);
+        <KPPurchasePowerSettledAt<T>>::insert(&key, <system::Module<T>>::block_number());
 
         account_power += power;
         <MinerPowerByAccount<T>>::insert(owner, account_power);
+        <MinerRawPowerByAccount<T>>::insert(owner, account_raw_power + power);
+
+        Self::deposit_event(RawEvent::PurchasePowerUpdated(
+            owner.clone(),
+            app_id,
+            cart_id.clone(),
+            power,
+            account_power,
+        ));
 
         // update model board
         Self::update_realtime_power_leader_boards(app_id, model_id, cart_id, power, owner.clone());
@@ -3203,8 +4752,10 @@ impl<T: Trait> Module<T> {
 
         if <KPPurchasePowerByIdHash<T>>::contains_key(&key) {
             let org_power_set = <KPPurchasePowerByIdHash<T>>::get(&key);
-            let org_power = Self::compute_commodity_power(&org_power_set);
-            let mut total_power = TotalPower::get();
+            let org_raw_power = Self::compute_commodity_power(&org_power_set);
+            let org_power =
+                Self::decay_power(org_raw_power, <KPPurchasePowerSettledAt<T>>::get(&key));
+            let mut total_power = Self::settle_total_power();
             if total_power >= org_power {
                 total_power -= org_power;
             } else {
@@ -3213,7 +4764,9 @@ impl<T: Trait> Module<T> {
             }
 
             TotalPower::put(total_power);
+            TotalRawPower::mutate(|raw| *raw = raw.saturating_sub(org_raw_power));
         }
+        <KPPurchasePowerSettledAt<T>>::remove(&key);
 
         <KPPurchasePowerByIdHash<T>>::insert(
             &key,
@@ -3285,104 +4838,105 @@ impl<T: Trait> Module<T> {
     }
 
     fn compute_publish_product_content_power(
+        weights: &PowerWeightProfile,
         para_issue_rate: Permill,
         self_issue_rate: Permill,
         attend_rate: Permill,
     ) -> PowerSize {
-        let mut base = Permill::from_percent(T::TopWeightProductPublish::get() as u32)
-            * FLOAT_COMPUTE_PRECISION;
+        let mut base = Permill::from_percent(weights.top.0 as u32) * FLOAT_COMPUTE_PRECISION;
 
-        base = Permill::from_percent(T::DocumentPowerWeightContent::get() as u32) * base;
+        base = Permill::from_percent(weights.document.1 as u32) * base;
 
         let mut sub1 = para_issue_rate * base;
-        sub1 = Permill::from_percent(T::DocumentPublishWeightParamsRate::get() as u32) * sub1;
+        sub1 = Permill::from_percent(weights.publish.0 as u32) * sub1;
 
         let mut sub2 = self_issue_rate * base;
-        sub2 = Permill::from_percent(T::DocumentPublishWeightParamsSelfRate::get() as u32) * sub2;
+        sub2 = Permill::from_percent(weights.publish.1 as u32) * sub2;
 
         let mut sub3 = attend_rate * base;
-        sub3 = Permill::from_percent(T::DocumentPublishWeightParamsAttendRate::get() as u32) * sub3;
+        sub3 = Permill::from_percent(weights.publish.2 as u32) * sub3;
 
         sub1 + sub2 + sub3
     }
 
     fn compute_identify_content_power(
+        weights: &PowerWeightProfile,
         ident_rate: Permill,
         ident_consistence: Permill,
         seller_consistence: Permill,
     ) -> PowerSize {
-        let mut base = Permill::from_percent(T::TopWeightDocumentIdentify::get() as u32)
-            * FLOAT_COMPUTE_PRECISION;
+        let mut base = Permill::from_percent(weights.top.1 as u32) * FLOAT_COMPUTE_PRECISION;
 
-        base = Permill::from_percent(T::DocumentPowerWeightContent::get() as u32) * base;
+        base = Permill::from_percent(weights.document.1 as u32) * base;
 
         let mut sub1 = ident_rate * base;
-        sub1 = Permill::from_percent(T::DocumentIdentifyWeightParamsRate::get() as u32) * sub1;
+        sub1 = Permill::from_percent(weights.identify.0 as u32) * sub1;
 
         let mut sub2 = ident_consistence * base;
-        sub2 = Permill::from_percent(T::DocumentIdentifyWeightCheckRate::get() as u32) * sub2;
+        sub2 = Permill::from_percent(weights.identify.1 as u32) * sub2;
 
         let mut sub3 = seller_consistence * base;
-        sub3 = Permill::from_percent(T::DocumentIdentifyWeightConsistentRate::get() as u32) * sub3;
+        sub3 = Permill::from_percent(weights.identify.2 as u32) * sub3;
 
         sub1 + sub2 + sub3
     }
 
     fn compute_try_content_power(
+        weights: &PowerWeightProfile,
         offset_rate: Permill,
         true_rate: Permill,
         seller_consistence: Permill,
     ) -> PowerSize {
-        let mut base =
-            Permill::from_percent(T::TopWeightDocumentTry::get() as u32) * FLOAT_COMPUTE_PRECISION;
+        let mut base = Permill::from_percent(weights.top.2 as u32) * FLOAT_COMPUTE_PRECISION;
 
-        base = Permill::from_percent(T::DocumentPowerWeightContent::get() as u32) * base;
+        base = Permill::from_percent(weights.document.1 as u32) * base;
 
         let mut sub1 = offset_rate * base;
-        sub1 = Permill::from_percent(T::DocumentTryWeightBiasRate::get() as u32) * sub1;
+        sub1 = Permill::from_percent(weights.product_try.0 as u32) * sub1;
 
         let mut sub2 = true_rate * base;
-        sub2 = Permill::from_percent(T::DocumentTryWeightTrueRate::get() as u32) * sub2;
+        sub2 = Permill::from_percent(weights.product_try.1 as u32) * sub2;
 
         let mut sub3 = seller_consistence * base;
-        sub3 = Permill::from_percent(T::DocumentTryWeightConsistentRate::get() as u32) * sub3;
+        sub3 = Permill::from_percent(weights.product_try.2 as u32) * sub3;
 
         sub1 + sub2 + sub3
     }
 
     fn compute_choose_content_power(
+        weights: &PowerWeightProfile,
         sell_count_rate: Permill,
         try_count_rate: Permill,
     ) -> PowerSize {
-        let base = Permill::from_percent(T::DocumentCMPowerWeightContent::get() as u32)
-            * FLOAT_COMPUTE_PRECISION;
+        let base = Permill::from_percent(weights.cm.2 as u32) * FLOAT_COMPUTE_PRECISION;
 
         let mut sub1 = sell_count_rate * base;
-        sub1 = Permill::from_percent(T::DocumentChooseWeightSellCount::get() as u32) * sub1;
+        sub1 = Permill::from_percent(weights.choose.0 as u32) * sub1;
 
         let mut sub2 = try_count_rate * base;
-        sub2 = Permill::from_percent(T::DocumentChooseWeightTryCount::get() as u32) * sub2;
+        sub2 = Permill::from_percent(weights.choose.1 as u32) * sub2;
 
         sub1 + sub2
     }
 
     fn compute_model_content_power(
+        weights: &PowerWeightProfile,
         producer_count_rate: Permill,
         product_count_rate: Permill,
     ) -> PowerSize {
-        let base = Permill::from_percent(T::DocumentCMPowerWeightContent::get() as u32)
-            * FLOAT_COMPUTE_PRECISION;
+        let base = Permill::from_percent(weights.cm.2 as u32) * FLOAT_COMPUTE_PRECISION;
 
         let mut sub1 = producer_count_rate * base;
-        sub1 = Permill::from_percent(T::DocumentModelWeightProducerCount::get() as u32) * sub1;
+        sub1 = Permill::from_percent(weights.model.0 as u32) * sub1;
 
         let mut sub2 = product_count_rate * base;
-        sub2 = Permill::from_percent(T::DocumentModelWeightProductCount::get() as u32) * sub2;
+        sub2 = Permill::from_percent(weights.model.1 as u32) * sub2;
 
         sub1 + sub2
     }
 
     fn compute_attend_power(
+        comment_weights: (u8, u8, u8, u8),
         rates: (Permill, Permill, Permill, Permill),
         second_weight: PowerSize,
         top_weight: PowerSize,
@@ -3392,16 +4946,16 @@ impl<T: Trait> Module<T> {
         base = Permill::from_percent(second_weight as u32) * base;
 
         let mut sub1 = rates.0 * base;
-        sub1 = Permill::from_percent(T::CommentPowerWeightCount::get() as u32) * sub1;
+        sub1 = Permill::from_percent(comment_weights.0 as u32) * sub1;
 
         let mut sub2 = rates.1 * base;
-        sub2 = Permill::from_percent(T::CommentPowerWeightCost::get() as u32) * sub2;
+        sub2 = Permill::from_percent(comment_weights.1 as u32) * sub2;
 
         let mut sub3 = rates.2 * base;
-        sub3 = Permill::from_percent(T::CommentPowerWeightPerCost::get() as u32) * sub3;
+        sub3 = Permill::from_percent(comment_weights.2 as u32) * sub3;
 
         let mut sub4 = rates.3 * base;
-        sub4 = Permill::from_percent(T::CommentPowerWeightPositive::get() as u32) * sub4;
+        sub4 = Permill::from_percent(comment_weights.3 as u32) * sub4;
 
         sub1 + sub2 + sub3 + sub4
     }
@@ -3417,13 +4971,12 @@ impl<T: Trait> Module<T> {
         origin_power * base
     }
 
-    fn compute_price_power(commodity_price: PowerSize) -> PowerSize {
+    fn compute_price_power(weights: &PowerWeightProfile, commodity_price: PowerSize) -> PowerSize {
         let max = <MaxGoodsPrice>::get();
         if max == 0 {
             0
         } else {
-            let base = Permill::from_percent(T::TopWeightAccountStake::get() as u32)
-                * FLOAT_COMPUTE_PRECISION;
+            let base = Permill::from_percent(weights.top.4 as u32) * FLOAT_COMPUTE_PRECISION;
             Permill::from_rational_approximation(commodity_price as u32, max as u32) * base
         }
     }
@@ -3638,11 +5191,14 @@ impl<T: Trait> Module<T> {
         }
     }
 
-    fn leader_board_lottery(block: T::BlockNumber, app_id: u32, model_id: &Vec<u8>) {
-        let seed = T::Randomness::random(b"ctt_power");
-        // seed needs to be guaranteed to be 32 bytes.
-        let seed = <[u8; 32]>::decode(&mut TrailingZeroInput::new(seed.as_ref()))
-            .expect("input is padded with zeroes; qed");
+    // returns board length and comment pool entries gone through, for weight
+    fn leader_board_lottery(
+        block: T::BlockNumber,
+        app_id: u32,
+        model_id: &Vec<u8>,
+    ) -> Result<(u32, u32), dispatch::DispatchError> {
+        // boards of same block must not share one seed
+        let seed = Self::random_seed(&(b"ctt_power", app_id, model_id).encode());
         let mut rng = ChaChaRng::from_seed(seed);
         let mut pdc_map: BTreeMap<T::Hash, ()> = BTreeMap::new();
 
@@ -3655,7 +5211,7 @@ impl<T: Trait> Module<T> {
 
         if board.len() == 0 {
             print("board empty");
-            return;
+            return Ok((0, 0));
         }
 
         // get this board(appid, model_id) total items count
@@ -3669,7 +5225,7 @@ impl<T: Trait> Module<T> {
 
         if total == 0 {
             print("total commodity empty");
-            return;
+            return Ok((board.len() as u32, 0));
         }
 
         // get board items count
@@ -3686,6 +5242,7 @@ impl<T: Trait> Module<T> {
 
         // hit records
         let mut records: Vec<T::AccountId> = vec![];
+        let mut pool_entries: u32 = 0;
 
         // get max comment info
         let max = <CommentMaxInfoPerDocMap>::get(app_id);
@@ -3693,6 +5250,7 @@ impl<T: Trait> Module<T> {
         let mut attend_lottery = |doc_id: &Vec<u8>, is_pub: bool| {
             let comment_set =
                 <DocumentCommentsAccountPool<T>>::get(&T::Hashing::hash_of(&(app_id, doc_id)));
+            pool_entries += comment_set.len() as u32;
             // this numbers should be put into config
             let hit_max = min(Percent::from_percent(30) * comment_set.len(), 100);
             let mut weight_pool: Vec<u32> = vec![];
@@ -3810,50 +5368,638 @@ impl<T: Trait> Module<T> {
             accounts: records,
         };
         <AppLeaderBoardRcord<T>>::insert(&lottery_record_key, &record);
+        <AppLeaderBoardSeed<T>>::insert(&lottery_record_key, seed);
+        Self::setup_leader_board_reward(
+            &lottery_record_key,
+            block,
+            app_id,
+            model_id,
+            record.accounts.len(),
+        )?;
         <AppLeaderBoardLastTime<T>>::insert(&lottery_last_time_key, block);
-        // update sequence keys
-        let mut keys = <AppLeaderBoardSequenceKeys<T>>::get();
-        keys.push((app_id, block, model_id.clone()));
-        <AppLeaderBoardSequenceKeys<T>>::put(keys);
-    }
-
-    fn update_document_comment_pool(
-        new_comment: &KPCommentData<T::AccountId, T::Hash>,
-        doc: &KPDocumentData<T::AccountId, T::Hash>,
-    ) {
-        let key = T::Hashing::hash_of(&(doc.app_id, &doc.document_id));
-        let mut pool = <DocumentCommentsAccountPool<T>>::get(&key);
-
-        let pool_item = CommentWeightData {
-            account: new_comment.sender.clone(),
-            position: doc.comment_count,
-            cash_cost: new_comment.comment_fee,
-        };
+        Self::add_leader_board_history(app_id, block, model_id);
 
-        pool.push(pool_item);
-        <DocumentCommentsAccountPool<T>>::insert(&key, pool);
+        Ok((board.len() as u32, pool_entries))
     }
 
-    fn update_max_goods_price(price: PowerSize) {
-        let current_max = <MaxGoodsPrice>::get();
-        if price > current_max {
-            <MaxGoodsPrice>::put(price);
+    fn add_leader_board_history(app_id: u32, block: T::BlockNumber, model_id: &Vec<u8>) {
+        let key = T::Hashing::hash_of(&(app_id, model_id));
+        if !<AppLeaderBoardHistory<T>>::contains_key(&key) {
+            <AppLeaderBoardModels>::append(app_id, model_id);
         }
+        <AppLeaderBoardHistory<T>>::append(&key, block);
     }
 
-    fn insert_document_power(
-        doc: &KPDocumentData<T::AccountId, T::Hash>,
-        content_power: PowerSize,
-        judge_power: PowerSize,
-    ) {
-        let key = T::Hashing::hash_of(&(doc.app_id, &doc.document_id));
-        let power = DocumentPower {
-            attend: 0,
-            content: content_power,
-            judge: judge_power,
-        };
+    // move AppLeaderBoardSequenceKeys into AppLeaderBoardHistory
+    fn migrate_leader_board_sequence_keys() -> Weight {
+        let db = T::DbWeight::get();
+        if !<AppLeaderBoardSequenceKeys<T>>::exists() {
+            return db.reads(1);
+        }
 
-        <KPDocumentPowerByIdHash<T>>::insert(&key, &power);
+        let keys = <AppLeaderBoardSequenceKeys<T>>::take();
+        for (app_id, block, model_id) in keys.iter() {
+            Self::add_leader_board_history(*app_id, *block, model_id);
+        }
+
+        let n = keys.len() as Weight;
+        db.reads_writes(1 + n, 1 + 2 * n)
+    }
+
+    fn is_leader_board_due(block: T::BlockNumber, app_id: u32, model_id: &Vec<u8>) -> bool {
+        let last_key = T::Hashing::hash_of(&(app_id, model_id));
+        if !<AppLeaderBoardLastTime<T>>::contains_key(&last_key) {
+            return true;
+        }
+
+        let last_block = <AppLeaderBoardLastTime<T>>::get(&last_key);
+        block - last_block > Self::active_economic_params().app_leader_board_interval
+    }
+
+    // returns weight of the snapshot taken, draw is rolled back when its reward pool can not be funded
+    fn create_leader_board_snapshot(
+        block: T::BlockNumber,
+        app_id: u32,
+        model_id: Vec<u8>,
+    ) -> Result<Weight, dispatch::DispatchError> {
+        let (board_len, pool_entries) = with_transaction(|| {
+            let result = Self::leader_board_lottery(block, app_id, &model_id);
+            match result {
+                Ok(_) => TransactionOutcome::Commit(result),
+                Err(_) => TransactionOutcome::Rollback(result),
+            }
+        })?;
+        Self::deposit_event(RawEvent::LeaderBoardsCreated(block, app_id, model_id));
+        Ok(T::WeightInfo::create_power_leader_board(
+            board_len,
+            pool_entries,
+        ))
+    }
+
+    fn schedule_leader_board(app_id: u32, model_id: &Vec<u8>) {
+        let key = T::Hashing::hash_of(&(app_id, model_id));
+        if <AppLeaderBoardScheduled<T>>::contains_key(&key) {
+            return;
+        }
+
+        let index = <AppLeaderBoardScheduleCount>::get();
+        <AppLeaderBoardSchedule>::insert(index, (app_id, model_id.clone()));
+        <AppLeaderBoardScheduled<T>>::insert(&key, index);
+        <AppLeaderBoardScheduleCount>::put(index + 1);
+    }
+
+    // remove board at index, last board moves into its place
+    fn unschedule_leader_board(index: u32, app_id: u32, model_id: &Vec<u8>) {
+        let last = <AppLeaderBoardScheduleCount>::get().saturating_sub(1);
+        if index != last {
+            if let Some((last_app_id, last_model_id)) = <AppLeaderBoardSchedule>::take(last) {
+                let last_key = T::Hashing::hash_of(&(last_app_id, &last_model_id));
+                <AppLeaderBoardScheduled<T>>::insert(&last_key, index);
+                <AppLeaderBoardSchedule>::insert(index, (last_app_id, last_model_id));
+            }
+        } else {
+            <AppLeaderBoardSchedule>::remove(index);
+        }
+
+        <AppLeaderBoardScheduled<T>>::remove(T::Hashing::hash_of(&(app_id, model_id)));
+        <AppLeaderBoardScheduleCount>::put(last);
+    }
+
+    // check a bounded window of scheduled boards each block, go on from last position next block
+    fn schedule_leader_board_snapshots(block: T::BlockNumber) -> Weight {
+        let db = T::DbWeight::get();
+        let mut total = <AppLeaderBoardScheduleCount>::get();
+        if total == 0 {
+            return db.reads(1);
+        }
+
+        let budget = min(T::AppLeaderBoardSnapshotsPerBlock::get(), total);
+        let mut cursor = <AppLeaderBoardScheduleCursor>::get() % total;
+        let mut weight = db.reads_writes(2, 1);
+
+        for _ in 0..budget {
+            if total == 0 {
+                break;
+            }
+            if cursor >= total {
+                cursor = 0;
+            }
+
+            // schedule entry, app, model and last time
+            weight = weight.saturating_add(db.reads(5));
+            let (app_id, model_id) = match <AppLeaderBoardSchedule>::get(cursor) {
+                Some(board) => board,
+                None => {
+                    cursor += 1;
+                    continue;
+                }
+            };
+
+            // boards of retired, disabled, killed models and deregistered apps leave the schedule,
+            // the board moved into this index is checked next
+            if !T::Membership::is_valid_app(app_id)
+                || (!model_id.is_empty() && !Self::is_valid_model(app_id, &model_id))
+            {
+                Self::unschedule_leader_board(cursor, app_id, &model_id);
+                total -= 1;
+                weight = weight.saturating_add(db.reads_writes(1, 4));
+                continue;
+            }
+
+            cursor += 1;
+            if !Self::is_leader_board_due(block, app_id, &model_id) {
+                continue;
+            }
+
+            // board not funded keeps its last time, so it is drawn again next round
+            match Self::create_leader_board_snapshot(block, app_id, model_id) {
+                Ok(snapshot_weight) => weight = weight.saturating_add(snapshot_weight),
+                Err(_) => {
+                    print("leader board reward not funded, snapshot deferred");
+                    weight = weight.saturating_add(T::WeightInfo::create_power_leader_board(
+                        T::AppLeaderBoardMaxPos::get(),
+                        Self::leader_board_lottery_weight_bound(),
+                    ));
+                }
+            }
+        }
+
+        <AppLeaderBoardScheduleCursor>::put(if total == 0 { 0 } else { cursor % total });
+        weight
+    }
+
+    // move reward pool from treasury to pot, winners share it by lottery hits
+    fn setup_leader_board_reward(
+        key: &T::Hash,
+        block: T::BlockNumber,
+        app_id: u32,
+        model_id: &Vec<u8>,
+        hits: usize,
+    ) -> dispatch::DispatchResult {
+        if hits == 0 {
+            return Ok(());
+        }
+
+        let per_hit = Self::leader_board_reward(app_id, model_id) / (hits as u32).into();
+        let total = per_hit * (hits as u32).into();
+        if total == 0u32.into() {
+            return Ok(());
+        }
+
+        let treasury_account: T::AccountId = T::TreasuryModuleId::get().into_account();
+        let pot_account: T::AccountId = T::LeaderBoardRewardModuleId::get().into_account();
+        T::Currency::transfer(&treasury_account, &pot_account, total, KeepAlive)
+            .map_err(|_| Error::<T>::LeaderBoardRewardNotFunded)?;
+
+        let end_block = block + T::LeaderBoardRewardClaimPeriod::get();
+        <LeaderBoardRewardRecords<T>>::insert(
+            key,
+            LeaderBoardRewardRecord {
+                app_id,
+                model_id: model_id.clone(),
+                block,
+                total,
+                per_hit,
+                claimed: 0u32.into(),
+                end_block,
+                is_released: false,
+            },
+        );
+
+        // release at the first block after claim window
+        <LeaderBoardRewardExpiry<T>>::mutate(end_block + 1u32.into(), |keys| keys.push(*key));
+        Ok(())
+    }
+
+    fn leader_board_reward(app_id: u32, model_id: &Vec<u8>) -> BalanceOf<T> {
+        <LeaderBoardRewardPools<T>>::get(app_id, model_id).unwrap_or_else(T::LeaderBoardReward::get)
+    }
+
+    fn leader_board_account_reward(
+        key: &T::Hash,
+        record: &LeaderBoardRewardRecord<BalanceOf<T>, T::BlockNumber>,
+        account: &T::AccountId,
+    ) -> BalanceOf<T> {
+        let hits = <AppLeaderBoardRcord<T>>::get(key)
+            .accounts
+            .iter()
+            .filter(|x| *x == account)
+            .count() as u32;
+
+        record.per_hit * hits.into()
+    }
+
+    // give back unclaimed leader board rewards whose claim window ended
+    fn release_leader_board_rewards(block: T::BlockNumber) -> Weight {
+        let db = T::DbWeight::get();
+        if !<LeaderBoardRewardExpiry<T>>::contains_key(block) {
+            return db.reads(1);
+        }
+
+        let keys = <LeaderBoardRewardExpiry<T>>::take(block);
+        let treasury_account: T::AccountId = T::TreasuryModuleId::get().into_account();
+        let pot_account: T::AccountId = T::LeaderBoardRewardModuleId::get().into_account();
+
+        for key in keys.iter() {
+            let mut record = <LeaderBoardRewardRecords<T>>::get(key);
+            if record.is_released {
+                continue;
+            }
+
+            let left = record.total.saturating_sub(record.claimed);
+            if left > 0u32.into()
+                && T::Currency::transfer(&pot_account, &treasury_account, left, AllowDeath).is_err()
+            {
+                print("leader board reward release failed");
+                continue;
+            }
+
+            record.is_released = true;
+            <LeaderBoardRewardRecords<T>>::insert(key, &record);
+
+            Self::deposit_event(RawEvent::LeaderBoardRewardReleased(
+                record.block,
+                record.app_id,
+                record.model_id,
+                left,
+            ));
+        }
+
+        let n = keys.len() as Weight;
+        db.reads_writes(1 + 3 * n, 1 + 3 * n)
+    }
+
+    fn update_document_comment_pool(
+        new_comment: &KPCommentData<T::AccountId, T::Hash>,
+        doc: &KPDocumentData<T::AccountId, T::Hash>,
+        position: PowerSize,
+    ) {
+        let key = T::Hashing::hash_of(&(doc.app_id, &doc.document_id));
+        let mut pool = <DocumentCommentsAccountPool<T>>::get(&key);
+
+        let pool_item = CommentWeightData {
+            account: new_comment.sender.clone(),
+            position,
+            cash_cost: new_comment.comment_fee,
+        };
+
+        if pool.len() < T::DocumentCommentPoolMax::get() as usize {
+            pool.push(pool_item);
+        } else {
+            // pool full, evict the lowest ranked one if new comment ranks higher
+            let lowest = pool
+                .iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| a.pool_rank(b))
+                .map(|(index, item)| (index, item.pool_rank(&pool_item)));
+
+            match lowest {
+                Some((index, Ordering::Less)) => {
+                    pool.remove(index);
+                    pool.push(pool_item);
+                }
+                _ => return,
+            }
+        }
+
+        <DocumentCommentsAccountPool<T>>::insert(&key, pool);
+    }
+
+    // moderated comments do not give their position back, so pool items never share one
+    fn next_comment_position(doc_key_hash: &T::Hash, comment_count: PowerSize) -> PowerSize {
+        // documents commented before positions were tracked used the comment count
+        let position =
+            <DocumentCommentPosition<T>>::get(doc_key_hash).map_or(comment_count, |last| last + 1);
+        <DocumentCommentPosition<T>>::insert(doc_key_hash, position);
+        position
+    }
+
+    // keep top ranked comments of pool, in position order
+    fn trim_document_comment_pool(pool: &mut Vec<CommentWeightData<T>>) {
+        pool.sort_by(|a, b| b.pool_rank(a));
+        pool.truncate(T::DocumentCommentPoolMax::get() as usize);
+        pool.sort();
+    }
+
+    // schedule boards of all active models, boards used to join at their first update
+    fn migrate_leader_board_schedule() -> Weight {
+        let mut reads: Weight = 0;
+        let mut models: Vec<(u32, Vec<u8>)> = vec![];
+        for (_, model) in <KPModelDataByIdHash<T>>::iter() {
+            reads += 1;
+            if model.status == ModelStatus::ENABLED {
+                models.push((model.app_id, model.model_id));
+            }
+        }
+
+        for (app_id, model_id) in models.iter() {
+            Self::schedule_leader_board(*app_id, model_id);
+            Self::schedule_leader_board(*app_id, &vec![]);
+        }
+
+        let n = models.len() as Weight;
+        T::DbWeight::get().reads_writes(reads + 4 * n, 6 * n)
+    }
+
+    // without a settled block, power stored before decay would never decay while total power does
+    fn migrate_power_settled_at() -> Weight {
+        let now = <system::Module<T>>::block_number();
+        let mut reads: Weight = 1;
+        let mut writes: Weight = 0;
+
+        let commodities: Vec<T::Hash> = <KPPurchasePowerByIdHash<T>>::iter()
+            .map(|(key, _)| key)
+            .collect();
+        for key in commodities.iter() {
+            reads += 2;
+            if !<KPPurchasePowerSettledAt<T>>::contains_key(key) {
+                <KPPurchasePowerSettledAt<T>>::insert(key, now);
+                writes += 1;
+            }
+        }
+
+        let miners: Vec<(T::AccountId, PowerSize)> = <MinerPowerByAccount<T>>::iter().collect();
+        for (account, power) in miners.iter() {
+            reads += 2;
+            if !<MinerPowerSettledAt<T>>::contains_key(account) {
+                <MinerRawPowerByAccount<T>>::insert(account, power);
+                <MinerPowerSettledAt<T>>::insert(account, now);
+                writes += 2;
+            }
+        }
+
+        if <TotalPowerSettledAt<T>>::get().is_none() {
+            TotalRawPower::put(TotalPower::get());
+            <TotalPowerSettledAt<T>>::put(now);
+            writes += 2;
+        }
+
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+
+    // app ids of a type are handed out upwards, reserved balance of an admin can't tell the app
+    // stake from other deposits, so legacy apps are recorded with nothing to return
+    fn migrate_app_stakes() -> Weight {
+        let ranges: Vec<(Vec<u8>, u32)> = <AppIdRange<T>>::iter()
+            .map(|(app_type, info)| (app_type, info.0))
+            .collect();
+        let mut reads = ranges.len() as Weight;
+        let mut records: Vec<(u32, AppStakeRecord<T::AccountId, BalanceOf<T>>)> = vec![];
+        for (app_id, _) in <AppModelTotalConfig>::iter() {
+            reads += 3;
+            if <AppStakes<T>>::contains_key(app_id)
+                || !(T::Membership::is_valid_app(app_id) || T::Membership::is_app_suspended(app_id))
+            {
+                continue;
+            }
+
+            reads += 1;
+            let app_type = ranges
+                .iter()
+                .filter(|(_, current_id)| *current_id >= app_id)
+                .min_by_key(|(_, current_id)| *current_id)
+                .map(|(app_type, _)| app_type.clone())
+                .unwrap_or_default();
+            let owner = T::Membership::get_app_admins(app_id)
+                .first()
+                .cloned()
+                .unwrap_or_default();
+            records.push((
+                app_id,
+                AppStakeRecord {
+                    app_type,
+                    owner,
+                    stake: 0u32.into(),
+                },
+            ));
+        }
+
+        for (app_id, record) in records.iter() {
+            <AppStakes<T>>::insert(app_id, record);
+        }
+
+        T::DbWeight::get().reads_writes(reads, records.len() as Weight)
+    }
+
+    // killed models used to stay in the app model count though they can never be retired
+    fn migrate_app_model_counts() -> Weight {
+        let mut reads: Weight = 0;
+        let mut counts: BTreeMap<u32, u32> = BTreeMap::new();
+        for (key, model) in <KPModelDataByIdHash<T>>::iter() {
+            reads += 2;
+            if model.status == ModelStatus::ENABLED || <ModelRetireRecords<T>>::contains_key(&key) {
+                *counts.entry(model.app_id).or_insert(0) += 1;
+            }
+        }
+
+        let apps: Vec<u32> = <AppModelCount>::iter().map(|(app_id, _)| app_id).collect();
+        for app_id in apps.iter() {
+            <AppModelCount>::insert(app_id, counts.get(app_id).cloned().unwrap_or_default());
+        }
+
+        let n = apps.len() as Weight;
+        T::DbWeight::get().reads_writes(reads + n, n)
+    }
+
+    fn migrate_product_refer_documents() -> Weight {
+        let mut reads: Weight = 0;
+        let mut refers: Vec<(T::Hash, T::Hash)> = vec![];
+        for (doc_key, doc) in <KPDocumentDataByIdHash<T>>::iter() {
+            reads += 1;
+            match doc.document_data {
+                DocumentSpecificData::ProductIdentify(_) | DocumentSpecificData::ProductTry(_) => {
+                    refers.push((T::Hashing::hash_of(&(doc.app_id, &doc.product_id)), doc_key));
+                }
+                _ => {}
+            }
+        }
+
+        for (product_key, doc_key) in refers.iter() {
+            <KPProductReferDocuments<T>>::insert(product_key, doc_key, ());
+        }
+
+        T::DbWeight::get().reads_writes(reads, refers.len() as Weight)
+    }
+
+    fn migrate_document_comment_pools() -> Weight {
+        let max = T::DocumentCommentPoolMax::get() as usize;
+        let mut reads: Weight = 0;
+        let mut oversized: Vec<(T::Hash, Vec<CommentWeightData<T>>)> = vec![];
+
+        for (key, pool) in <DocumentCommentsAccountPool<T>>::iter() {
+            reads += 1;
+            if pool.len() > max {
+                oversized.push((key, pool));
+            }
+        }
+
+        let writes = oversized.len() as Weight;
+        for (key, mut pool) in oversized {
+            Self::trim_document_comment_pool(&mut pool);
+            <DocumentCommentsAccountPool<T>>::insert(&key, pool);
+        }
+
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+
+    fn migrate_app_financed_last() -> Weight {
+        let db = T::DbWeight::get();
+        let key = <AppFinancedLast<T>>::take();
+        let record: AppFinancedDataV8<BalanceOf<T>, T::BlockNumber> =
+            match unhashed::get(&<AppFinancedRecord<T>>::hashed_key_for(&key)) {
+                Some(record) => record,
+                None => return db.reads_writes(2, 1),
+            };
+
+        <AppFinancedLastOfApp<T>>::insert(record.app_id, key);
+        if record.exchange_end_block >= <system::Module<T>>::block_number() {
+            <AppFinancedOpenRounds<T>>::put(vec![(
+                record.app_id,
+                record.exchange_end_block,
+                record.amount,
+            )]);
+        }
+
+        db.reads_writes(3, 3)
+    }
+
+    // rounds opened before the end blocks were kept close with the current exchange period,
+    // open rounds were dropped at exchange end and are rebuilt up to compensate end
+    fn migrate_app_financed_end_blocks() -> Weight {
+        let now = <system::Module<T>>::block_number();
+        let exchange_period = Self::active_economic_params().app_finance_exchange_period;
+        let translated = sp_std::cell::Cell::new(0 as Weight);
+        let open_rounds = sp_std::cell::RefCell::new(vec![]);
+        let translate = |_, old: AppFinancedDataV8<BalanceOf<T>, T::BlockNumber>| {
+            translated.set(translated.get() + 1);
+            let compensate_end_block = old.exchange_end_block + exchange_period;
+            if compensate_end_block >= now {
+                open_rounds
+                    .borrow_mut()
+                    .push((old.app_id, compensate_end_block, old.amount));
+            }
+            Some(AppFinancedData {
+                app_id: old.app_id,
+                proposal_id: old.proposal_id,
+                amount: old.amount,
+                exchange: old.exchange,
+                block: old.block,
+                total_balance: old.total_balance,
+                exchanged: old.exchanged,
+                exchange_end_block: old.exchange_end_block,
+                confirm_end_block: old.exchange_end_block + exchange_period / 2u32.into(),
+                compensate_end_block,
+            })
+        };
+
+        <AppFinancedRecord<T>>::translate(translate);
+        <AppFinancedOpenRounds<T>>::put(open_rounds.into_inner());
+
+        T::DbWeight::get().reads_writes(translated.get() + 1, translated.get() + 1)
+    }
+
+    // appeals so far reserved the bond constant, which has not changed since
+    fn migrate_model_dispute_appeal_bonds() -> Weight {
+        let bond = T::ModelDisputeBond::get();
+        let translated = sp_std::cell::Cell::new(0 as Weight);
+        let translate =
+            |_, old: ModelDisputeCaseV10<T::AccountId, BalanceOf<T>, T::BlockNumber, T::Hash>| {
+                translated.set(translated.get() + 1);
+                let appeal_bond = if old.appellant.is_some() {
+                    bond
+                } else {
+                    0u32.into()
+                };
+                Some(ModelDisputeCase {
+                    app_id: old.app_id,
+                    model_id: old.model_id,
+                    comment_id: old.comment_id,
+                    dispute_type: old.dispute_type,
+                    reporter: old.reporter,
+                    owner: old.owner,
+                    bond: old.bond,
+                    status: old.status,
+                    evidence_end: old.evidence_end,
+                    vote_end: old.vote_end,
+                    appeal_end: old.appeal_end,
+                    evidences: old.evidences,
+                    ayes: old.ayes,
+                    nays: old.nays,
+                    upheld: old.upheld,
+                    appellant: old.appellant,
+                    appeal_bond,
+                })
+            };
+
+        <ModelDisputeCases<T>>::translate(translate);
+
+        T::DbWeight::get().reads_writes(translated.get(), translated.get())
+    }
+
+    fn migrate_power_weight_profiles() -> Weight {
+        let defaults = Self::default_power_weight_profile();
+        let translated = sp_std::cell::Cell::new(0 as Weight);
+        let translate = |_, old: PowerWeightProfileV11| {
+            translated.set(translated.get() + 1);
+            Some(PowerWeightProfile {
+                top: old.top,
+                document: old.document,
+                comment: old.comment,
+                publish: old.publish,
+                identify: old.identify,
+                product_try: old.product_try,
+                choose: old.choose,
+                model: old.model,
+                cm: old.cm,
+                comment_cm: defaults.comment_cm,
+                comment_power: defaults.comment_power,
+            })
+        };
+
+        <AppPowerWeightProfiles>::translate(translate);
+        <PendingPowerWeightProfiles>::translate(translate);
+
+        T::DbWeight::get().reads_writes(translated.get(), translated.get())
+    }
+
+    fn migrate_exchange_records() -> Weight {
+        let translated = sp_std::cell::Cell::new(0 as Weight);
+        let translate = |_, old: AppFinancedUserExchangeDataV3<BalanceOf<T>>| {
+            translated.set(translated.get() + 1);
+            Some(AppFinancedUserExchangeData {
+                exchange_amount: old.exchange_amount,
+                status: old.status,
+                pay_id: old.pay_id,
+                transitions: vec![],
+            })
+        };
+
+        <AppFinancedUserExchangeRecord<T>>::translate(translate);
+        <AppCycleIncomeExchangeRecords<T>>::translate(translate);
+
+        T::DbWeight::get().reads_writes(translated.get(), translated.get())
+    }
+
+    fn update_max_goods_price(price: PowerSize) {
+        let current_max = <MaxGoodsPrice>::get();
+        if price > current_max {
+            <MaxGoodsPrice>::put(price);
+        }
+    }
+
+    fn insert_document_power(
+        doc: &KPDocumentData<T::AccountId, T::Hash>,
+        content_power: PowerSize,
+        judge_power: PowerSize,
+    ) {
+        let key = T::Hashing::hash_of(&(doc.app_id, &doc.document_id));
+        let power = DocumentPower {
+            attend: 0,
+            content: content_power,
+            judge: judge_power,
+        };
+
+        <KPDocumentPowerByIdHash<T>>::insert(&key, &power);
     }
 
     fn update_document_power(
@@ -3924,6 +6070,7 @@ impl<T: Trait> Module<T> {
 
                 // compute power
                 let content_power = Self::compute_publish_product_content_power(
+                    &Self::power_weight_profile(app_id),
                     para_issue_rate_p,
                     self_issue_rate_p,
                     attend_rate_p,
@@ -3938,8 +6085,11 @@ impl<T: Trait> Module<T> {
         <KPDocumentDataByIdHash<T>>::insert(&publish_doc_key, &doc);
     }
 
-    // only invoked when creating document
-    fn process_document_content_power(doc: &KPDocumentData<T::AccountId, T::Hash>) {
+    // content power and initial judge power of document, app max params are updated meanwhile
+    fn compute_document_content_power(
+        doc: &KPDocumentData<T::AccountId, T::Hash>,
+    ) -> (PowerSize, PowerSize) {
+        let weights = Self::power_weight_profile(doc.app_id);
         let content_power;
         let initial_judge_power;
 
@@ -3969,6 +6119,7 @@ impl<T: Trait> Module<T> {
 
                 // compute power
                 content_power = Self::compute_publish_product_content_power(
+                    &weights,
                     para_issue_rate_p,
                     self_issue_rate_p,
                     attend_rate_p,
@@ -3976,10 +6127,9 @@ impl<T: Trait> Module<T> {
 
                 initial_judge_power = Self::compute_judge_power(
                     Self::compute_doc_trend_power(&doc),
-                    T::TopWeightProductPublish::get() as PowerSize,
-                    T::DocumentPowerWeightJudge::get(),
+                    weights.top.0 as PowerSize,
+                    weights.document.2,
                 );
-                Self::insert_document_power(&doc, content_power, initial_judge_power);
             }
             DocumentSpecificData::ProductIdentify(data) => {
                 let params_max = <DocumentIdentifyMaxParams>::get(doc.app_id);
@@ -4007,6 +6157,7 @@ impl<T: Trait> Module<T> {
                 );
 
                 content_power = Self::compute_identify_content_power(
+                    &weights,
                     ident_rate_p,
                     ident_consistence_p,
                     seller_consistence_p,
@@ -4014,13 +6165,9 @@ impl<T: Trait> Module<T> {
 
                 initial_judge_power = Self::compute_judge_power(
                     Self::compute_doc_trend_power(&doc),
-                    T::TopWeightDocumentIdentify::get() as PowerSize,
-                    T::DocumentPowerWeightJudge::get(),
+                    weights.top.1 as PowerSize,
+                    weights.document.2,
                 );
-
-                Self::update_max_goods_price(data.goods_price);
-                Self::insert_document_power(&doc, content_power, initial_judge_power);
-                Self::process_publish_doc_content_refer_power(doc.app_id, &doc.product_id, 1);
             }
             DocumentSpecificData::ProductTry(data) => {
                 let params_max = <DocumentTryMaxParams>::get(doc.app_id);
@@ -4048,6 +6195,7 @@ impl<T: Trait> Module<T> {
                 );
 
                 content_power = Self::compute_try_content_power(
+                    &weights,
                     offset_rate_p,
                     true_rate_p,
                     seller_consistence_p,
@@ -4055,13 +6203,9 @@ impl<T: Trait> Module<T> {
 
                 initial_judge_power = Self::compute_judge_power(
                     Self::compute_doc_trend_power(&doc),
-                    T::TopWeightDocumentTry::get() as PowerSize,
-                    T::DocumentPowerWeightJudge::get(),
+                    weights.top.2 as PowerSize,
+                    weights.document.2,
                 );
-
-                Self::update_max_goods_price(data.goods_price);
-                Self::insert_document_power(&doc, content_power, initial_judge_power);
-                Self::process_publish_doc_content_refer_power(doc.app_id, &doc.product_id, 1);
             }
             DocumentSpecificData::ProductChoose(data) => {
                 let params_max = <DocumentChooseMaxParams>::get(doc.app_id);
@@ -4077,14 +6221,14 @@ impl<T: Trait> Module<T> {
                     })
                 });
 
-                content_power = Self::compute_choose_content_power(sell_count_p, try_count_p);
+                content_power =
+                    Self::compute_choose_content_power(&weights, sell_count_p, try_count_p);
 
                 initial_judge_power = Self::compute_judge_power(
                     Self::compute_doc_trend_power(&doc),
                     100 as PowerSize,
-                    T::DocumentCMPowerWeightJudge::get(),
+                    weights.cm.3,
                 );
-                Self::insert_document_power(&doc, content_power, initial_judge_power);
             }
             DocumentSpecificData::ModelCreate(data) => {
                 let params_max = <DocumentModelCreateMaxParams>::get(doc.app_id);
@@ -4103,17 +6247,38 @@ impl<T: Trait> Module<T> {
                     });
 
                 content_power =
-                    Self::compute_model_content_power(producer_count_p, product_count_p);
+                    Self::compute_model_content_power(&weights, producer_count_p, product_count_p);
 
                 initial_judge_power = Self::compute_judge_power(
                     Self::compute_doc_trend_power(&doc),
                     100 as PowerSize,
-                    T::DocumentCMPowerWeightJudge::get(),
+                    weights.cm.3,
                 );
-                Self::insert_document_power(&doc, content_power, initial_judge_power);
             }
         }
 
+        (content_power, initial_judge_power)
+    }
+
+    // only invoked when creating document
+    fn process_document_content_power(doc: &KPDocumentData<T::AccountId, T::Hash>) {
+        let (content_power, initial_judge_power) = Self::compute_document_content_power(doc);
+        Self::insert_document_power(doc, content_power, initial_judge_power);
+
+        match &doc.document_data {
+            DocumentSpecificData::ProductIdentify(data) => {
+                Self::update_max_goods_price(data.goods_price);
+                Self::process_publish_doc_content_refer_power(doc.app_id, &doc.product_id, 1);
+                Self::insert_product_refer_document(doc);
+            }
+            DocumentSpecificData::ProductTry(data) => {
+                Self::update_max_goods_price(data.goods_price);
+                Self::process_publish_doc_content_refer_power(doc.app_id, &doc.product_id, 1);
+                Self::insert_product_refer_document(doc);
+            }
+            _ => {}
+        }
+
         // update account document store record
         let owner_account = Self::convert_account(&doc.owner);
         let mut owner_doc_ids = <AccountDocumentSet<T>>::get(&owner_account, doc.app_id);
@@ -4122,7 +6287,7 @@ impl<T: Trait> Module<T> {
         <AccountDocumentSet<T>>::insert(&owner_account, doc.app_id, owner_doc_ids);
     }
 
-    fn give_comment_reward(is_normal: bool, owner: &T::AccountId, cost: u64) {
+    fn give_comment_reward(is_normal: bool, owner: &T::AccountId, cost: u64) -> BalanceOf<T> {
         let rate = if is_normal {
             Permill::from_percent(T::CommentRewardNormalRate::get())
         } else {
@@ -4135,10 +6300,54 @@ impl<T: Trait> Module<T> {
         amount = rate * amount;
 
         let treasury_account: T::AccountId = T::TreasuryModuleId::get().into_account();
-        T::Currency::transfer(&treasury_account, owner, amount, KeepAlive).ok();
+        match T::Currency::transfer(&treasury_account, owner, amount, KeepAlive) {
+            Ok(_) => amount,
+            Err(_) => 0u32.into(),
+        }
+    }
+
+    // (account attend weight, comment power weight, doc comment top weight, doc judge weight,
+    // comment attend weights)
+    fn comment_power_weights(
+        weights: &PowerWeightProfile,
+        document_type: &DocumentType,
+    ) -> (PowerSize, PowerSize, PowerSize, u8, (u8, u8, u8, u8)) {
+        match document_type {
+            DocumentType::ProductPublish => (
+                weights.top.3 as PowerSize,
+                weights.comment_power as PowerSize,
+                weights.top.0 as PowerSize,
+                weights.document.2,
+                weights.comment,
+            ),
+            DocumentType::ProductIdentify => (
+                weights.top.3 as PowerSize,
+                weights.comment_power as PowerSize,
+                weights.top.1 as PowerSize,
+                weights.document.2,
+                weights.comment,
+            ),
+            DocumentType::ProductTry => (
+                weights.top.3 as PowerSize,
+                weights.comment_power as PowerSize,
+                weights.top.2 as PowerSize,
+                weights.document.2,
+                weights.comment,
+            ),
+            DocumentType::ProductChoose | DocumentType::ModelCreate => (
+                weights.cm.0 as PowerSize,
+                weights.cm.1 as PowerSize,
+                100 as PowerSize,
+                weights.cm.3,
+                weights.comment_cm,
+            ),
+            _ => (0, 0, 0, 0, weights.comment),
+        }
     }
 
-    fn process_comment_power(comment: &KPCommentData<T::AccountId, T::Hash>) {
+    fn process_comment_power(
+        comment: &KPCommentData<T::AccountId, T::Hash>,
+    ) -> KPCommentEffect<BalanceOf<T>> {
         // target compute
         let account_comment_power: PowerSize;
         let doc_comment_power: PowerSize;
@@ -4171,47 +6380,18 @@ impl<T: Trait> Module<T> {
             account_comment_unit_fee,
         );
 
-        let mut account_attend_weight: PowerSize = 0;
-        let mut comment_power_weight: PowerSize = 0;
-        let mut doc_comment_top_weight: PowerSize = 0;
-        let mut doc_judge_weight: u8 = 0;
-
         // according doc type to decide weight
-        match doc.document_type {
-            DocumentType::ProductPublish => {
-                account_attend_weight = T::TopWeightAccountAttend::get() as PowerSize;
-                comment_power_weight = T::DocumentPowerWeightAttend::get() as PowerSize;
-                doc_comment_top_weight = T::TopWeightProductPublish::get() as PowerSize;
-                doc_judge_weight = T::DocumentPowerWeightJudge::get()
-            }
-            DocumentType::ProductIdentify => {
-                account_attend_weight = T::TopWeightAccountAttend::get() as PowerSize;
-                comment_power_weight = T::DocumentPowerWeightAttend::get() as PowerSize;
-                doc_comment_top_weight = T::TopWeightDocumentIdentify::get() as PowerSize;
-                doc_judge_weight = T::DocumentPowerWeightJudge::get()
-            }
-            DocumentType::ProductTry => {
-                account_attend_weight = T::TopWeightAccountAttend::get() as PowerSize;
-                comment_power_weight = T::DocumentPowerWeightAttend::get() as PowerSize;
-                doc_comment_top_weight = T::TopWeightDocumentTry::get() as PowerSize;
-                doc_judge_weight = T::DocumentPowerWeightJudge::get()
-            }
-            DocumentType::ProductChoose => {
-                account_attend_weight = T::CMPowerAccountAttend::get() as PowerSize;
-                comment_power_weight = T::DocumentCMPowerWeightAttend::get() as PowerSize;
-                doc_comment_top_weight = 100 as PowerSize;
-                doc_judge_weight = T::DocumentCMPowerWeightJudge::get();
-            }
-            DocumentType::ModelCreate => {
-                account_attend_weight = T::CMPowerAccountAttend::get() as PowerSize;
-                comment_power_weight = T::DocumentCMPowerWeightAttend::get() as PowerSize;
-                doc_comment_top_weight = 100 as PowerSize;
-                doc_judge_weight = T::DocumentCMPowerWeightJudge::get();
-            }
-            _ => {}
-        }
+        let weights = Self::power_weight_profile(doc.app_id);
+        let (
+            account_attend_weight,
+            comment_power_weight,
+            doc_comment_top_weight,
+            doc_judge_weight,
+            comment_weights,
+        ) = Self::comment_power_weights(&weights, &doc.document_type);
 
         account_comment_power = Self::compute_attend_power(
+            comment_weights,
             Self::compute_comment_action_rate(
                 &account_comment_max,
                 account.count,
@@ -4257,6 +6437,7 @@ impl<T: Trait> Module<T> {
             compare_base = doc_comment_max.clone();
         }
         doc_comment_power = Self::compute_attend_power(
+            comment_weights,
             Self::compute_comment_action_rate(
                 &compare_base,
                 doc.comment_count,
@@ -4268,41 +6449,37 @@ impl<T: Trait> Module<T> {
             doc_comment_top_weight,
         );
 
-        // chcek if owner's membership
+        // role is checked on the commenting user, not the app server sending the extrinsic
+        let commenter = Self::convert_account(&comment.owner);
+        let is_expert = T::Membership::is_expert(&commenter, doc.app_id, &doc.model_id);
+        let is_platform = T::Membership::is_platform(&commenter, doc.app_id);
+        let mut effect = KPCommentEffect {
+            position: Self::next_comment_position(&doc_key_hash, doc.comment_count),
+            is_expert: is_expert || is_platform,
+            ..Default::default()
+        };
+
+        // first expert and platform comment decide document trend
         let mut platform_comment_power: PowerSize = 0;
-        let mut is_normal_comment = true;
-        if doc.expert_trend == CommentTrend::Empty
-            && T::Membership::is_expert(&comment.sender, doc.app_id, &doc.model_id)
-        {
+        if doc.expert_trend == CommentTrend::Empty && is_expert {
             doc.expert_trend = comment.comment_trend.into();
+            effect.expert_trend_set = true;
+        }
+        if doc.platform_trend == CommentTrend::Empty && is_platform {
+            doc.platform_trend = comment.comment_trend.into();
+            effect.platform_trend_set = true;
+        }
+        if effect.expert_trend_set || effect.platform_trend_set {
             platform_comment_power = Self::compute_judge_power(
                 Self::compute_doc_trend_power(&doc),
                 doc_comment_top_weight,
                 doc_judge_weight,
             );
-
-            // give expert comment reward
-            Self::give_comment_reward(false, &comment.sender, comment.comment_fee);
-            is_normal_comment = false;
-        }
-        if doc.platform_trend == CommentTrend::Empty
-            && T::Membership::is_platform(&comment.sender, doc.app_id)
-        {
-            doc.platform_trend = comment.comment_trend.into();
-            platform_comment_power = Self::compute_judge_power(
-                Self::compute_doc_trend_power(&doc),
-                doc_comment_top_weight,
-                doc_judge_weight,
-            );
-
-            // give platform comment reward
-            Self::give_comment_reward(false, &comment.sender, comment.comment_fee);
-            is_normal_comment = false;
         }
 
-        if is_normal_comment {
-            Self::give_comment_reward(true, &comment.sender, comment.comment_fee);
-        }
+        // expert and platform comments are rewarded at expert rate
+        effect.reward =
+            Self::give_comment_reward(!effect.is_expert, &commenter, comment.comment_fee);
 
         // below are write actions
 
@@ -4330,7 +6507,7 @@ impl<T: Trait> Module<T> {
         // update document attend power store
         Self::update_document_power(&doc, doc_comment_power, platform_comment_power, 0);
 
-        Self::update_document_comment_pool(&comment, &doc);
+        Self::update_document_comment_pool(&comment, &doc, effect.position);
 
         // update account statistics
         <AccountStatisticsMap<T>>::mutate(&comment.sender, |info| {
@@ -4347,6 +6524,136 @@ impl<T: Trait> Module<T> {
                 info.comment_cost_max = comment.comment_fee;
             }
         });
+
+        effect
+    }
+
+    // undo counters, trends and reward a comment brought, then refresh powers
+    // returns the reward reclaimed, identities match process_comment_power:
+    // reward went to the commenting user, counters and attend power to the sender
+    fn reverse_comment_effect(
+        comment: &KPCommentData<T::AccountId, T::Hash>,
+        effect: &KPCommentEffect<BalanceOf<T>>,
+    ) -> BalanceOf<T> {
+        // reward goes back to treasury out of free balance only, reserved funds are not touched
+        let mut reclaimed: BalanceOf<T> = 0u32.into();
+        if effect.reward > 0u32.into() {
+            let treasury_account: T::AccountId = T::TreasuryModuleId::get().into_account();
+            let commenter = Self::convert_account(&comment.owner);
+            let spendable = T::Currency::free_balance(&commenter)
+                .saturating_sub(T::Currency::minimum_balance());
+            let amount = effect.reward.min(spendable);
+            if T::Currency::transfer(&commenter, &treasury_account, amount, KeepAlive).is_ok() {
+                reclaimed = amount;
+            }
+        }
+
+        let is_positive = comment.comment_trend == 0;
+
+        let comment_account_key = T::Hashing::hash_of(&(comment.app_id, &comment.sender));
+        let mut account = Self::kp_comment_account_record_map(&comment_account_key);
+        account.count = account.count.saturating_sub(1);
+        account.fees = account.fees.saturating_sub(comment.comment_fee);
+        if is_positive {
+            account.positive_count = account.positive_count.saturating_sub(1);
+        }
+        <KPCommentAccountRecordMap<T>>::insert(&comment_account_key, &account);
+
+        <AccountStatisticsMap<T>>::mutate(&comment.sender, |info| {
+            info.comment_num = info.comment_num.saturating_sub(1);
+            info.comment_cost_total = info.comment_cost_total.saturating_sub(comment.comment_fee);
+            if is_positive {
+                info.comment_positive_trend_num = info.comment_positive_trend_num.saturating_sub(1);
+            } else {
+                info.comment_negative_trend_num = info.comment_negative_trend_num.saturating_sub(1);
+            }
+        });
+
+        // document may be retracted already
+        let doc_key_hash = T::Hashing::hash_of(&(comment.app_id, &comment.document_id));
+        if !<KPDocumentDataByIdHash<T>>::contains_key(&doc_key_hash) {
+            return reclaimed;
+        }
+        let mut doc = Self::kp_document_data_by_idhash(&doc_key_hash);
+        doc.comment_count = doc.comment_count.saturating_sub(1);
+        doc.comment_total_fee = doc.comment_total_fee.saturating_sub(comment.comment_fee);
+        if is_positive {
+            doc.comment_positive_count = doc.comment_positive_count.saturating_sub(1);
+        }
+        if effect.expert_trend_set {
+            doc.expert_trend = CommentTrend::Empty;
+        }
+        if effect.platform_trend_set {
+            doc.platform_trend = CommentTrend::Empty;
+        }
+
+        let weights = Self::power_weight_profile(doc.app_id);
+        let (
+            account_attend_weight,
+            comment_power_weight,
+            doc_comment_top_weight,
+            doc_judge_weight,
+            comment_weights,
+        ) = Self::comment_power_weights(&weights, &doc.document_type);
+
+        let account_comment_power = if account.count > 0 {
+            Self::compute_attend_power(
+                comment_weights,
+                Self::compute_comment_action_rate(
+                    &Self::comment_max_info_per_account_map(comment.app_id),
+                    account.count,
+                    account.fees,
+                    account.positive_count,
+                    account.fees / account.count,
+                ),
+                100,
+                account_attend_weight,
+            )
+        } else {
+            0
+        };
+        let key = T::Hashing::hash_of(&(&comment.sender, comment.app_id));
+        <AccountAttendPowerMap<T>>::insert(&key, account_comment_power);
+
+        let doc_comment_power = if doc.comment_count > 0 {
+            Self::compute_attend_power(
+                comment_weights,
+                Self::compute_comment_action_rate(
+                    &<DocumentCommentPowerBase<T>>::get(&doc_key_hash),
+                    doc.comment_count,
+                    doc.comment_total_fee,
+                    doc.comment_positive_count,
+                    doc.comment_total_fee / doc.comment_count,
+                ),
+                comment_power_weight,
+                doc_comment_top_weight,
+            )
+        } else {
+            0
+        };
+
+        <KPDocumentPowerByIdHash<T>>::mutate(&doc_key_hash, |power| {
+            power.attend = doc_comment_power;
+            if effect.expert_trend_set || effect.platform_trend_set {
+                power.judge = Self::compute_judge_power(
+                    Self::compute_doc_trend_power(&doc),
+                    doc_comment_top_weight,
+                    doc_judge_weight,
+                );
+            }
+        });
+
+        <DocumentCommentsAccountPool<T>>::mutate(&doc_key_hash, |pool| {
+            pool.retain(|item| {
+                !(item.account == comment.sender && item.position == effect.position)
+            })
+        });
+
+        <KPDocumentDataByIdHash<T>>::insert(&doc_key_hash, &doc);
+
+        Self::process_commodity_power(&doc);
+
+        reclaimed
     }
 
     // triggered when:
@@ -4382,7 +6689,10 @@ impl<T: Trait> Module<T> {
                     // update publish power
                     update_publish(&mut commodity_power);
                     // update price power
-                    commodity_power.4 = Self::compute_price_power(data.goods_price);
+                    commodity_power.4 = Self::compute_price_power(
+                        &Self::power_weight_profile(doc.app_id),
+                        data.goods_price,
+                    );
 
                     let model_id = Self::get_model_id_from_product(doc.app_id, &doc.product_id)?;
 
@@ -4408,7 +6718,10 @@ impl<T: Trait> Module<T> {
                     // update publish power
                     update_publish(&mut commodity_power);
                     // update price power
-                    commodity_power.4 = Self::compute_price_power(data.goods_price);
+                    commodity_power.4 = Self::compute_price_power(
+                        &Self::power_weight_profile(doc.app_id),
+                        data.goods_price,
+                    );
 
                     let model_id = Self::get_model_id_from_product(doc.app_id, &doc.product_id)?;
                     Self::update_purchase_power(
@@ -4437,20 +6750,23 @@ impl<T: Trait> Module<T> {
         Some(0)
     }
 
-    fn slash_power(cart_key: &T::Hash, power_owner: &T::AccountId) {
+    fn slash_power(cart_key: &T::Hash, power_owner: &T::AccountId) -> PowerSize {
         let cart_power = Self::get_purchase_power(cart_key);
         print("slash_power");
         print(cart_power);
         if cart_power > 0 {
+            let decayed_power =
+                Self::decay_power(cart_power, <KPPurchasePowerSettledAt<T>>::get(cart_key));
             // clear power
             Self::clear_purchase_power(cart_key);
             // reduce account power
-            <MinerPowerByAccount<T>>::mutate(power_owner, |pow| {
-                if *pow > cart_power {
-                    *pow -= cart_power;
-                } else {
-                    *pow = 0
-                }
+            let account_power = Self::settle_account_power(power_owner);
+            <MinerPowerByAccount<T>>::insert(
+                power_owner,
+                account_power.saturating_sub(decayed_power),
+            );
+            <MinerRawPowerByAccount<T>>::mutate(power_owner, |pow| {
+                *pow = pow.saturating_sub(cart_power)
             });
 
             // update account statistics
@@ -4459,21 +6775,69 @@ impl<T: Trait> Module<T> {
                 info.slash_kp_total += cart_power;
             });
         }
+
+        cart_power
+    }
+
+    // power added at block `from` decays once per period boundary passed since then,
+    // boundaries are shared by all accounts so total power decays the same way
+    fn decay_power(power: PowerSize, from: Option<T::BlockNumber>) -> PowerSize {
+        let period = T::PowerDecayPeriod::get();
+        let from = match from {
+            Some(from) if !period.is_zero() => from,
+            _ => return power,
+        };
+
+        let now = <system::Module<T>>::block_number();
+        let passed: u32 = (now / period)
+            .saturating_sub(from / period)
+            .saturated_into();
+        if passed == 0 {
+            return power;
+        }
+
+        T::PowerDecayRatio::get().saturating_pow(passed as usize) * power
+    }
+
+    // write decay of account power to storage, returns decayed power
+    fn settle_account_power(account: &T::AccountId) -> PowerSize {
+        let power = Self::kp_account_power(account.clone());
+        // power stored before decay was introduced is all raw
+        if !<MinerPowerSettledAt<T>>::contains_key(account) {
+            <MinerRawPowerByAccount<T>>::insert(account, power);
+        }
+
+        <MinerPowerByAccount<T>>::insert(account, power);
+        <MinerPowerSettledAt<T>>::insert(account, <system::Module<T>>::block_number());
+        power
+    }
+
+    fn settle_total_power() -> PowerSize {
+        let power = Self::kp_total_power();
+        if <TotalPowerSettledAt<T>>::get().is_none() {
+            TotalRawPower::put(power);
+        }
+
+        TotalPower::put(power);
+        <TotalPowerSettledAt<T>>::put(<system::Module<T>>::block_number());
+        power
     }
 
     fn model_income_stage(block: T::BlockNumber) -> (ModelIncomeStage, T::BlockNumber) {
+        let params = Self::active_economic_params();
         let cycle_index = Self::model_income_cycle_index(block);
-        let cycle_blocks = T::ModelIncomeCyclePeriod::get();
+        let cycle_blocks = params.model_income_cycle_period;
+        let (anchor_block, _) = <EconomicParamsAnchor<T>>::get();
+        let progress = block.saturating_sub(anchor_block) % cycle_blocks;
 
         if cycle_index == 0u32.into() {
-            return (ModelIncomeStage::NORMAL, cycle_blocks - block);
+            return (ModelIncomeStage::NORMAL, cycle_blocks - progress);
         }
 
-        let collecting = T::ModelIncomeCollectingPeriod::get();
-        let rewarding_blocks = T::ModelIncomeRewardingPeriod::get();
+        let collecting = params.model_income_collecting_period;
+        let rewarding_blocks = params.model_income_rewarding_period;
         let confirming_blocks = rewarding_blocks / 2u32.into();
         let compensating_blocks = rewarding_blocks / 2u32.into();
-        let progress = block % T::ModelIncomeCyclePeriod::get();
 
         return if progress < collecting {
             (ModelIncomeStage::COLLECTING, collecting - progress)
@@ -4499,7 +6863,144 @@ impl<T: Trait> Module<T> {
     }
 
     fn model_income_cycle_index(block: T::BlockNumber) -> T::BlockNumber {
-        block / T::ModelIncomeCyclePeriod::get()
+        let (anchor_block, anchor_cycle) = <EconomicParamsAnchor<T>>::get();
+        anchor_cycle
+            + block.saturating_sub(anchor_block)
+                / Self::active_economic_params().model_income_cycle_period
+    }
+
+    fn next_model_income_cycle_start(block: T::BlockNumber) -> T::BlockNumber {
+        let (anchor_block, anchor_cycle) = <EconomicParamsAnchor<T>>::get();
+        let cycles = Self::model_income_cycle_index(block) - anchor_cycle + 1u32.into();
+        anchor_block + cycles * Self::active_economic_params().model_income_cycle_period
+    }
+
+    pub fn default_power_weight_profile() -> PowerWeightProfile {
+        PowerWeightProfile {
+            top: (
+                T::TopWeightProductPublish::get(),
+                T::TopWeightDocumentIdentify::get(),
+                T::TopWeightDocumentTry::get(),
+                T::TopWeightAccountAttend::get(),
+                T::TopWeightAccountStake::get(),
+            ),
+            document: (
+                T::DocumentPowerWeightAttend::get(),
+                T::DocumentPowerWeightContent::get(),
+                T::DocumentPowerWeightJudge::get(),
+            ),
+            comment: (
+                T::CommentPowerWeightCount::get(),
+                T::CommentPowerWeightCost::get(),
+                T::CommentPowerWeightPerCost::get(),
+                T::CommentPowerWeightPositive::get(),
+            ),
+            publish: (
+                T::DocumentPublishWeightParamsRate::get(),
+                T::DocumentPublishWeightParamsSelfRate::get(),
+                T::DocumentPublishWeightParamsAttendRate::get(),
+            ),
+            identify: (
+                T::DocumentIdentifyWeightParamsRate::get(),
+                T::DocumentIdentifyWeightCheckRate::get(),
+                T::DocumentIdentifyWeightConsistentRate::get(),
+            ),
+            product_try: (
+                T::DocumentTryWeightBiasRate::get(),
+                T::DocumentTryWeightTrueRate::get(),
+                T::DocumentTryWeightConsistentRate::get(),
+            ),
+            choose: (
+                T::DocumentChooseWeightSellCount::get(),
+                T::DocumentChooseWeightTryCount::get(),
+            ),
+            model: (
+                T::DocumentModelWeightProducerCount::get(),
+                T::DocumentModelWeightProductCount::get(),
+            ),
+            cm: (
+                T::CMPowerAccountAttend::get(),
+                T::DocumentCMPowerWeightAttend::get(),
+                T::DocumentCMPowerWeightContent::get(),
+                T::DocumentCMPowerWeightJudge::get(),
+            ),
+            comment_cm: (
+                T::CommentCMPowerWeightCount::get(),
+                T::CommentCMPowerWeightCost::get(),
+                T::CommentCMPowerWeightPerCost::get(),
+                T::CommentCMPowerWeightPositive::get(),
+            ),
+            comment_power: T::CommentPowerWeight::get(),
+        }
+    }
+
+    pub fn power_weight_profile(app_id: u32) -> PowerWeightProfile {
+        <AppPowerWeightProfiles>::get(app_id).unwrap_or_else(Self::default_power_weight_profile)
+    }
+
+    pub fn default_economic_params() -> KPEconomicParams<BalanceOf<T>, T::BlockNumber> {
+        KPEconomicParams {
+            model_income_cycle_period: T::ModelIncomeCyclePeriod::get(),
+            model_income_collecting_period: T::ModelIncomeCollectingPeriod::get(),
+            model_income_rewarding_period: T::ModelIncomeRewardingPeriod::get(),
+            model_cycle_income_reward_total: T::ModelCycleIncomeRewardTotal::get(),
+            app_leader_board_interval: T::AppLeaderBoardInterval::get(),
+            app_finance_exchange_period: T::AppFinanceExchangePeriod::get(),
+            model_dispute_delay_time: T::ModelDisputeDelayTime::get(),
+        }
+    }
+
+    pub fn active_economic_params() -> KPEconomicParams<BalanceOf<T>, T::BlockNumber> {
+        <EconomicParams<T>>::get().unwrap_or_else(Self::default_economic_params)
+    }
+
+    fn is_economic_params_valid(params: &KPEconomicParams<BalanceOf<T>, T::BlockNumber>) -> bool {
+        let zero: T::BlockNumber = 0u32.into();
+        // confirming and compensating stages take half of rewarding each
+        let stages = params.model_income_collecting_period
+            + params.model_income_rewarding_period / 2u32.into() * 2u32.into()
+            + params.model_income_rewarding_period;
+
+        params.model_income_cycle_period > zero
+            && params.model_income_collecting_period > zero
+            && params.model_income_rewarding_period > zero
+            && stages <= params.model_income_cycle_period
+            && params.app_leader_board_interval > zero
+            && params.app_finance_exchange_period > zero
+            && params.model_dispute_delay_time > zero
+    }
+
+    fn apply_economic_params(block: T::BlockNumber) -> Weight {
+        let db = T::DbWeight::get();
+        let (start, params) = match <PendingEconomicParams<T>>::get() {
+            Some(pending) if pending.0 <= block => pending,
+            _ => return db.reads(1),
+        };
+
+        // the old params close their last cycle exactly at start
+        let cycle = Self::model_income_cycle_index(start);
+        <EconomicParamsAnchor<T>>::put((start, cycle));
+        <EconomicParams<T>>::put(params);
+        <PendingEconomicParams<T>>::kill();
+
+        Self::deposit_event(RawEvent::EconomicParamsApplied(start));
+        db.reads_writes(3, 3)
+    }
+
+    // killed models can't be retired, they leave the app model count at once
+    fn disable_killed_model(app_id: u32, model_id: &Vec<u8>) {
+        let key = T::Hashing::hash_of(&(app_id, model_id));
+        let mut was_enabled = false;
+        <KPModelDataByIdHash<T>>::mutate(&key, |model| {
+            was_enabled = model.status == ModelStatus::ENABLED;
+            model.status = ModelStatus::DISABLED;
+        });
+
+        if was_enabled {
+            <AppModelCount>::mutate(app_id, |count| {
+                *count = count.saturating_sub(1);
+            });
+        }
     }
 
     fn is_valid_model(app_id: u32, model_id: &Vec<u8>) -> bool {
@@ -4534,6 +7035,7 @@ impl<T: Trait> Module<T> {
 
         match dispute_type {
             ModelDisputeType::NoneIntendNormal => {
+                Self::model_dispute_lv1_slash(app_id, model_id, owner, current_block);
                 cycle_dispute_count += 1;
                 reporter_reward = T::ModelDisputeRewardLv1::get();
             }
@@ -4549,13 +7051,12 @@ impl<T: Trait> Module<T> {
             ModelDisputeType::Serious => {
                 if cycle_dispute_count >= T::ModelDisputeCycleCount::get() {
                     cancel_model_cycle_reward();
-                    <KPModelDataByIdHash<T>>::mutate(&key, |model| {
-                        model.status = ModelStatus::DISABLED;
-                    });
+                    Self::disable_killed_model(app_id, model_id);
 
                     T::Slash::on_unbalanced(
                         T::Currency::slash_reserved(owner, <KPModelDepositMap<T>>::get(&key)).0,
                     );
+                    <KPModelDepositMap<T>>::insert(&key, BalanceOf::<T>::from(0u32));
                 }
 
                 cycle_dispute_count += T::ModelDisputeCycleLv3IncreaseCount::get();
@@ -4568,6 +7069,762 @@ impl<T: Trait> Module<T> {
         // reward reporter
         let treasury_account: T::AccountId = T::TreasuryModuleId::get().into_account();
         T::Currency::transfer(&treasury_account, &reporter, reporter_reward, KeepAlive).ok();
+
+        Self::deposit_event(RawEvent::ModelDisputed(
+            owner.clone(),
+            reporter.clone(),
+            app_id,
+            model_id.clone(),
+            dispute_type,
+            reporter_reward,
+        ));
+    }
+
+    // slash part of model deposit, model goes into pre-black list if deposit lower than half required
+    fn model_dispute_lv1_slash(
+        app_id: u32,
+        model_id: &Vec<u8>,
+        owner: &T::AccountId,
+        block: T::BlockNumber,
+    ) {
+        let key = T::Hashing::hash_of(&(app_id, model_id));
+        let deposit = <KPModelDepositMap<T>>::get(&key);
+        let slash = min(T::ModelDisputeLv1Slash::get(), deposit);
+        let (imbalance, _) = T::Currency::slash_reserved(owner, slash);
+        T::Slash::on_unbalanced(imbalance);
+        <KPModelDepositMap<T>>::insert(&key, deposit - slash);
+
+        // retiring model is already disabled
+        if Self::is_model_deposit_enough(app_id, model_id)
+            || <ModelRetireRecords<T>>::contains_key(&key)
+        {
+            return;
+        }
+
+        let mut pre_black_list = <ModelPreBlackList<T>>::get();
+        if pre_black_list
+            .iter()
+            .any(|x| x.0 == app_id && &x.1 == model_id)
+        {
+            return;
+        }
+
+        let end_block = block + Self::active_economic_params().model_dispute_delay_time;
+        pre_black_list.push((app_id, model_id.clone(), owner.clone(), end_block));
+        <ModelPreBlackList<T>>::put(pre_black_list);
+
+        Self::deposit_event(RawEvent::ModelPreBlackListed(
+            app_id,
+            model_id.clone(),
+            end_block,
+        ));
+    }
+
+    // give back left deposit of retired models, whose cool-down ends at this block,
+    // models with a dispute not resolved wait another cool-down
+    fn finish_model_retirements(block: T::BlockNumber) -> Weight {
+        let db = T::DbWeight::get();
+        if !<ModelRetireExpiry<T>>::contains_key(block) {
+            return db.reads(1);
+        }
+
+        let models = <ModelRetireExpiry<T>>::take(block);
+        let mut retired: Vec<(u32, Vec<u8>)> = vec![];
+        for (app_id, model_id) in models.iter() {
+            let key = T::Hashing::hash_of(&(*app_id, model_id));
+            if <ModelOpenDisputeCount<T>>::get(&key) > 0 {
+                <ModelRetireExpiry<T>>::append(
+                    block + T::ModelRetireCoolDown::get(),
+                    (*app_id, model_id.clone()),
+                );
+                continue;
+            }
+
+            let (owner, _) = <ModelRetireRecords<T>>::take(&key);
+            let deposit = <KPModelDepositMap<T>>::take(&key);
+            // unreserve gives back the part not able to unreserve
+            let returned = deposit.saturating_sub(T::Currency::unreserve(&owner, deposit));
+
+            <AppModelCount>::mutate(*app_id, |count| {
+                *count = count.saturating_sub(1);
+            });
+            retired.push((*app_id, model_id.clone()));
+
+            Self::deposit_event(RawEvent::ModelRetired(
+                owner,
+                *app_id,
+                model_id.clone(),
+                returned,
+            ));
+        }
+
+        // retired model has no deposit left to recover
+        let mut pre_black_list = <ModelPreBlackList<T>>::get();
+        let mut removed: Vec<(u32, Vec<u8>)> = vec![];
+        pre_black_list.retain(|x| {
+            let model = (x.0, x.1.clone());
+            if retired.contains(&model) {
+                removed.push(model);
+                return false;
+            }
+            true
+        });
+        if !removed.is_empty() {
+            <ModelPreBlackList<T>>::put(pre_black_list);
+        }
+        for (app_id, model_id) in removed {
+            Self::deposit_event(RawEvent::ModelPreBlackListRemoved(app_id, model_id));
+        }
+
+        let n = models.len() as Weight;
+        db.reads_writes(2 + 5 * n, 2 + 4 * n)
+    }
+
+    fn is_model_deposit_enough(app_id: u32, model_id: &Vec<u8>) -> bool {
+        let key = T::Hashing::hash_of(&(app_id, model_id));
+        <KPModelDepositMap<T>>::get(&key) >= T::ModelCreateDeposit::get() / 2u32.into()
+    }
+
+    // apply final result: model slash and reporter reward on upheld, bonds back to winners
+    fn settle_model_dispute(
+        case: &mut ModelDisputeCase<T::AccountId, BalanceOf<T>, T::BlockNumber, T::Hash>,
+        upheld: bool,
+    ) {
+        let slash_bond = |who: &T::AccountId, bond: BalanceOf<T>| {
+            T::Slash::on_unbalanced(T::Currency::slash_reserved(who, bond).0);
+        };
+
+        if upheld {
+            Self::model_dispute(
+                case.app_id,
+                &case.model_id,
+                case.dispute_type,
+                &case.owner,
+                &case.reporter,
+            );
+            Self::add_model_dispute_record(
+                case.app_id,
+                &case.model_id,
+                &case.comment_id,
+                case.dispute_type,
+            );
+            T::Currency::unreserve(&case.reporter, case.bond);
+        } else {
+            slash_bond(&case.reporter, case.bond);
+        }
+
+        // appellant wins when judgement overturns the votes
+        if let Some(appellant) = &case.appellant {
+            if upheld != case.upheld {
+                T::Currency::unreserve(appellant, case.appeal_bond);
+            } else {
+                slash_bond(appellant, case.appeal_bond);
+            }
+        }
+
+        case.upheld = upheld;
+        case.status = ModelDisputeStatus::Resolved;
+
+        let model_key = T::Hashing::hash_of(&(case.app_id, &case.model_id));
+        <ModelOpenDisputeCount<T>>::mutate_exists(&model_key, |count| {
+            *count = count.and_then(|n| n.checked_sub(1)).filter(|n| *n > 0);
+        });
+    }
+
+    fn add_identify_document(
+        who: &T::AccountId,
+        app_user_account: &AuthAccountId,
+        client_params: ClientParamsCreateIdentifyDoc<T::Hash>,
+    ) -> dispatch::DispatchResult {
+        let ClientParamsCreateIdentifyDoc {
+            app_id,
+            document_id,
+            product_id,
+            content_hash,
+            goods_price,
+            ident_rate,
+            ident_consistence,
+            seller_consistence,
+            cart_id,
+        } = client_params;
+
+        ensure!(
+            T::Membership::is_valid_app(app_id),
+            Error::<T>::AppIdInvalid
+        );
+
+        let doc_key_hash = T::Hashing::hash_of(&(app_id, &document_id));
+        ensure!(
+            !Self::is_document_id_used(&doc_key_hash),
+            Error::<T>::DocumentAlreadyExisted
+        );
+
+        let product_key_hash = T::Hashing::hash_of(&(app_id, &product_id));
+        ensure!(
+            <KPDocumentProductIndexByIdHash<T>>::contains_key(&product_key_hash),
+            Error::<T>::ProductNotFound
+        );
+
+        let key = T::Hashing::hash_of(&(app_id, &cart_id));
+        ensure!(
+            !<KPCartProductIdentifyIndexByIdHash<T>>::contains_key(&key),
+            Error::<T>::DocumentIdentifyAlreadyExisted
+        );
+
+        let model_id = Self::get_model_id_from_product(app_id, &product_id).unwrap_or_default();
+
+        // create doc
+        let doc = KPDocumentData {
+            sender: who.clone(),
+            owner: app_user_account.clone(),
+            document_type: DocumentType::ProductIdentify,
+            app_id,
+            document_id: document_id.clone(),
+            model_id,
+            product_id,
+            content_hash,
+            document_data: DocumentSpecificData::ProductIdentify(KPProductIdentifyData {
+                goods_price,
+                ident_rate,
+                ident_consistence,
+                seller_consistence,
+                cart_id: cart_id.clone(),
+            }),
+            ..Default::default()
+        };
+
+        // process content power
+        Self::process_document_content_power(&doc);
+        Self::process_commodity_power(&doc);
+
+        // create cartid -> product identify document id record
+        <KPCartProductIdentifyIndexByIdHash<T>>::insert(&key, &document_id);
+
+        // create document record
+        <KPDocumentDataByIdHash<T>>::insert(&doc_key_hash, &doc);
+
+        Self::increase_commodity_count(
+            app_id,
+            &doc.model_id,
+            &cart_id,
+            DocumentType::ProductIdentify,
+            &Self::convert_account(&doc.owner),
+        );
+
+        Self::deposit_event(RawEvent::KnowledgeCreated(
+            who.clone(),
+            Self::convert_account(&doc.owner),
+            app_id,
+            document_id,
+            doc.document_type,
+        ));
+        Ok(())
+    }
+
+    fn add_try_document(
+        who: &T::AccountId,
+        app_user_account: &AuthAccountId,
+        client_params: ClientParamsCreateTryDoc<T::Hash>,
+    ) -> dispatch::DispatchResult {
+        let ClientParamsCreateTryDoc {
+            app_id,
+            document_id,
+            product_id,
+            content_hash,
+            goods_price,
+            offset_rate,
+            true_rate,
+            seller_consistence,
+            cart_id,
+        } = client_params;
+
+        ensure!(
+            T::Membership::is_valid_app(app_id),
+            Error::<T>::AppIdInvalid
+        );
+
+        let doc_key_hash = T::Hashing::hash_of(&(app_id, &document_id));
+        ensure!(
+            !Self::is_document_id_used(&doc_key_hash),
+            Error::<T>::DocumentAlreadyExisted
+        );
+
+        let product_key_hash = T::Hashing::hash_of(&(app_id, &product_id));
+        ensure!(
+            <KPDocumentProductIndexByIdHash<T>>::contains_key(&product_key_hash),
+            Error::<T>::ProductNotFound
+        );
+
+        let key = T::Hashing::hash_of(&(app_id, &cart_id));
+        ensure!(
+            !<KPCartProductTryIndexByIdHash<T>>::contains_key(&key),
+            Error::<T>::DocumentTryAlreadyExisted
+        );
+
+        let model_id = Self::get_model_id_from_product(app_id, &product_id).unwrap_or_default();
+
+        // create doc
+        let doc = KPDocumentData {
+            sender: who.clone(),
+            owner: app_user_account.clone(),
+            document_type: DocumentType::ProductTry,
+            app_id,
+            document_id: document_id.clone(),
+            model_id,
+            product_id,
+            content_hash,
+            document_data: DocumentSpecificData::ProductTry(KPProductTryData {
+                goods_price,
+                offset_rate,
+                true_rate,
+                seller_consistence,
+                cart_id: cart_id.clone(),
+            }),
+            ..Default::default()
+        };
+
+        // process content power
+        Self::process_document_content_power(&doc);
+        Self::process_commodity_power(&doc);
+
+        // create cartid -> product identify document id record
+
+        <KPCartProductTryIndexByIdHash<T>>::insert(&key, &document_id);
+
+        // create document record
+        <KPDocumentDataByIdHash<T>>::insert(&doc_key_hash, &doc);
+
+        Self::increase_commodity_count(
+            app_id,
+            &doc.model_id,
+            &cart_id,
+            DocumentType::ProductTry,
+            &Self::convert_account(&doc.owner),
+        );
+
+        Self::deposit_event(RawEvent::KnowledgeCreated(
+            who.clone(),
+            Self::convert_account(&doc.owner),
+            app_id,
+            document_id,
+            doc.document_type,
+        ));
+        Ok(())
+    }
+
+    fn add_comment(
+        who: &T::AccountId,
+        app_user_account: &AuthAccountId,
+        comment_data: CommentData<T::Hash>,
+    ) -> dispatch::DispatchResult {
+        let CommentData {
+            app_id,
+            document_id,
+            comment_id,
+            comment_hash,
+            comment_fee,
+            comment_trend,
+        } = comment_data;
+
+        ensure!(
+            T::Membership::is_valid_app(app_id),
+            Error::<T>::AppIdInvalid
+        );
+
+        // comment target must exist, commenter role is checked when computing power
+        let doc_key_hash = T::Hashing::hash_of(&(app_id, &document_id));
+        ensure!(
+            <KPDocumentDataByIdHash<T>>::contains_key(&doc_key_hash),
+            Error::<T>::DocumentNotFound
+        );
+
+        // make sure this comment not exist
+        let key = T::Hashing::hash_of(&(app_id, &comment_id));
+        ensure!(
+            !<KPCommentDataByIdHash<T>>::contains_key(&key),
+            Error::<T>::CommentAlreadyExisted
+        );
+
+        let comment = KPCommentData {
+            sender: who.clone(),
+            owner: app_user_account.clone(),
+            app_id,
+            document_id: document_id.clone(),
+            comment_id: comment_id.clone(),
+            comment_fee,
+            comment_trend,
+            comment_hash,
+        };
+
+        let effect = Self::process_comment_power(&comment);
+        <KPCommentEffects<T>>::insert(&key, effect);
+
+        // read out related document, trigger account power update
+        let doc = Self::kp_document_data_by_idhash(&doc_key_hash);
+        Self::process_commodity_power(&doc);
+
+        // create comment record
+        <KPCommentDataByIdHash<T>>::insert(&key, &comment);
+
+        Self::deposit_event(RawEvent::CommentCreated(
+            who.clone(),
+            Self::convert_account(&comment.owner),
+            app_id,
+            comment_id,
+            document_id,
+        ));
+        Ok(())
+    }
+
+    // retracted document id stays taken, so its versions never mix with a new document
+    fn is_document_id_used(doc_key_hash: &T::Hash) -> bool {
+        <KPDocumentDataByIdHash<T>>::contains_key(doc_key_hash)
+            || <KPDocumentRetracted<T>>::contains_key(doc_key_hash)
+    }
+
+    fn revise_document_content(
+        who: &T::AccountId,
+        app_user_account: &AuthAccountId,
+        client_params: ClientParamsReviseDoc<T::Hash>,
+    ) -> dispatch::DispatchResult {
+        let ClientParamsReviseDoc {
+            app_id,
+            document_id,
+            content_hash,
+            document_data,
+        } = client_params;
+
+        ensure!(
+            T::Membership::is_valid_app(app_id),
+            Error::<T>::AppIdInvalid
+        );
+        let doc_key_hash = T::Hashing::hash_of(&(app_id, &document_id));
+        ensure!(
+            <KPDocumentDataByIdHash<T>>::contains_key(&doc_key_hash),
+            Error::<T>::DocumentNotFound
+        );
+        let mut doc = <KPDocumentDataByIdHash<T>>::get(&doc_key_hash);
+        ensure!(&doc.owner == app_user_account, Error::<T>::DocumentNotOwner);
+
+        // refer count of publish document is counted on chain, not revised by client
+        let document_data = match (&doc.document_data, document_data) {
+            (
+                DocumentSpecificData::ProductPublish(org),
+                DocumentSpecificData::ProductPublish(mut data),
+            ) => {
+                data.refer_count = org.refer_count;
+                DocumentSpecificData::ProductPublish(data)
+            }
+            (
+                DocumentSpecificData::ProductIdentify(org),
+                DocumentSpecificData::ProductIdentify(data),
+            ) if org.cart_id == data.cart_id => DocumentSpecificData::ProductIdentify(data),
+            (DocumentSpecificData::ProductTry(org), DocumentSpecificData::ProductTry(data))
+                if org.cart_id == data.cart_id =>
+            {
+                DocumentSpecificData::ProductTry(data)
+            }
+            (
+                DocumentSpecificData::ProductChoose(_),
+                data @ DocumentSpecificData::ProductChoose(_),
+            ) => data,
+            (DocumentSpecificData::ModelCreate(_), data @ DocumentSpecificData::ModelCreate(_)) => {
+                data
+            }
+            _ => return Err(Error::<T>::DocumentRevisionInvalid.into()),
+        };
+
+        let mut versions = <KPDocumentVersions<T>>::get(&doc_key_hash);
+        ensure!(
+            versions.len() < DOCUMENT_VERSION_MAX,
+            Error::<T>::DocumentVersionFull
+        );
+        versions.push(KPDocumentVersion {
+            content_hash: doc.content_hash,
+            block: <system::Module<T>>::block_number(),
+        });
+        <KPDocumentVersions<T>>::insert(&doc_key_hash, &versions);
+
+        doc.content_hash = content_hash;
+        doc.document_data = document_data;
+
+        // only content power follows revised data, attend and judge power come from comments
+        let (content_power, _) = Self::compute_document_content_power(&doc);
+        Self::set_document_content_power(&doc, content_power);
+        match &doc.document_data {
+            DocumentSpecificData::ProductIdentify(data) => {
+                Self::update_max_goods_price(data.goods_price)
+            }
+            DocumentSpecificData::ProductTry(data) => {
+                Self::update_max_goods_price(data.goods_price)
+            }
+            _ => {}
+        }
+
+        <KPDocumentDataByIdHash<T>>::insert(&doc_key_hash, &doc);
+
+        // commodity and leader boards pick up new document power
+        match &doc.document_data {
+            DocumentSpecificData::ProductPublish(_) => {
+                let product_key_hash = T::Hashing::hash_of(&(app_id, &doc.product_id));
+                let refer_doc_keys: Vec<T::Hash> =
+                    <KPProductReferDocuments<T>>::iter_prefix(&product_key_hash)
+                        .map(|(refer_doc_key, _)| refer_doc_key)
+                        .collect();
+                for refer_doc_key in refer_doc_keys.iter() {
+                    Self::process_commodity_power(&<KPDocumentDataByIdHash<T>>::get(refer_doc_key));
+                }
+            }
+            _ => {
+                Self::process_commodity_power(&doc);
+            }
+        }
+
+        Self::deposit_event(RawEvent::DocumentRevised(
+            who.clone(),
+            Self::convert_account(&doc.owner),
+            app_id,
+            document_id,
+            versions.len() as u32,
+        ));
+        Ok(())
+    }
+
+    fn retract_document_content(
+        who: &T::AccountId,
+        app_user_account: &AuthAccountId,
+        client_params: ClientParamsRetractDoc,
+    ) -> dispatch::DispatchResult {
+        let ClientParamsRetractDoc {
+            app_id,
+            document_id,
+        } = client_params;
+
+        ensure!(
+            T::Membership::is_valid_app(app_id),
+            Error::<T>::AppIdInvalid
+        );
+        let doc_key_hash = T::Hashing::hash_of(&(app_id, &document_id));
+        ensure!(
+            <KPDocumentDataByIdHash<T>>::contains_key(&doc_key_hash),
+            Error::<T>::DocumentNotFound
+        );
+        let doc = <KPDocumentDataByIdHash<T>>::get(&doc_key_hash);
+        ensure!(&doc.owner == app_user_account, Error::<T>::DocumentNotOwner);
+
+        match &doc.document_data {
+            DocumentSpecificData::ProductPublish(data) => {
+                // identify and try documents still compute power from this product
+                ensure!(data.refer_count == 0, Error::<T>::DocumentStillReferred);
+                let product_key_hash = T::Hashing::hash_of(&(app_id, &doc.product_id));
+                <KPDocumentProductIndexByIdHash<T>>::remove(&product_key_hash);
+            }
+            DocumentSpecificData::ProductIdentify(data) => {
+                let key = T::Hashing::hash_of(&(app_id, &data.cart_id));
+                <KPCartProductIdentifyIndexByIdHash<T>>::remove(&key);
+                Self::retract_commodity_document_power(&doc, &data.cart_id);
+                Self::release_publish_doc_refer(app_id, &doc.product_id);
+                let product_key_hash = T::Hashing::hash_of(&(app_id, &doc.product_id));
+                <KPProductReferDocuments<T>>::remove(&product_key_hash, &doc_key_hash);
+            }
+            DocumentSpecificData::ProductTry(data) => {
+                let key = T::Hashing::hash_of(&(app_id, &data.cart_id));
+                <KPCartProductTryIndexByIdHash<T>>::remove(&key);
+                Self::retract_commodity_document_power(&doc, &data.cart_id);
+                Self::release_publish_doc_refer(app_id, &doc.product_id);
+                let product_key_hash = T::Hashing::hash_of(&(app_id, &doc.product_id));
+                <KPProductReferDocuments<T>>::remove(&product_key_hash, &doc_key_hash);
+            }
+            _ => {
+                <KPMiscDocumentPowerByIdHash<T>>::remove(&doc_key_hash);
+            }
+        }
+
+        // last content hash is always kept, even when versions are full
+        let block = <system::Module<T>>::block_number();
+        let mut versions = <KPDocumentVersions<T>>::get(&doc_key_hash);
+        versions.push(KPDocumentVersion {
+            content_hash: doc.content_hash,
+            block,
+        });
+        <KPDocumentVersions<T>>::insert(&doc_key_hash, versions);
+        <KPDocumentRetracted<T>>::insert(&doc_key_hash, block);
+
+        <KPDocumentDataByIdHash<T>>::remove(&doc_key_hash);
+        <KPDocumentPowerByIdHash<T>>::remove(&doc_key_hash);
+
+        let owner_account = Self::convert_account(&doc.owner);
+        <AccountDocumentSet<T>>::mutate(&owner_account, app_id, |doc_ids| {
+            doc_ids.retain(|id| id != &document_id)
+        });
+
+        Self::deposit_event(RawEvent::DocumentRetracted(
+            who.clone(),
+            owner_account,
+            app_id,
+            document_id,
+        ));
+        Ok(())
+    }
+
+    // take power of a retracted identify or try document out of its commodity
+    fn retract_commodity_document_power(
+        doc: &KPDocumentData<T::AccountId, T::Hash>,
+        cart_id: &Vec<u8>,
+    ) {
+        let commodity_key = T::Hashing::hash_of(&(doc.app_id, cart_id));
+        // slashed commodity has no power left
+        if <KPPurchaseBlackList<T>>::contains_key(&commodity_key) {
+            return;
+        }
+
+        let model_id = match Self::get_model_id_from_product(doc.app_id, &doc.product_id) {
+            Some(model_id) => model_id,
+            None => return,
+        };
+        let mut commodity_power = <KPPurchasePowerByIdHash<T>>::get(&commodity_key);
+        match &doc.document_data {
+            DocumentSpecificData::ProductIdentify(_) => commodity_power.1 = Default::default(),
+            DocumentSpecificData::ProductTry(_) => commodity_power.2 = Default::default(),
+            _ => return,
+        }
+
+        // no document left for this commodity, it leaves the leader boards
+        let identify_left = <KPCartProductIdentifyIndexByIdHash<T>>::contains_key(&commodity_key);
+        let try_left = <KPCartProductTryIndexByIdHash<T>>::contains_key(&commodity_key);
+        if !identify_left && !try_left {
+            commodity_power = Default::default();
+        }
+
+        Self::update_purchase_power(
+            &commodity_key,
+            &commodity_power,
+            doc.app_id,
+            &model_id,
+            cart_id,
+            &Self::convert_account(&doc.owner),
+        );
+
+        if !identify_left && !try_left {
+            for board_model_id in &[model_id, vec![]] {
+                Self::remove_leader_board_item(doc.app_id, board_model_id, cart_id);
+                let leader_key = T::Hashing::hash_of(&(doc.app_id, board_model_id));
+                <LeaderBoardCommoditySet<T>>::remove(&leader_key, cart_id);
+            }
+        }
+    }
+
+    // publish document loses one refer from a retracted identify or try document
+    fn release_publish_doc_refer(app_id: u32, product_id: &Vec<u8>) {
+        let publish_key = T::Hashing::hash_of(&(app_id, product_id));
+        let publish_doc_id = <KPDocumentProductIndexByIdHash<T>>::get(&publish_key);
+        let publish_doc_key = T::Hashing::hash_of(&(app_id, &publish_doc_id));
+
+        let mut doc = <KPDocumentDataByIdHash<T>>::get(&publish_doc_key);
+        if let DocumentSpecificData::ProductPublish(data) = &mut doc.document_data {
+            data.refer_count = data.refer_count.saturating_sub(1);
+        } else {
+            return;
+        }
+
+        let (content_power, _) = Self::compute_document_content_power(&doc);
+        Self::set_document_content_power(&doc, content_power);
+        <KPDocumentDataByIdHash<T>>::insert(&publish_doc_key, &doc);
+    }
+
+    // content power may drop to zero, which `update_document_power` skips
+    fn set_document_content_power(
+        doc: &KPDocumentData<T::AccountId, T::Hash>,
+        content_power: PowerSize,
+    ) {
+        let key = T::Hashing::hash_of(&(doc.app_id, &doc.document_id));
+        <KPDocumentPowerByIdHash<T>>::mutate(&key, |power| power.content = content_power);
+    }
+
+    fn insert_product_refer_document(doc: &KPDocumentData<T::AccountId, T::Hash>) {
+        let product_key = T::Hashing::hash_of(&(doc.app_id, &doc.product_id));
+        let doc_key = T::Hashing::hash_of(&(doc.app_id, &doc.document_id));
+        <KPProductReferDocuments<T>>::insert(&product_key, &doc_key, ());
+    }
+
+    // revising a publish document refreshes every commodity referring its product
+    fn revise_document_weight(app_id: u32, document_id: &Vec<u8>, b: u32, s: u32) -> Weight {
+        let doc_key = T::Hashing::hash_of(&(app_id, document_id));
+        let refer_count = match <KPDocumentDataByIdHash<T>>::get(&doc_key).document_data {
+            DocumentSpecificData::ProductPublish(data) => data.refer_count,
+            _ => 0,
+        };
+
+        T::WeightInfo::revise_document(b, s).saturating_add(
+            T::WeightInfo::create_product_identify_document(b, 0)
+                .saturating_mul(refer_count as Weight),
+        )
+    }
+
+    // verify user signature of one batch item and apply it
+    fn submit_batch_item(
+        who: &T::AccountId,
+        app_id: u32,
+        item: BatchItem<T::Hash, T::BlockNumber>,
+    ) -> dispatch::DispatchResult {
+        let BatchItem {
+            params,
+            envelope,
+            app_user_account,
+            app_user_sign,
+        } = item;
+
+        let (tag, item_app_id, encode): (&[u8], u32, Vec<u8>) = match &params {
+            BatchItemParams::ProductIdentify(p) => {
+                (b"kp/create_product_identify_document", p.app_id, p.encode())
+            }
+            BatchItemParams::ProductTry(p) => {
+                (b"kp/create_product_try_document", p.app_id, p.encode())
+            }
+            BatchItemParams::Comment(p) => (b"kp/create_comment", p.app_id, p.encode()),
+        };
+        ensure!(item_app_id == app_id, Error::<T>::BatchItemAppIdMismatch);
+
+        let buf = Self::sign_envelope_message(tag, &app_user_account, &envelope, &encode)?;
+        ensure!(
+            Self::verify_sign(&app_user_account, app_user_sign, &buf),
+            Error::<T>::SignVerifyErrorUser
+        );
+        T::Membership::inc_sign_nonce(&app_user_account);
+
+        match params {
+            BatchItemParams::ProductIdentify(p) => {
+                Self::add_identify_document(who, &app_user_account, p)
+            }
+            BatchItemParams::ProductTry(p) => Self::add_try_document(who, &app_user_account, p),
+            BatchItemParams::Comment(p) => Self::add_comment(who, &app_user_account, p),
+        }
+    }
+
+    // sum of the single call weights of all items, plus the per item user sign check
+    // and storage transaction of the batch
+    // auth signs are checked once for the whole batch, so only the first item pays for them
+    fn submit_batch_weight(items: &[BatchItem<T::Hash, T::BlockNumber>], s: u32) -> Weight {
+        let board_max = T::AppLeaderBoardMaxPos::get();
+        let pool_max = T::DocumentCommentPoolMax::get();
+        let overhead = T::WeightInfo::submit_batch(items.len() as u32);
+        items
+            .iter()
+            .enumerate()
+            .fold(overhead, |weight: Weight, (index, item)| {
+                let s = if index == 0 { s } else { 0 };
+                weight.saturating_add(match &item.params {
+                    BatchItemParams::ProductIdentify(_) => {
+                        T::WeightInfo::create_product_identify_document(board_max, s)
+                    }
+                    BatchItemParams::ProductTry(_) => {
+                        T::WeightInfo::create_product_try_document(board_max, s)
+                    }
+                    BatchItemParams::Comment(_) => {
+                        T::WeightInfo::create_comment(pool_max, board_max, s)
+                    }
+                })
+            })
     }
 
     fn add_model_dispute_record(
@@ -4612,7 +7869,7 @@ impl<T: Trait> PowerVote<T::AccountId> for Module<T> {
             return 0;
         }*/
 
-        let power = <MinerPowerByAccount<T>>::get(account);
+        let power = Self::kp_account_power(account.clone());
         max(power, 1)
         //Permill::from_rational_approximation(power, total) * 10000
     }
@@ -94,12 +94,25 @@ parameter_types! {
 
     pub const AppLeaderBoardInterval: u32 = 5;
     pub const AppLeaderBoardMaxPos: u32 = 5;
+    pub const AppLeaderBoardSnapshotsPerBlock: u32 = 2;
+    pub const SubmitBatchMax: u32 = 10;
+    pub const DocumentCommentPoolMax: u32 = 100;
+    pub const LeaderBoardReward: u64 = 100;
+    pub const LeaderBoardRewardClaimPeriod: u64 = 5;
+    pub const LeaderBoardRewardModuleId: ModuleId = ModuleId(*b"py/ldbrw");
     pub const AppFinanceExchangePeriod: u32 = 1;
 
     pub const ModelIncomeCyclePeriod: u32 = 1;
     pub const ModelIncomeCollectingPeriod: u32 = 1;
     pub const ModelIncomeRewardingPeriod: u32 = 1;
+    pub const ModelIncomeParamsMax: u32 = 100;
+    pub const MaxModelExperts: u32 = 100;
     pub const ModelDisputeDelayTime: u32 = 1;
+    pub const ModelRetireCoolDown: u64 = 5;
+    pub const ModelDisputeBond: u64 = 10;
+    pub const ModelDisputeEvidencePeriod: u64 = 2;
+    pub const ModelDisputeVotePeriod: u64 = 2;
+    pub const ModelDisputeAppealPeriod: u64 = 2;
 
     pub const ModelDisputeCycleCount: u32 = 10;
     pub const ModelDisputeCycleLv2IncreaseCount: u32 = 3;
@@ -115,9 +128,13 @@ parameter_types! {
     pub const MinFinanceMemberDeposit: u64 = 0;
 
     pub const RedeemFeeRate: u32 = 0;
+    pub const AppFinancedExposureMax: u64 = u64::max_value();
+    pub const AppFinancedOpenRoundsMax: u32 = 100;
 
     pub const CommentRewardNormalRate: u32 = 0;
     pub const CommentRewardExpertRate: u32 = 0;
+    pub const PowerDecayPeriod: u64 = 10;
+    pub const PowerDecayRatio: Perbill = Perbill::from_percent(50);
 }
 
 impl system::Trait for Test {
@@ -197,10 +214,10 @@ impl Trait for Test {
     type DocumentCMPowerWeightAttend = ();
     type DocumentCMPowerWeightContent = ();
     type DocumentCMPowerWeightJudge = ();
-    type CommentCMPowerWeightCount = ();
-    type CommentCMPowerWeightCost = ();
-    type CommentCMPowerWeightPerCost = ();
-    type CommentCMPowerWeightPositive = ();
+    type CommentCMPowerWeightCount = CommentCMPowerWeightCount;
+    type CommentCMPowerWeightCost = CommentCMPowerWeightCost;
+    type CommentCMPowerWeightPerCost = CommentCMPowerWeightPerCost;
+    type CommentCMPowerWeightPositive = CommentCMPowerWeightPositive;
     type CMPowerAccountAttend = ();
     type ModelCreateDeposit = ();
     type ModelCycleIncomeRewardTotal = ();
@@ -208,8 +225,18 @@ impl Trait for Test {
     type Slash = ();
     type AppLeaderBoardInterval = AppLeaderBoardInterval;
     type AppLeaderBoardMaxPos = AppLeaderBoardMaxPos;
+    type AppLeaderBoardSnapshotsPerBlock = AppLeaderBoardSnapshotsPerBlock;
+    type SubmitBatchMax = SubmitBatchMax;
+    type DocumentCommentPoolMax = DocumentCommentPoolMax;
+    type LeaderBoardReward = LeaderBoardReward;
+    type LeaderBoardRewardClaimPeriod = LeaderBoardRewardClaimPeriod;
+    type LeaderBoardRewardModuleId = LeaderBoardRewardModuleId;
     type Randomness = TestRandomness;
+    type VrfRandomness = TestRandomness;
+    type UseVrfRandomness = ();
     type FinTreasuryModuleId = TreasuryModuleId;
+    type AppFinancedExposureMax = AppFinancedExposureMax;
+    type AppFinancedOpenRoundsMax = AppFinancedOpenRoundsMax;
     type ModTreasuryModuleId = TreasuryModuleId;
     type TechTreasuryModuleId = TreasuryModuleId;
     type TreasuryModuleId = TreasuryModuleId;
@@ -219,8 +246,15 @@ impl Trait for Test {
     type ModelIncomeCollectingPeriod = ModelIncomeCollectingPeriod;
     type ModelIncomeCyclePeriod = ModelIncomeCyclePeriod;
     type ModelIncomeRewardingPeriod = ModelIncomeRewardingPeriod;
+    type ModelIncomeParamsMax = ModelIncomeParamsMax;
+    type ModelExpertsMax = MaxModelExperts;
     type ModelDisputeLv1Slash = ();
     type ModelDisputeDelayTime = ModelDisputeDelayTime;
+    type ModelRetireCoolDown = ModelRetireCoolDown;
+    type ModelDisputeBond = ModelDisputeBond;
+    type ModelDisputeEvidencePeriod = ModelDisputeEvidencePeriod;
+    type ModelDisputeVotePeriod = ModelDisputeVotePeriod;
+    type ModelDisputeAppealPeriod = ModelDisputeAppealPeriod;
     type TechMemberOrigin = EnsureSignedBy<Two, u64>;
     type TechFundBase = ();
     type ModelDisputeCycleCount = ModelDisputeCycleCount;
@@ -234,6 +268,9 @@ impl Trait for Test {
 
     type CommentRewardNormalRate = CommentRewardNormalRate;
     type CommentRewardExpertRate = CommentRewardExpertRate;
+    type PowerDecayPeriod = PowerDecayPeriod;
+    type PowerDecayRatio = PowerDecayRatio;
+    type WeightInfo = ();
 }
 
 impl pallet_balances::Trait for Test {
@@ -253,6 +290,8 @@ impl members::Trait for Test {
     type ModTreasuryModuleId = TreasuryModuleId;
     type MaxFinanceMembers = MaxFinanceMembers;
     type MinFinanceMemberDeposit = MinFinanceMemberDeposit;
+    type MaxModelExperts = MaxModelExperts;
+    type WeightInfo = ();
 }
 pub type System = system::Module<Test>;
 pub type Balances = pallet_balances::Module<Test>;
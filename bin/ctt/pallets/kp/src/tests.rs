@@ -1,11 +1,12 @@
-use frame_support::{assert_err, assert_ok, dispatch};
+use frame_support::{assert_err, assert_ok, dispatch, traits::OnRuntimeUpgrade};
 use frame_system::RawOrigin;
 use sp_core::H256;
 use sp_io::hashing::blake2_256;
+use sp_runtime::traits::BlakeTwo256;
 
 use crate::mock::*;
 use crate::*;
-use sp_core::{sr25519, Pair};
+use sp_core::{ed25519, sr25519, Pair};
 
 use primitives::{Balance, PowerSize};
 
@@ -27,3 +28,1159 @@ fn kp_account_power() {
         assert!(KpModule::binary_search_closet(&test_list, &300) == 6);
     });
 }
+
+const APP_ID: u32 = 100;
+const MODEL_OWNER: u64 = 7;
+const REPORTER: u64 = 3;
+// tech committee member of MockContains
+const VOTER: u64 = 0;
+
+fn auth_account(who: u64) -> AuthAccountId {
+    let mut raw = [0u8; 32];
+    raw[..8].copy_from_slice(&who.encode());
+    AuthAccountId::from(raw)
+}
+
+fn treasury_account() -> u64 {
+    TreasuryModuleId::get().into_account()
+}
+
+fn setup_app_model(model_id: &Vec<u8>) {
+    members::Module::<Test>::config_app_setting(APP_ID, 0, b"app".to_vec(), 0);
+
+    let key = BlakeTwo256::hash_of(&(APP_ID, model_id));
+    members::Module::<Test>::set_model_creator(&key, &MODEL_OWNER, false);
+    <KPModelDataByIdHash<Test>>::insert(
+        &key,
+        KPModelData {
+            app_id: APP_ID,
+            model_id: model_id.clone(),
+            owner: auth_account(MODEL_OWNER),
+            ..Default::default()
+        },
+    );
+}
+
+#[test]
+fn model_dispute_lifecycle() {
+    new_test_ext().execute_with(|| {
+        let model_id = b"model".to_vec();
+        let comment_id = b"comment".to_vec();
+        let model_key = BlakeTwo256::hash_of(&(APP_ID, &model_id));
+        let case_key = BlakeTwo256::hash_of(&(APP_ID, &comment_id));
+        System::set_block_number(1);
+        setup_app_model(&model_id);
+        let _ = Balances::deposit_creating(&REPORTER, 1000);
+        let _ = Balances::deposit_creating(&treasury_account(), 1000);
+
+        assert_ok!(KpModule::open_model_dispute(
+            Origin::signed(REPORTER),
+            APP_ID,
+            model_id.clone(),
+            ModelDisputeType::NoneIntendNormal,
+            comment_id.clone(),
+        ));
+        assert_eq!(
+            Balances::reserved_balance(REPORTER),
+            ModelDisputeBond::get()
+        );
+        assert_eq!(KpModule::model_open_dispute_count(&model_key), 1);
+
+        // no governance shortcut nor retirement while the case is open
+        assert_err!(
+            KpModule::democracy_model_dispute(
+                RawOrigin::Root.into(),
+                APP_ID,
+                model_id.clone(),
+                ModelDisputeType::NoneIntendNormal,
+                comment_id.clone(),
+                REPORTER,
+            ),
+            Error::<Test>::ModelDisputeExisted
+        );
+        assert_err!(
+            KpModule::retire_model(Origin::signed(MODEL_OWNER), APP_ID, model_id.clone()),
+            Error::<Test>::ModelDisputeInProgress
+        );
+
+        assert_ok!(KpModule::submit_model_dispute_evidence(
+            Origin::signed(REPORTER),
+            APP_ID,
+            comment_id.clone(),
+            H256::repeat_byte(1),
+        ));
+        // votes only open after evidence period
+        assert_err!(
+            KpModule::vote_model_dispute(Origin::signed(VOTER), APP_ID, comment_id.clone(), true),
+            Error::<Test>::ModelDisputeStageInvalid
+        );
+
+        System::set_block_number(4);
+        assert_ok!(KpModule::vote_model_dispute(
+            Origin::signed(VOTER),
+            APP_ID,
+            comment_id.clone(),
+            true
+        ));
+
+        System::set_block_number(6);
+        assert_ok!(KpModule::decide_model_dispute(
+            Origin::signed(REPORTER),
+            APP_ID,
+            comment_id.clone()
+        ));
+        // nobody appealed, result is applied after appeal period
+        assert_err!(
+            KpModule::finalize_model_dispute(Origin::signed(REPORTER), APP_ID, comment_id.clone()),
+            Error::<Test>::ModelDisputeStageInvalid
+        );
+
+        System::set_block_number(9);
+        assert_ok!(KpModule::finalize_model_dispute(
+            Origin::signed(REPORTER),
+            APP_ID,
+            comment_id.clone()
+        ));
+
+        let case = KpModule::model_dispute_cases(&case_key);
+        assert_eq!(case.status, ModelDisputeStatus::Resolved);
+        assert!(case.upheld);
+        assert!(<ModelDisputeRecords<Test>>::contains_key(&case_key));
+        assert!(!<ModelOpenDisputeCount<Test>>::contains_key(&model_key));
+        // bond back and reporter paid once resolved
+        assert_eq!(Balances::reserved_balance(REPORTER), 0);
+        assert_eq!(
+            Balances::free_balance(REPORTER),
+            1000 + ModelDisputeRewardLv1::get()
+        );
+
+        assert_ok!(KpModule::retire_model(
+            Origin::signed(MODEL_OWNER),
+            APP_ID,
+            model_id.clone()
+        ));
+    });
+}
+
+#[test]
+fn model_dispute_appeal_settles_reserved_bond() {
+    new_test_ext().execute_with(|| {
+        let model_id = b"model".to_vec();
+        let comment_id = b"comment".to_vec();
+        let case_key = BlakeTwo256::hash_of(&(APP_ID, &comment_id));
+        System::set_block_number(1);
+        setup_app_model(&model_id);
+        let _ = Balances::deposit_creating(&REPORTER, 1000);
+
+        assert_ok!(KpModule::open_model_dispute(
+            Origin::signed(REPORTER),
+            APP_ID,
+            model_id.clone(),
+            ModelDisputeType::NoneIntendNormal,
+            comment_id.clone(),
+        ));
+        System::set_block_number(4);
+        assert_ok!(KpModule::vote_model_dispute(
+            Origin::signed(VOTER),
+            APP_ID,
+            comment_id.clone(),
+            false
+        ));
+        System::set_block_number(6);
+        assert_ok!(KpModule::decide_model_dispute(
+            Origin::signed(REPORTER),
+            APP_ID,
+            comment_id.clone()
+        ));
+
+        assert_ok!(KpModule::appeal_model_dispute(
+            Origin::signed(REPORTER),
+            APP_ID,
+            comment_id.clone()
+        ));
+        assert_eq!(
+            KpModule::model_dispute_cases(&case_key).appeal_bond,
+            ModelDisputeBond::get()
+        );
+        assert_eq!(
+            Balances::reserved_balance(REPORTER),
+            2 * ModelDisputeBond::get()
+        );
+
+        // bond reserved under an older constant, only that much is slashed
+        <ModelDisputeCases<Test>>::mutate(&case_key, |case| case.appeal_bond = 4);
+        assert_ok!(KpModule::democracy_judge_model_dispute(
+            Origin::signed(Two::get()),
+            APP_ID,
+            comment_id.clone(),
+            false
+        ));
+        assert_eq!(
+            KpModule::model_dispute_cases(&case_key).status,
+            ModelDisputeStatus::Resolved
+        );
+        assert_eq!(
+            Balances::reserved_balance(REPORTER),
+            ModelDisputeBond::get() - 4
+        );
+    });
+}
+
+#[test]
+fn model_dispute_appeal_bonds_after_upgrade() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::put(Releases::V10_0_0);
+        let old_case = |comment_id: &[u8], appellant: Option<u64>| ModelDisputeCaseV10::<
+            u64,
+            u64,
+            u64,
+            H256,
+        > {
+            app_id: APP_ID,
+            model_id: b"model".to_vec(),
+            comment_id: comment_id.to_vec(),
+            dispute_type: ModelDisputeType::NoneIntendNormal,
+            reporter: REPORTER,
+            owner: MODEL_OWNER,
+            bond: ModelDisputeBond::get(),
+            status: ModelDisputeStatus::Decided,
+            evidence_end: 3,
+            vote_end: 5,
+            appeal_end: 8,
+            evidences: vec![],
+            ayes: vec![],
+            nays: vec![],
+            upheld: false,
+            appellant,
+        };
+        let appealed_key = BlakeTwo256::hash_of(&(APP_ID, b"appealed".to_vec()));
+        let decided_key = BlakeTwo256::hash_of(&(APP_ID, b"decided".to_vec()));
+        unhashed::put(
+            &<ModelDisputeCases<Test>>::hashed_key_for(&appealed_key),
+            &old_case(b"appealed", Some(REPORTER)),
+        );
+        unhashed::put(
+            &<ModelDisputeCases<Test>>::hashed_key_for(&decided_key),
+            &old_case(b"decided", None),
+        );
+
+        KpModule::on_runtime_upgrade();
+        assert_eq!(StorageVersion::get(), Releases::V13_0_0);
+        let appealed = KpModule::model_dispute_cases(&appealed_key);
+        assert_eq!(appealed.appellant, Some(REPORTER));
+        assert_eq!(appealed.appeal_bond, ModelDisputeBond::get());
+        assert_eq!(appealed.comment_id, b"appealed".to_vec());
+        assert_eq!(KpModule::model_dispute_cases(&decided_key).appeal_bond, 0);
+    });
+}
+
+#[test]
+fn auth_signature_decodes_legacy_sr25519() {
+    let pair = sr25519::Pair::from_seed(&[2u8; 32]);
+    let signer: AuthAccountId = pair.public().into();
+    let message = b"message";
+
+    // bare signature of older clients followed by the next call argument, those starting
+    // with a variant byte are retried by the client with a fresh signature
+    let legacy = (0..100)
+        .map(|_| pair.sign(message))
+        .find(|sign| sign.0[0] > 2)
+        .unwrap();
+    let mut encoded = legacy.0.to_vec();
+    encoded.extend(7u32.encode());
+    let (decoded, next) = <(AuthSignature, u32)>::decode(&mut &encoded[..]).unwrap();
+    assert_eq!(next, 7);
+    assert!(decoded.verify(message, &signer));
+    // encoded back as MultiSignature
+    assert_eq!(decoded.encode(), MultiSignature::Sr25519(legacy).encode());
+
+    let current: AuthSignature = pair.sign(message).into();
+    assert_eq!(
+        AuthSignature::decode(&mut &current.encode()[..]).unwrap(),
+        current
+    );
+
+    let ed_pair = ed25519::Pair::from_seed(&[3u8; 32]);
+    let ed_sign = AuthSignature(ed_pair.sign(message).into());
+    let decoded = AuthSignature::decode(&mut &ed_sign.encode()[..]).unwrap();
+    assert!(decoded.verify(message, &ed_pair.public().into()));
+}
+
+// envelope with the current nonce of `signer`, returns it with the signature of the message
+fn signed_envelope(
+    signer: &sr25519::Pair,
+    tag: &[u8],
+    payload: &[u8],
+) -> (SignEnvelope<u64>, AuthSignature) {
+    let envelope = SignEnvelope {
+        nonce: members::Module::<Test>::sign_nonce(AuthAccountId::from(signer.public())),
+        expiry: 100,
+    };
+    let message = envelope.message(tag, &System::block_hash(0), payload);
+    (envelope, signer.sign(&message).into())
+}
+
+fn identify_item(
+    user: &sr25519::Pair,
+    document_id: &[u8],
+    product_id: &[u8],
+) -> BatchItem<H256, u64> {
+    let params = ClientParamsCreateIdentifyDoc {
+        app_id: APP_ID,
+        document_id: document_id.to_vec(),
+        product_id: product_id.to_vec(),
+        content_hash: H256::default(),
+        goods_price: 100,
+        ident_rate: 50,
+        ident_consistence: 50,
+        seller_consistence: 50,
+        cart_id: document_id.to_vec(),
+    };
+    let (envelope, app_user_sign) = signed_envelope(
+        user,
+        b"kp/create_product_identify_document",
+        &params.encode(),
+    );
+    BatchItem {
+        params: BatchItemParams::ProductIdentify(params),
+        envelope,
+        app_user_account: user.public().into(),
+        app_user_sign,
+    }
+}
+
+// app user and auth server signatures of one call payload
+fn user_auth_signs(
+    user: &sr25519::Pair,
+    auth: &sr25519::Pair,
+    tag: &[u8],
+    payload: &[u8],
+) -> (
+    SignEnvelope<u64>,
+    AuthSignature,
+    Vec<(AuthAccountId, AuthSignature)>,
+) {
+    let (envelope, user_sign) = signed_envelope(user, tag, payload);
+    let message = envelope.message(tag, &System::block_hash(0), payload);
+    let auth_signs = vec![(auth.public().into(), auth.sign(&message).into())];
+    (envelope, user_sign, auth_signs)
+}
+
+fn publish_params(model_id: &Vec<u8>, issue_rate: PowerSize) -> ClientParamsCreatePublishDoc<H256> {
+    ClientParamsCreatePublishDoc {
+        app_id: APP_ID,
+        document_id: b"publish".to_vec(),
+        model_id: model_id.clone(),
+        product_id: b"product".to_vec(),
+        content_hash: H256::default(),
+        para_issue_rate: issue_rate,
+        self_issue_rate: issue_rate,
+    }
+}
+
+// app with an auth server, `user` published product of the model
+fn setup_published_product(model_id: &Vec<u8>, user: &sr25519::Pair) -> sr25519::Pair {
+    System::set_block_number(1);
+    setup_app_model(model_id);
+
+    let auth = sr25519::Pair::from_seed(&[1u8; 32]);
+    members::Module::<Test>::config_app_key(
+        &KpModule::convert_account(&auth.public().into()),
+        APP_ID,
+    );
+
+    let params = publish_params(model_id, 50);
+    let (envelope, user_sign, auth_signs) = user_auth_signs(
+        user,
+        &auth,
+        b"kp/create_product_publish_document",
+        &params.encode(),
+    );
+    assert_ok!(KpModule::create_product_publish_document(
+        Origin::signed(REPORTER),
+        params,
+        envelope,
+        user.public().into(),
+        user_sign,
+        auth_signs,
+    ));
+    auth
+}
+
+fn create_identify_document(user: &sr25519::Pair, auth: &sr25519::Pair) {
+    let params = match identify_item(user, b"identify", b"product").params {
+        BatchItemParams::ProductIdentify(params) => params,
+        _ => unreachable!(),
+    };
+    let (envelope, user_sign, auth_signs) = user_auth_signs(
+        user,
+        auth,
+        b"kp/create_product_identify_document",
+        &params.encode(),
+    );
+    assert_ok!(KpModule::create_product_identify_document(
+        Origin::signed(REPORTER),
+        params,
+        envelope,
+        user.public().into(),
+        user_sign,
+        auth_signs,
+    ));
+}
+
+#[test]
+fn submit_batch_rolls_back_failed_item() {
+    new_test_ext().execute_with(|| {
+        let user = sr25519::Pair::from_seed(&[2u8; 32]);
+        let other = sr25519::Pair::from_seed(&[3u8; 32]);
+        let auth = setup_published_product(&b"model".to_vec(), &user);
+
+        // second item names a product never published
+        let items = vec![
+            identify_item(&user, b"identify", b"product"),
+            identify_item(&other, b"identify_missing", b"missing"),
+        ];
+        let (envelope, auth_sign) =
+            signed_envelope(&auth, b"kp/submit_batch", &(APP_ID, &items).encode());
+        assert_ok!(KpModule::submit_batch(
+            Origin::signed(REPORTER),
+            APP_ID,
+            items,
+            envelope,
+            vec![(auth.public().into(), auth_sign)],
+        ));
+
+        let events: Vec<TestEvent> = System::events().into_iter().map(|r| r.event).collect();
+        assert!(events.contains(&TestEvent::kp(RawEvent::BatchItemSucceeded(0))));
+        assert!(events.contains(&TestEvent::kp(RawEvent::BatchItemFailed(
+            1,
+            Error::<Test>::ProductNotFound.into()
+        ))));
+        assert!(events.contains(&TestEvent::kp(RawEvent::BatchSubmitted(REPORTER, 1, 1))));
+
+        assert!(<KPDocumentDataByIdHash<Test>>::contains_key(
+            BlakeTwo256::hash_of(&(APP_ID, b"identify".to_vec()))
+        ));
+        // failed item left nothing behind, its nonce included
+        assert!(!<KPDocumentDataByIdHash<Test>>::contains_key(
+            BlakeTwo256::hash_of(&(APP_ID, b"identify_missing".to_vec()))
+        ));
+        assert_eq!(
+            members::Module::<Test>::sign_nonce(AuthAccountId::from(other.public())),
+            0
+        );
+        assert_eq!(
+            members::Module::<Test>::sign_nonce(AuthAccountId::from(user.public())),
+            2
+        );
+    });
+}
+
+#[test]
+fn revise_publish_document_updates_commodity_power() {
+    new_test_ext().execute_with(|| {
+        let model_id = b"model".to_vec();
+        let user = sr25519::Pair::from_seed(&[2u8; 32]);
+        let auth = setup_published_product(&model_id, &user);
+
+        create_identify_document(&user, &auth);
+
+        let publish_key = BlakeTwo256::hash_of(&(APP_ID, b"publish".to_vec()));
+        let commodity_key = BlakeTwo256::hash_of(&(APP_ID, b"identify".to_vec()));
+        let publish_power = KpModule::kp_document_power_by_idhash(&publish_key);
+        assert_eq!(
+            KpModule::kp_purchase_power_by_idhash(&commodity_key).0,
+            publish_power
+        );
+
+        let params = publish_params(&model_id, 10);
+        let revise = ClientParamsReviseDoc {
+            app_id: APP_ID,
+            document_id: b"publish".to_vec(),
+            content_hash: H256::repeat_byte(1),
+            document_data: DocumentSpecificData::ProductPublish(KPProductPublishData {
+                para_issue_rate: params.para_issue_rate,
+                self_issue_rate: params.self_issue_rate,
+                refer_count: 0,
+            }),
+        };
+        let (envelope, user_sign, auth_signs) =
+            user_auth_signs(&user, &auth, b"kp/revise_document", &revise.encode());
+        assert_ok!(KpModule::revise_document(
+            Origin::signed(REPORTER),
+            revise,
+            envelope,
+            user.public().into(),
+            user_sign,
+            auth_signs,
+        ));
+
+        // commodity referring the product follows the revised publish power
+        let revised_power = KpModule::kp_document_power_by_idhash(&publish_key);
+        assert_ne!(revised_power, publish_power);
+        assert_eq!(
+            KpModule::kp_purchase_power_by_idhash(&commodity_key).0,
+            revised_power
+        );
+    });
+}
+
+fn create_comment(user: &sr25519::Pair, auth: &sr25519::Pair, comment_id: &[u8]) {
+    let comment_data = CommentData {
+        app_id: APP_ID,
+        document_id: b"identify".to_vec(),
+        comment_id: comment_id.to_vec(),
+        comment_hash: H256::default(),
+        comment_fee: 100,
+        comment_trend: 0,
+    };
+    let (envelope, user_sign, auth_signs) =
+        user_auth_signs(user, auth, b"kp/create_comment", &comment_data.encode());
+    assert_ok!(KpModule::create_comment(
+        Origin::signed(REPORTER),
+        comment_data,
+        envelope,
+        user.public().into(),
+        user_sign,
+        auth_signs,
+    ));
+}
+
+#[test]
+fn moderate_comment_keeps_positions_unique() {
+    new_test_ext().execute_with(|| {
+        const ADMIN: u64 = 5;
+        let user = sr25519::Pair::from_seed(&[2u8; 32]);
+        let auth = setup_published_product(&b"model".to_vec(), &user);
+        members::Module::<Test>::config_app_admin(&ADMIN, APP_ID);
+        create_identify_document(&user, &auth);
+
+        create_comment(&user, &auth, b"comment1");
+        create_comment(&user, &auth, b"comment2");
+        assert_ok!(KpModule::moderate_comment(
+            Origin::signed(ADMIN),
+            APP_ID,
+            b"comment2".to_vec()
+        ));
+        create_comment(&user, &auth, b"comment3");
+
+        // comment count went back with the moderation, the position did not
+        let comment3_key = BlakeTwo256::hash_of(&(APP_ID, b"comment3".to_vec()));
+        assert_eq!(KpModule::kp_comment_effects(&comment3_key).position, 3);
+        let doc_key = BlakeTwo256::hash_of(&(APP_ID, b"identify".to_vec()));
+        let positions: Vec<PowerSize> = KpModule::document_comments_account_pool(&doc_key)
+            .iter()
+            .map(|item| item.position)
+            .collect();
+        assert_eq!(positions, vec![1, 3]);
+
+        // comment created before effects were recorded
+        let legacy_key = BlakeTwo256::hash_of(&(APP_ID, b"legacy".to_vec()));
+        <KPCommentDataByIdHash<Test>>::insert(&legacy_key, KPCommentData::default());
+        assert_err!(
+            KpModule::moderate_comment(Origin::signed(ADMIN), APP_ID, b"legacy".to_vec()),
+            Error::<Test>::CommentEffectNotRecorded
+        );
+    });
+}
+
+#[test]
+fn moderate_comment_reclaims_from_free_balance_of_commenter() {
+    new_test_ext().execute_with(|| {
+        const ADMIN: u64 = 5;
+        let user = sr25519::Pair::from_seed(&[2u8; 32]);
+        let auth = setup_published_product(&b"model".to_vec(), &user);
+        members::Module::<Test>::config_app_admin(&ADMIN, APP_ID);
+        create_identify_document(&user, &auth);
+        create_comment(&user, &auth, b"comment");
+
+        // reward went to the commenting user, counters and power to the sender
+        let commenter = KpModule::convert_account(&user.public().into());
+        assert_ne!(commenter, REPORTER);
+        let attend_key = BlakeTwo256::hash_of(&(&REPORTER, APP_ID));
+        assert_eq!(KpModule::account_statistics_map(REPORTER).comment_num, 1);
+        assert_eq!(KpModule::account_statistics_map(commenter).comment_num, 0);
+
+        // commenter spent part of the reward and reserved some of the rest
+        let key = BlakeTwo256::hash_of(&(APP_ID, b"comment".to_vec()));
+        <KPCommentEffects<Test>>::mutate(&key, |effect| effect.reward = 500);
+        let _ = Balances::deposit_creating(&commenter, 300);
+        assert_ok!(Balances::reserve(&commenter, 100));
+
+        assert_ok!(KpModule::moderate_comment(
+            Origin::signed(ADMIN),
+            APP_ID,
+            b"comment".to_vec()
+        ));
+        assert_eq!(Balances::free_balance(commenter), 0);
+        assert_eq!(Balances::reserved_balance(commenter), 100);
+        assert_eq!(Balances::free_balance(treasury_account()), 200);
+        assert_eq!(KpModule::kp_comment_reward_shortfall(&key), 300);
+        let events: Vec<TestEvent> = System::events().into_iter().map(|r| r.event).collect();
+        assert!(events.contains(&TestEvent::kp(RawEvent::CommentModerated(
+            ADMIN,
+            APP_ID,
+            b"comment".to_vec(),
+            200,
+            300
+        ))));
+
+        assert_eq!(KpModule::account_attend_power_map(&attend_key), 0);
+        assert_eq!(KpModule::account_statistics_map(REPORTER).comment_num, 0);
+        let comment_account_key = BlakeTwo256::hash_of(&(APP_ID, REPORTER));
+        assert_eq!(
+            KpModule::kp_comment_account_record_map(&comment_account_key).count,
+            0
+        );
+    });
+}
+
+#[test]
+fn power_decay_keeps_total_in_step_after_upgrade() {
+    new_test_ext().execute_with(|| {
+        // powers stored before decay was introduced
+        StorageVersion::put(Releases::V6_0_0);
+        <MinerPowerByAccount<Test>>::insert(1, 1000);
+        <MinerPowerByAccount<Test>>::insert(2, 3000);
+        TotalPower::put(4000);
+
+        System::set_block_number(5);
+        KpModule::on_runtime_upgrade();
+        assert_eq!(StorageVersion::get(), Releases::V13_0_0);
+        assert_eq!(KpModule::miner_power_settled_at(1), Some(5));
+        assert_eq!(KpModule::miner_raw_power_by_account(2), 3000);
+        assert_eq!(KpModule::total_power_settled_at(), Some(5));
+
+        let sum_of_accounts = || KpModule::kp_account_power(1) + KpModule::kp_account_power(2);
+
+        // one period boundary passed, everything decayed by half
+        System::set_block_number(12);
+        assert_eq!(KpModule::kp_total_power(), 2000);
+        assert_eq!(KpModule::kp_total_power(), sum_of_accounts());
+
+        // account settled after the boundary still adds up with the unsettled total
+        KpModule::settle_account_power(&1);
+        System::set_block_number(25);
+        assert_eq!(KpModule::kp_total_power(), 1000);
+        assert_eq!(KpModule::kp_total_power(), sum_of_accounts());
+        assert_eq!(KpModule::kp_total_raw_power(), 4000);
+    });
+}
+
+#[test]
+fn app_stakes_backfilled_after_upgrade() {
+    new_test_ext().execute_with(|| {
+        // apps added before stake records were kept
+        StorageVersion::put(Releases::V7_0_0);
+        <AppIdRange<Test>>::insert(b"admin".to_vec(), (100, 10, 0, 1, 0));
+        <AppIdRange<Test>>::insert(b"user".to_vec(), (200, 20, 0, 1, 0));
+        for (app_id, admin, stake) in vec![(100, 11, 10), (200, 12, 20)] {
+            members::Module::<Test>::config_app_admin(&admin, app_id);
+            members::Module::<Test>::config_app_setting(app_id, 0, b"app".to_vec(), stake);
+            <AppModelTotalConfig>::insert(app_id, 0);
+        }
+        // reserved for something else than the app stake
+        let _ = Balances::deposit_creating(&11, 100);
+        assert_ok!(Balances::reserve(&11, 10));
+
+        KpModule::on_runtime_upgrade();
+        assert_eq!(StorageVersion::get(), Releases::V13_0_0);
+        assert_eq!(
+            KpModule::app_stakes(100),
+            AppStakeRecord {
+                app_type: b"admin".to_vec(),
+                owner: 11,
+                stake: 0
+            }
+        );
+        assert_eq!(
+            KpModule::app_stakes(200),
+            AppStakeRecord {
+                app_type: b"user".to_vec(),
+                owner: 12,
+                stake: 0
+            }
+        );
+
+        assert_ok!(KpModule::democracy_deregister_app(
+            RawOrigin::Root.into(),
+            100,
+            true
+        ));
+        assert_eq!(Balances::reserved_balance(11), 10);
+        assert_eq!(KpModule::app_id_range(b"admin".to_vec()).3, 0);
+        assert!(!<AppStakes<Test>>::contains_key(100));
+    });
+}
+
+#[test]
+fn deregister_app_waits_for_models_and_financed_rounds() {
+    new_test_ext().execute_with(|| {
+        let model_id = b"model".to_vec();
+        setup_app_model(&model_id);
+        <AppModelCount>::insert(APP_ID, 1);
+        <AppFinancedOpenRounds<Test>>::put(vec![(APP_ID, 20, 100)]);
+
+        // model deposit can only be returned while the app is valid
+        assert_err!(
+            KpModule::democracy_deregister_app(RawOrigin::Root.into(), APP_ID, false),
+            Error::<Test>::AppHasModels
+        );
+
+        // model killed by dispute can't be retired, it no longer holds the app
+        KpModule::disable_killed_model(APP_ID, &model_id);
+        assert_eq!(KpModule::app_model_count(APP_ID), 0);
+        KpModule::disable_killed_model(APP_ID, &model_id);
+        assert_eq!(KpModule::app_model_count(APP_ID), 0);
+
+        System::set_block_number(20);
+        assert_err!(
+            KpModule::democracy_deregister_app(RawOrigin::Root.into(), APP_ID, false),
+            Error::<Test>::AppHasOpenFinancedRounds
+        );
+
+        System::set_block_number(21);
+        assert_ok!(KpModule::democracy_deregister_app(
+            RawOrigin::Root.into(),
+            APP_ID,
+            false
+        ));
+        assert!(!members::Module::<Test>::is_valid_app(APP_ID));
+    });
+}
+
+#[test]
+fn app_model_counts_skip_killed_models_after_upgrade() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::put(Releases::V9_0_0);
+        for (model_id, status) in vec![
+            (b"enabled".to_vec(), ModelStatus::ENABLED),
+            (b"killed".to_vec(), ModelStatus::DISABLED),
+            (b"retiring".to_vec(), ModelStatus::DISABLED),
+        ] {
+            let key = BlakeTwo256::hash_of(&(APP_ID, &model_id));
+            <KPModelDataByIdHash<Test>>::insert(
+                &key,
+                KPModelData {
+                    app_id: APP_ID,
+                    model_id,
+                    status,
+                    ..Default::default()
+                },
+            );
+        }
+        let retiring_key = BlakeTwo256::hash_of(&(APP_ID, b"retiring".to_vec()));
+        <ModelRetireRecords<Test>>::insert(&retiring_key, (MODEL_OWNER, 10));
+        <AppModelCount>::insert(APP_ID, 3);
+
+        KpModule::on_runtime_upgrade();
+        assert_eq!(StorageVersion::get(), Releases::V13_0_0);
+        assert_eq!(KpModule::app_model_count(APP_ID), 2);
+    });
+}
+
+#[test]
+fn request_model_reward_reverts_on_failed_expert_transfer() {
+    new_test_ext().execute_with(|| {
+        const EXPERT: u64 = 9;
+        let model_id = b"model".to_vec();
+        let creator = sr25519::Pair::from_seed(&[4u8; 32]);
+        let creator_account = KpModule::convert_account(&creator.public().into());
+
+        members::Module::<Test>::config_app_setting(APP_ID, 0, b"app".to_vec(), 0);
+        let key = BlakeTwo256::hash_of(&(APP_ID, &model_id));
+        members::Module::<Test>::set_model_creator(&key, &creator_account, false);
+        <KPModelDataByIdHash<Test>>::insert(
+            &key,
+            KPModelData {
+                app_id: APP_ID,
+                model_id: model_id.clone(),
+                owner: creator.public().into(),
+                ..Default::default()
+            },
+        );
+
+        // expert takes half of model reward
+        let params = members::ModelExpertAddMemberParams::decode(
+            &mut &(APP_ID, model_id.clone(), 50u32).encode()[..],
+        )
+        .unwrap();
+        let (envelope, creator_sign) =
+            signed_envelope(&creator, b"members/add_expert_member", &params.encode());
+        assert_ok!(members::Module::<Test>::add_expert_member(
+            Origin::signed(EXPERT),
+            params,
+            envelope,
+            creator.public().into(),
+            creator_sign,
+        ));
+
+        // block 12 is in rewarding stage of cycle 1, model earned all income of the cycle
+        <EconomicParams<Test>>::put(KPEconomicParams {
+            model_income_cycle_period: 10,
+            model_income_collecting_period: 2,
+            model_income_rewarding_period: 4,
+            model_cycle_income_reward_total: 1000,
+            ..KpModule::default_economic_params()
+        });
+        System::set_block_number(12);
+        <ModelCycleIncomeTotal<Test>>::insert(1, 100);
+        <ModelCycleIncome<Test>>::insert(1, &key, 100);
+
+        // weight paid must cover every expert sharing the reward
+        assert_err!(
+            KpModule::request_model_reward(
+                Origin::signed(creator_account),
+                APP_ID,
+                model_id.clone(),
+                0
+            ),
+            Error::<Test>::ModelExpertCountTooSmall
+        );
+
+        // treasury pays the creator half but not the expert one
+        let _ = Balances::deposit_creating(&treasury_account(), 700);
+        assert!(KpModule::request_model_reward(
+            Origin::signed(creator_account),
+            APP_ID,
+            model_id.clone(),
+            1
+        )
+        .is_err());
+
+        assert_eq!(Balances::free_balance(creator_account), 0);
+        assert_eq!(Balances::free_balance(EXPERT), 0);
+        assert_eq!(Balances::free_balance(treasury_account()), 700);
+        assert!(!<ModelCycleIncomeRewardRecords<Test>>::contains_key(
+            1, &key
+        ));
+        assert_eq!(KpModule::model_income_reward_total(), 0);
+    });
+}
+
+#[test]
+fn economic_params_apply_at_cycle_boundary() {
+    new_test_ext().execute_with(|| {
+        let params = KPEconomicParams {
+            model_income_cycle_period: 10,
+            model_income_collecting_period: 2,
+            model_income_rewarding_period: 4,
+            ..KpModule::default_economic_params()
+        };
+        <EconomicParams<Test>>::put(params.clone());
+
+        assert_err!(
+            KpModule::democracy_set_economic_params(
+                RawOrigin::Root.into(),
+                KPEconomicParams {
+                    model_dispute_delay_time: 0,
+                    ..params.clone()
+                }
+            ),
+            Error::<Test>::EconomicParamsInvalid
+        );
+
+        // scheduled in rewarding stage of cycle 1, cycle 2 starts at block 20
+        System::set_block_number(13);
+        let longer = KPEconomicParams {
+            model_income_cycle_period: 30,
+            model_income_collecting_period: 6,
+            ..params.clone()
+        };
+        assert_ok!(KpModule::democracy_set_economic_params(
+            RawOrigin::Root.into(),
+            longer.clone()
+        ));
+        assert_eq!(
+            KpModule::pending_economic_params(),
+            Some((20, longer.clone()))
+        );
+
+        // last block of cycle 1 still runs its own stages
+        KpModule::apply_economic_params(19);
+        assert_eq!(KpModule::economic_params(), Some(params));
+        assert_eq!(KpModule::model_income_cycle_index(19), 1);
+        assert_eq!(
+            KpModule::model_income_stage(19),
+            (ModelIncomeStage::COMPENSATING, 1)
+        );
+
+        KpModule::apply_economic_params(20);
+        assert_eq!(KpModule::economic_params(), Some(longer));
+        assert_eq!(KpModule::pending_economic_params(), None);
+        assert_eq!(KpModule::model_income_cycle_index(20), 2);
+        assert_eq!(
+            KpModule::model_income_stage(20),
+            (ModelIncomeStage::COLLECTING, 6)
+        );
+        assert_eq!(KpModule::model_income_cycle_index(49), 2);
+        assert_eq!(KpModule::model_income_cycle_index(50), 3);
+    });
+}
+
+#[test]
+fn power_weight_profile_carries_comment_weights() {
+    new_test_ext().execute_with(|| {
+        let defaults = KpModule::default_power_weight_profile();
+        assert_eq!(defaults.comment_cm, (65, 20, 10, 5));
+        assert_eq!(defaults.comment_power, 40);
+        assert!(defaults.is_valid());
+
+        let profile = PowerWeightProfile {
+            comment_cm: (50, 30, 10, 10),
+            comment_power: 25,
+            ..defaults.clone()
+        };
+        assert!(profile.is_valid());
+        assert!(!PowerWeightProfile {
+            comment_cm: (50, 30, 10, 5),
+            ..profile.clone()
+        }
+        .is_valid());
+        assert!(!PowerWeightProfile {
+            comment_power: 101,
+            ..profile.clone()
+        }
+        .is_valid());
+
+        <AppPowerWeightProfiles>::insert(APP_ID, profile.clone());
+        let weights = KpModule::power_weight_profile(APP_ID);
+        assert_eq!(
+            KpModule::comment_power_weights(&weights, &DocumentType::ProductPublish),
+            (
+                profile.top.3 as PowerSize,
+                25,
+                profile.top.0 as PowerSize,
+                profile.document.2,
+                profile.comment
+            )
+        );
+        assert_eq!(
+            KpModule::comment_power_weights(&weights, &DocumentType::ModelCreate),
+            (
+                profile.cm.0 as PowerSize,
+                profile.cm.1 as PowerSize,
+                100,
+                profile.cm.3,
+                (50, 30, 10, 10)
+            )
+        );
+    });
+}
+
+#[test]
+fn power_weight_profiles_keep_weights_after_upgrade() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::put(Releases::V11_0_0);
+        let defaults = KpModule::default_power_weight_profile();
+        unhashed::put(
+            &<AppPowerWeightProfiles>::hashed_key_for(APP_ID),
+            &PowerWeightProfileV11 {
+                top: (20, 30, 30, 10, 10),
+                document: defaults.document,
+                comment: defaults.comment,
+                publish: defaults.publish,
+                identify: defaults.identify,
+                product_try: defaults.product_try,
+                choose: defaults.choose,
+                model: defaults.model,
+                cm: defaults.cm,
+            },
+        );
+
+        KpModule::on_runtime_upgrade();
+        assert_eq!(StorageVersion::get(), Releases::V13_0_0);
+        assert_eq!(
+            KpModule::app_power_weight_profile(APP_ID),
+            Some(PowerWeightProfile {
+                top: (20, 30, 30, 10, 10),
+                ..defaults
+            })
+        );
+    });
+}
+
+#[test]
+fn leader_board_sequence_keys_move_to_history_once() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::put(Releases::V12_0_0);
+        let model_id = b"m1".to_vec();
+        <AppLeaderBoardSequenceKeys<Test>>::put(vec![
+            (APP_ID, 5, model_id.clone()),
+            (APP_ID, 9, model_id.clone()),
+        ]);
+
+        KpModule::on_runtime_upgrade();
+        assert_eq!(StorageVersion::get(), Releases::V13_0_0);
+        assert!(!<AppLeaderBoardSequenceKeys<Test>>::exists());
+        let key = BlakeTwo256::hash_of(&(APP_ID, &model_id));
+        assert_eq!(KpModule::app_leader_board_history(&key), vec![5, 9]);
+        assert_eq!(
+            KpModule::app_leader_board_models(APP_ID),
+            vec![model_id.clone()]
+        );
+
+        // later upgrades leave the storage alone
+        <AppLeaderBoardSequenceKeys<Test>>::put(vec![(APP_ID, 12, model_id.clone())]);
+        KpModule::on_runtime_upgrade();
+        assert_eq!(KpModule::app_leader_board_history(&key), vec![5, 9]);
+    });
+}
+
+#[test]
+fn app_financed_end_blocks_after_upgrade() {
+    new_test_ext().execute_with(|| {
+        // round opened before end blocks were kept
+        StorageVersion::put(Releases::V8_0_0);
+        <EconomicParams<Test>>::put(KPEconomicParams {
+            app_finance_exchange_period: 10,
+            ..KpModule::default_economic_params()
+        });
+        let key = BlakeTwo256::hash_of(&(APP_ID, b"p1".to_vec()));
+        unhashed::put(
+            &<AppFinancedRecord<Test>>::hashed_key_for(&key),
+            &AppFinancedDataV8::<u64, u64> {
+                app_id: APP_ID,
+                proposal_id: b"p1".to_vec(),
+                amount: 100,
+                exchange: 100,
+                block: 1,
+                total_balance: 1000,
+                exchanged: 0,
+                exchange_end_block: 11,
+            },
+        );
+
+        KpModule::on_runtime_upgrade();
+        assert_eq!(StorageVersion::get(), Releases::V13_0_0);
+        let record = KpModule::app_finance_record(APP_ID, b"p1".to_vec());
+        assert_eq!(record.amount, 100);
+        assert_eq!(record.exchange_end_block, 11);
+        assert_eq!(record.confirm_end_block, 16);
+        assert_eq!(record.compensate_end_block, 21);
+        assert_eq!(
+            KpModule::app_financed_open_rounds(),
+            vec![(APP_ID, 21, 100)]
+        );
+    });
+}
+
+#[test]
+fn app_financed_last_moves_to_app_after_upgrade() {
+    new_test_ext().execute_with(|| {
+        // single global last round, exchange ended at block 11
+        StorageVersion::put(Releases::V2_0_0);
+        <EconomicParams<Test>>::put(KPEconomicParams {
+            app_finance_exchange_period: 10,
+            ..KpModule::default_economic_params()
+        });
+        let key = BlakeTwo256::hash_of(&(APP_ID, b"p1".to_vec()));
+        <AppFinancedLast<Test>>::put(key);
+        unhashed::put(
+            &<AppFinancedRecord<Test>>::hashed_key_for(&key),
+            &AppFinancedDataV8::<u64, u64> {
+                app_id: APP_ID,
+                proposal_id: b"p1".to_vec(),
+                amount: 100,
+                exchange: 100,
+                block: 1,
+                total_balance: 1000,
+                exchanged: 0,
+                exchange_end_block: 11,
+            },
+        );
+
+        // still in compensate stage
+        System::set_block_number(15);
+        KpModule::on_runtime_upgrade();
+        assert_eq!(StorageVersion::get(), Releases::V13_0_0);
+        assert!(!<AppFinancedLast<Test>>::exists());
+        assert_eq!(KpModule::app_financed_last_of_app(APP_ID), key);
+        assert_eq!(
+            KpModule::app_financed_open_rounds(),
+            vec![(APP_ID, 21, 100)]
+        );
+    });
+}
+
+#[test]
+fn exchange_records_keep_status_after_upgrade() {
+    new_test_ext().execute_with(|| {
+        // record exchanged before transitions were kept
+        StorageVersion::put(Releases::V3_0_0);
+        let key = KpModule::app_financed_exchange_record_key(APP_ID, &b"p1".to_vec(), &REPORTER);
+        unhashed::put(
+            &<AppFinancedUserExchangeRecord<Test>>::hashed_key_for(&key),
+            &AppFinancedUserExchangeDataV3::<u64> {
+                exchange_amount: 50,
+                status: ExchangeStatus::Confirmed,
+                pay_id: b"pay".to_vec(),
+            },
+        );
+
+        KpModule::on_runtime_upgrade();
+        assert_eq!(StorageVersion::get(), Releases::V13_0_0);
+        assert_eq!(
+            KpModule::app_finance_exchange_data(APP_ID, b"p1".to_vec(), REPORTER),
+            AppFinancedUserExchangeData {
+                exchange_amount: 50,
+                status: ExchangeStatus::Confirmed,
+                pay_id: b"pay".to_vec(),
+                transitions: vec![],
+            }
+        );
+    });
+}
+
+#[test]
+fn comment_pools_trimmed_after_upgrade() {
+    new_test_ext().execute_with(|| {
+        // pools grew without bound before V2_0_0, three cheapest comments are evicted
+        StorageVersion::put(Releases::V1_0_0);
+        let pool: Vec<CommentWeightData<Test>> = (1..=103)
+            .map(|position| CommentWeightData {
+                account: position,
+                position,
+                cash_cost: if position % 40 == 5 { 1 } else { 10 },
+            })
+            .collect();
+        let key = BlakeTwo256::hash_of(&(APP_ID, b"doc".to_vec()));
+        <DocumentCommentsAccountPool<Test>>::insert(&key, pool);
+        let small_key = BlakeTwo256::hash_of(&(APP_ID, b"small".to_vec()));
+        <DocumentCommentsAccountPool<Test>>::insert(&small_key, vec![CommentWeightData::default()]);
+
+        KpModule::on_runtime_upgrade();
+        assert_eq!(StorageVersion::get(), Releases::V13_0_0);
+        let positions: Vec<u64> = KpModule::document_comments_account_pool(&key)
+            .iter()
+            .map(|comment| comment.position)
+            .collect();
+        let kept: Vec<u64> = (1..=103).filter(|position| position % 40 != 5).collect();
+        assert_eq!(positions, kept);
+        assert_eq!(
+            KpModule::document_comments_account_pool(&small_key).len(),
+            1
+        );
+    });
+}
+
+#[test]
+fn random_draws_differ_within_epoch() {
+    new_test_ext().execute_with(|| {
+        let round = |proposal_id: &[u8]| BlakeTwo256::hash_of(&(APP_ID, proposal_id.to_vec()));
+        let finance_seed = |key: H256| KpModule::random_seed(&(b"ctt_finance", key).encode());
+
+        // epoch randomness is fixed, rounds of one block still draw differently
+        System::set_block_number(5);
+        let first = finance_seed(round(b"p1"));
+        assert_ne!(first, finance_seed(round(b"p2")));
+        assert_ne!(
+            KpModule::random_seed(&(b"ctt_power", APP_ID, b"m1".to_vec()).encode()),
+            KpModule::random_seed(&(b"ctt_power", APP_ID, b"m2".to_vec()).encode())
+        );
+
+        // and the same round draws differently in a later block
+        System::set_block_number(6);
+        assert_ne!(first, finance_seed(round(b"p1")));
+    });
+}
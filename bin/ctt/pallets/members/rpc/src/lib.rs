@@ -7,7 +7,7 @@ use members_runtime_api::MembersApi as MembersRuntimeApi;
 pub use members_runtime_api::MembersApi as MembersRuntimeRpcApi;
 use primitives::AccountId;
 use serde::{Deserialize, Serialize};
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_core::Bytes;
 use sp_runtime::{generic::BlockId, traits::Block as BlockT};
@@ -67,6 +67,9 @@ pub trait MembersApi<BlockHash, AccountId> {
         query: QueryModelExpertParams,
         at: Option<BlockHash>,
     ) -> Result<AccountId>;
+
+    #[rpc(name = "members_signNonce")]
+    fn sign_nonce(&self, account: AccountId, at: Option<BlockHash>) -> Result<u64>;
 }
 
 /// A struct that implements the `MembersApi`.
@@ -104,6 +107,12 @@ impl From<Error> for i64 {
     }
 }
 
+// runtimes before MembersApi version 2 keep no sign nonces
+fn has_api_v2<Block: BlockT, Api: ApiExt<Block>>(api: &Api, at: &BlockId<Block>) -> bool {
+    api.has_api_with::<dyn MembersRuntimeApi<Block, AccountId, Error = ()>, _>(at, |v| v >= 2)
+        .unwrap_or_default()
+}
+
 impl<C, Block> MembersApi<<Block as BlockT>::Hash, AccountId> for Members<C, Block>
 where
     Block: BlockT,
@@ -214,4 +223,26 @@ where
             data: Some(format!("{:?}", e).into()),
         })
     }
+
+    fn sign_nonce(&self, account: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<u64> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(||
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash));
+
+        if !has_api_v2(&*api, &at) {
+            return Err(RpcError {
+                code: ErrorCode::ServerError(Error::RuntimeError.into()),
+                message: "Sign nonce is not supported by the runtime".into(),
+                data: None,
+            });
+        }
+
+        let runtime_api_result = api.sign_nonce(&at, account);
+        runtime_api_result.map_err(|e| RpcError {
+            code: ErrorCode::ServerError(9876), // No real reason for this value
+            message: "Something wrong".into(),
+            data: Some(format!("{:?}", e).into()),
+        })
+    }
 }
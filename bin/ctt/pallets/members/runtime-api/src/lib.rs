@@ -5,11 +5,14 @@ use codec::Codec;
 use sp_std::prelude::*;
 
 sp_api::decl_runtime_apis! {
+    // 2: sign_nonce, callers must check the version before calling it
+    #[api_version(2)]
     pub trait MembersApi<AccountId> where AccountId: Codec {
         fn is_platform_expert(account: AccountId, app_id: u32) -> bool;
         fn is_model_expert(account: AccountId, app_id: u32, model_id: Vec<u8>) -> bool;
         fn is_model_creator(account: AccountId, app_id: u32, model_id: Vec<u8>) -> bool;
         fn model_experts(app_id: u32, model_id: Vec<u8>) -> Vec<AccountId>;
         fn model_creator(app_id: u32, model_id: Vec<u8>) -> AccountId;
+        fn sign_nonce(account: AccountId) -> u64;
     }
 }
@@ -0,0 +1,370 @@
+//! Members pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_core::{crypto::key_types, sr25519};
+use sp_runtime::traits::Bounded;
+
+use crate::Module as Members;
+
+const SEED: u32 = 0;
+const APP_ID: u32 = 100;
+// upper bound used for the sets which are not limited by config
+const MAX_MEMBERS: u32 = 1000;
+
+fn new_signer() -> sr25519::Public {
+    sp_io::crypto::sr25519_generate(key_types::ACCOUNT, None)
+}
+
+fn sign(signer: &sr25519::Public, msg: &[u8]) -> AuthSignature {
+    sp_io::crypto::sr25519_sign(key_types::ACCOUNT, signer, msg)
+        .expect("signer was generated in keystore; qed")
+        .into()
+}
+
+// envelope with the current nonce of the primary `signer`
+fn envelope<T: Trait>(signer: &sr25519::Public) -> SignEnvelope<T::BlockNumber> {
+    SignEnvelope {
+        nonce: Members::<T>::sign_nonce(AuthAccountId::from(*signer)),
+        expiry: T::BlockNumber::max_value(),
+    }
+}
+
+fn sign_envelope<T: Trait>(
+    signer: &sr25519::Public,
+    tag: &[u8],
+    envelope: &SignEnvelope<T::BlockNumber>,
+    payload: &[u8],
+) -> AuthSignature {
+    let genesis_hash = frame_system::Module::<T>::block_hash(T::BlockNumber::zero());
+    sign(signer, &envelope.message(tag, &genesis_hash, payload))
+}
+
+fn account_of<T: Trait>(signer: &sr25519::Public) -> T::AccountId {
+    Members::<T>::convert_account(&AuthAccountId::from(*signer))
+}
+
+fn funded<T: Trait>(who: &T::AccountId) {
+    T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 2u32.into());
+}
+
+// sorted set with `n` generated members
+fn members_of<T: Trait>(name: &'static str, n: u32) -> Vec<T::AccountId> {
+    let mut members: Vec<T::AccountId> = (0..n).map(|i| account(name, i, SEED)).collect();
+    members.sort();
+    members
+}
+
+fn setup_app<T: Trait>() {
+    Members::<T>::config_app_setting(APP_ID, 100, b"bench".to_vec(), 0u32.into());
+}
+
+// fill app admins and app keys with `k` members each, returns (signing admin, app key)
+fn setup_app_keys<T: Trait>(k: u32) -> (sr25519::Public, T::AccountId) {
+    setup_app::<T>();
+
+    let admin = new_signer();
+    let app_key: T::AccountId = whitelisted_caller();
+
+    let mut admins = members_of::<T>("admin", k.saturating_sub(1));
+    let mut keys = members_of::<T>("app_key", k.saturating_sub(1));
+    admins.push(account_of::<T>(&admin));
+    admins.sort();
+    keys.push(app_key.clone());
+    keys.sort();
+
+    <AppAdmins<T>>::insert(APP_ID, admins);
+    <AppKeys<T>>::insert(APP_ID, keys);
+
+    (admin, app_key)
+}
+
+fn setup_app_admin<T: Trait>() -> T::AccountId {
+    setup_app::<T>();
+
+    let admin: T::AccountId = whitelisted_caller();
+    <AppAdmins<T>>::insert(APP_ID, vec![admin.clone()]);
+    admin
+}
+
+fn setup_model<T: Trait>(creator: &sr25519::Public, m: u32) -> Vec<u8> {
+    setup_app::<T>();
+
+    let model_id = b"model".to_vec();
+    let key = T::Hashing::hash_of(&(APP_ID, &model_id));
+    let mut members = members_of::<T>("expert", m);
+    let creator = account_of::<T>(creator);
+    if let Err(index) = members.binary_search(&creator) {
+        members.insert(index, creator.clone());
+    }
+
+    <ModelCreators<T>>::insert(&key, creator);
+    <ExpertMembers<T>>::insert(&key, members);
+    model_id
+}
+
+benchmarks! {
+    _ { }
+
+    add_investor_member {
+        let m in 0 .. MAX_MEMBERS;
+
+        let caller = setup_app_admin::<T>();
+        AppInvestorMembers::<T>::insert(APP_ID, members_of::<T>("investor", m));
+        let new_member: T::AccountId = account("new_member", 0, SEED);
+    }: _(RawOrigin::Signed(caller), APP_ID, new_member.clone())
+    verify {
+        assert!(Members::<T>::is_investor(&new_member, APP_ID));
+    }
+
+    remove_investor_member {
+        let m in 1 .. MAX_MEMBERS;
+
+        let caller = setup_app_admin::<T>();
+        let members = members_of::<T>("investor", m);
+        let old_member = members[0].clone();
+        AppInvestorMembers::<T>::insert(APP_ID, members);
+    }: _(RawOrigin::Signed(caller), APP_ID, old_member.clone())
+    verify {
+        assert!(!Members::<T>::is_investor(&old_member, APP_ID));
+    }
+
+    add_finance_member {
+        let m in 0 .. T::MaxFinanceMembers::get() - 1;
+
+        let caller: T::AccountId = whitelisted_caller();
+        <FinanceRoot<T>>::put(&caller);
+        FinanceMembers::<T>::put(members_of::<T>("finance", m));
+
+        let user = new_signer();
+        let member = account_of::<T>(&user);
+        funded::<T>(&member);
+        let params = FinanceMemberParams {
+            deposit: T::MinFinanceMemberDeposit::get(),
+            member: member.clone(),
+        };
+        let envelope = envelope::<T>(&user);
+        let user_sign = sign_envelope::<T>(&user, b"members/add_finance_member", &envelope, &params.encode());
+    }: _(RawOrigin::Signed(caller), params, envelope, user.into(), user_sign)
+    verify {
+        assert!(Members::<T>::is_finance_member(&member));
+    }
+
+    remove_finance_member {
+        let m in 1 .. T::MaxFinanceMembers::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+        <FinanceRoot<T>>::put(&caller);
+        let members = members_of::<T>("finance", m);
+        let old_member = members[0].clone();
+        FinanceMembers::<T>::put(members);
+    }: _(RawOrigin::Signed(caller), old_member.clone())
+    verify {
+        assert!(!Members::<T>::is_finance_member(&old_member));
+    }
+
+    finance_member_add_deposit {
+        let caller: T::AccountId = whitelisted_caller();
+        funded::<T>(&caller);
+        let deposit = T::MinFinanceMemberDeposit::get().max(1u32.into());
+    }: _(RawOrigin::Signed(caller.clone()), deposit)
+    verify {
+        assert_eq!(Members::<T>::finance_member_deposit(&caller), deposit);
+    }
+
+    add_app_admin {
+        let k in 1 .. MAX_APP_KEYS as u32 - 1;
+
+        let (admin, caller) = setup_app_keys::<T>(k);
+        let member: T::AccountId = account("new_member", 0, SEED);
+        let params = AppKeyManageParams {
+            admin: admin.into(),
+            app_id: APP_ID,
+            member: member.clone(),
+        };
+        let envelope = envelope::<T>(&admin);
+        let admin_sign = sign_envelope::<T>(&admin, b"members/add_app_admin", &envelope, &params.encode());
+    }: _(RawOrigin::Signed(caller), params, envelope, admin_sign)
+    verify {
+        assert!(Members::<T>::is_app_admin(&member, APP_ID));
+    }
+
+    remove_app_admin {
+        let k in 2 .. MAX_APP_KEYS as u32;
+
+        let (admin, caller) = setup_app_keys::<T>(k);
+        let member: T::AccountId = account("admin", 0, SEED);
+        let params = AppKeyManageParams {
+            admin: admin.into(),
+            app_id: APP_ID,
+            member: member.clone(),
+        };
+        let envelope = envelope::<T>(&admin);
+        let admin_sign = sign_envelope::<T>(&admin, b"members/remove_app_admin", &envelope, &params.encode());
+    }: _(RawOrigin::Signed(caller), params, envelope, admin_sign)
+    verify {
+        assert!(!Members::<T>::is_app_admin(&member, APP_ID));
+    }
+
+    add_app_key {
+        let k in 1 .. MAX_APP_KEYS as u32 - 1;
+
+        let (admin, caller) = setup_app_keys::<T>(k);
+        let member: T::AccountId = account("new_member", 0, SEED);
+        let params = AppKeyManageParams {
+            admin: admin.into(),
+            app_id: APP_ID,
+            member: member.clone(),
+        };
+        let envelope = envelope::<T>(&admin);
+        let admin_sign = sign_envelope::<T>(&admin, b"members/add_app_key", &envelope, &params.encode());
+    }: _(RawOrigin::Signed(caller), params, envelope, admin_sign)
+    verify {
+        assert!(Members::<T>::is_app_identity(&member, APP_ID));
+    }
+
+    remove_app_key {
+        let k in 2 .. MAX_APP_KEYS as u32;
+
+        let (admin, caller) = setup_app_keys::<T>(k);
+        let member: T::AccountId = account("app_key", 0, SEED);
+        let params = AppKeyManageParams {
+            admin: admin.into(),
+            app_id: APP_ID,
+            member: member.clone(),
+        };
+        let envelope = envelope::<T>(&admin);
+        let admin_sign = sign_envelope::<T>(&admin, b"members/remove_app_key", &envelope, &params.encode());
+    }: _(RawOrigin::Signed(caller), params, envelope, admin_sign)
+    verify {
+        assert!(!Members::<T>::is_app_identity(&member, APP_ID));
+    }
+
+    add_app_platform_expert_member {
+        let m in 0 .. MAX_MEMBERS;
+
+        let caller = setup_app_admin::<T>();
+        <AppPlatformExpertMembers<T>>::insert(APP_ID, members_of::<T>("platform", m));
+        let new_member: T::AccountId = account("new_member", 0, SEED);
+    }: _(RawOrigin::Signed(caller), APP_ID, new_member.clone())
+    verify {
+        assert!(Members::<T>::is_platform_expert(&new_member, APP_ID));
+    }
+
+    remove_app_platform_expert_member {
+        let m in 1 .. MAX_MEMBERS;
+
+        let caller = setup_app_admin::<T>();
+        let members = members_of::<T>("platform", m);
+        let old_member = members[0].clone();
+        <AppPlatformExpertMembers<T>>::insert(APP_ID, members);
+    }: _(RawOrigin::Signed(caller), APP_ID, old_member.clone())
+    verify {
+        assert!(!Members::<T>::is_platform_expert(&old_member, APP_ID));
+    }
+
+    add_expert_member {
+        // model creator is kept in the expert set too
+        let m in 0 .. T::MaxModelExperts::get() - 2;
+
+        let creator = new_signer();
+        let model_id = setup_model::<T>(&creator, m);
+        let caller: T::AccountId = whitelisted_caller();
+        let params = ModelExpertAddMemberParams {
+            app_id: APP_ID,
+            model_id: model_id.clone(),
+            kpt_profit_rate: 10,
+        };
+        let envelope = envelope::<T>(&creator);
+        let creator_sign = sign_envelope::<T>(&creator, b"members/add_expert_member", &envelope, &params.encode());
+    }: _(RawOrigin::Signed(caller.clone()), params, envelope, creator.into(), creator_sign)
+    verify {
+        assert!(Members::<T>::is_model_expert(&caller, APP_ID, &model_id));
+    }
+
+    remove_expert_member {
+        let m in 1 .. T::MaxModelExperts::get() - 1;
+
+        let creator = new_signer();
+        let model_id = setup_model::<T>(&creator, m);
+        let admin = new_signer();
+        <AppAdmins<T>>::insert(APP_ID, vec![account_of::<T>(&admin)]);
+
+        let caller: T::AccountId = whitelisted_caller();
+        let member: T::AccountId = account("expert", 0, SEED);
+        let params = ModelExpertDelMemberParams {
+            app_id: APP_ID,
+            model_id: model_id.clone(),
+            member: member.clone(),
+        };
+        let envelope = envelope::<T>(&creator);
+        let encode = params.encode();
+        let creator_sign = sign_envelope::<T>(&creator, b"members/remove_expert_member", &envelope, &encode);
+        let admin_sign = sign_envelope::<T>(&admin, b"members/remove_expert_member", &envelope, &encode);
+    }: _(RawOrigin::Signed(caller), params, envelope, creator.into(), creator_sign, admin.into(), admin_sign)
+    verify {
+        assert!(!Members::<T>::is_model_expert(&member, APP_ID, &model_id));
+    }
+
+    air_drop_new_user_benefit {
+        setup_app::<T>();
+        let caller: T::AccountId = whitelisted_caller();
+        funded::<T>(&caller);
+        let receiver: T::AccountId = account("receiver", 0, SEED);
+        let amount = T::Currency::minimum_balance().max(1u32.into());
+    }: _(RawOrigin::Signed(caller), APP_ID, b"user".to_vec(), receiver.clone(), amount)
+    verify {
+        assert_eq!(T::Currency::free_balance(&receiver), amount);
+    }
+
+    stable_exchange {
+        setup_app::<T>();
+        let caller: T::AccountId = whitelisted_caller();
+        funded::<T>(&caller);
+        let receiver: T::AccountId = account("receiver", 0, SEED);
+        let amount = T::Currency::minimum_balance().max(1u32.into());
+    }: _(RawOrigin::Signed(caller), amount, receiver.clone(), APP_ID, b"receipt".to_vec())
+    verify {
+        assert_eq!(T::Currency::free_balance(&receiver), amount);
+    }
+
+    stable_redeem {
+        setup_app::<T>();
+        let caller: T::AccountId = whitelisted_caller();
+        funded::<T>(&caller);
+        let redeem_account: T::AccountId = account("redeem", 0, SEED);
+        <AppRedeemAccount<T>>::insert(APP_ID, &redeem_account);
+
+        let amount = T::Currency::minimum_balance().max(1u32.into());
+        let key = T::Hashing::hash_of(&(APP_ID, b"receipt".to_vec()));
+        <StableExchangeRecords<T>>::insert(&key, StableExchangeData {
+            receiver: caller.clone(),
+            amount,
+            redeemed: false,
+        });
+    }: _(RawOrigin::Signed(caller), APP_ID, b"receipt".to_vec())
+    verify {
+        assert!(<StableExchangeRecords<T>>::get(&key).redeemed);
+    }
+
+    set_app_key_threshold {
+        let (_, _) = setup_app_keys::<T>(MAX_APP_KEYS as u32);
+        let caller = setup_app_admin::<T>();
+        let threshold = MAX_APP_KEYS as u32;
+    }: _(RawOrigin::Signed(caller), APP_ID, threshold)
+    verify {
+        assert_eq!(Members::<T>::app_key_threshold(APP_ID), threshold);
+    }
+
+    set_app_redeem_account {
+        let caller = setup_app_admin::<T>();
+        let redeem_account: T::AccountId = account("redeem", 0, SEED);
+    }: _(RawOrigin::Signed(caller), APP_ID, redeem_account.clone())
+    verify {
+        assert_eq!(Members::<T>::app_redeem_account(APP_ID), redeem_account);
+    }
+}
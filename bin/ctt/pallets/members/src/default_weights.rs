@@ -0,0 +1,116 @@
+//! Default weights of the members pallet.
+//!
+//! These are hand estimated values, not benchmark output. Replace them with the output of
+//! `ctt benchmark --chain dev --execution wasm --wasm-execution compiled --pallet members
+//! --extrinsic '*' --steps 50 --repeat 20` (node built with `--features runtime-benchmarks`)
+//! once the benchmarks are run on reference hardware. Every call here has a benchmark.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
+
+impl crate::WeightInfo for () {
+    fn add_investor_member(m: u32, ) -> Weight {
+        (41000000 as Weight)
+            .saturating_add((186000 as Weight).saturating_mul(m as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn remove_investor_member(m: u32, ) -> Weight {
+        (39000000 as Weight)
+            .saturating_add((179000 as Weight).saturating_mul(m as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn add_finance_member(m: u32, ) -> Weight {
+        (96000000 as Weight)
+            .saturating_add((214000 as Weight).saturating_mul(m as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(3 as Weight))
+    }
+    fn remove_finance_member(m: u32, ) -> Weight {
+        (58000000 as Weight)
+            .saturating_add((201000 as Weight).saturating_mul(m as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn finance_member_add_deposit() -> Weight {
+        (52000000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn add_app_admin(k: u32, ) -> Weight {
+        (112000000 as Weight)
+            .saturating_add((392000 as Weight).saturating_mul(k as Weight))
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn remove_app_admin(k: u32, ) -> Weight {
+        (108000000 as Weight)
+            .saturating_add((388000 as Weight).saturating_mul(k as Weight))
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn add_app_key(k: u32, ) -> Weight {
+        (112000000 as Weight)
+            .saturating_add((395000 as Weight).saturating_mul(k as Weight))
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn remove_app_key(k: u32, ) -> Weight {
+        (108000000 as Weight)
+            .saturating_add((391000 as Weight).saturating_mul(k as Weight))
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn add_app_platform_expert_member(m: u32, ) -> Weight {
+        (44000000 as Weight)
+            .saturating_add((187000 as Weight).saturating_mul(m as Weight))
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn remove_app_platform_expert_member(m: u32, ) -> Weight {
+        (42000000 as Weight)
+            .saturating_add((181000 as Weight).saturating_mul(m as Weight))
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn add_expert_member(m: u32, ) -> Weight {
+        (98000000 as Weight)
+            .saturating_add((189000 as Weight).saturating_mul(m as Weight))
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn remove_expert_member(m: u32, ) -> Weight {
+        (141000000 as Weight)
+            .saturating_add((183000 as Weight).saturating_mul(m as Weight))
+            .saturating_add(DbWeight::get().reads(4 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn air_drop_new_user_benefit() -> Weight {
+        (84000000 as Weight)
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn stable_exchange() -> Weight {
+        (86000000 as Weight)
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn stable_redeem() -> Weight {
+        (91000000 as Weight)
+            .saturating_add(DbWeight::get().reads(4 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn set_app_redeem_account() -> Weight {
+        (28000000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn set_app_key_threshold() -> Weight {
+        (30000000 as Weight)
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+}
@@ -8,15 +8,16 @@ use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
     dispatch::DispatchResult,
     ensure,
+    storage::IterableStorageMap,
     traits::{Currency, ExistenceRequirement::KeepAlive, Get, ReservableCurrency},
+    weights::Weight,
 };
 use frame_system::{self as system, ensure_signed};
-use primitives::{AuthAccountId, Membership};
-use sp_core::sr25519;
+use primitives::{AuthAccountId, AuthSignature, Membership, SignEnvelope, SignEnvelopeError};
 use sp_runtime::{
     print,
-    traits::{AccountIdConversion, Hash, Verify},
-    ModuleId, MultiSignature, RuntimeDebug,
+    traits::{AccountIdConversion, Hash},
+    ModuleId, RuntimeDebug,
 };
 use sp_std::cmp::*;
 use sp_std::prelude::*;
@@ -24,9 +25,28 @@ use sp_std::prelude::*;
 #[cfg(test)]
 mod tests;
 
+mod benchmarking;
+mod default_weights;
+
 type BalanceOf<T> =
     <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
 
+// A value placed in storage that represents the current version of the Members storage.
+// This value is used by the `on_runtime_upgrade` logic to determine whether we run
+// storage migration logic.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+enum Releases {
+    V1_0_0,
+    // investor members scoped per app
+    V2_0_0,
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V1_0_0
+    }
+}
+
 #[derive(Encode, Decode, Clone, Default, RuntimeDebug)]
 pub struct AppData<Balance> {
     name: Vec<u8>,
@@ -55,6 +75,7 @@ impl<T: Trait> Default for StableExchangeData<T> {
 pub struct ModelExpertAddMemberParams {
     app_id: u32,
     model_id: Vec<u8>,
+    // percent of model cycle reward paid to this expert
     kpt_profit_rate: u32,
 }
 
@@ -78,6 +99,27 @@ pub struct FinanceMemberParams<Account, Balance> {
     member: Account,
 }
 
+pub trait WeightInfo {
+    fn add_investor_member(m: u32, ) -> Weight;
+    fn remove_investor_member(m: u32, ) -> Weight;
+    fn add_finance_member(m: u32, ) -> Weight;
+    fn remove_finance_member(m: u32, ) -> Weight;
+    fn finance_member_add_deposit() -> Weight;
+    fn add_app_admin(k: u32, ) -> Weight;
+    fn remove_app_admin(k: u32, ) -> Weight;
+    fn add_app_key(k: u32, ) -> Weight;
+    fn remove_app_key(k: u32, ) -> Weight;
+    fn add_app_platform_expert_member(m: u32, ) -> Weight;
+    fn remove_app_platform_expert_member(m: u32, ) -> Weight;
+    fn add_expert_member(m: u32, ) -> Weight;
+    fn remove_expert_member(m: u32, ) -> Weight;
+    fn air_drop_new_user_benefit() -> Weight;
+    fn stable_exchange() -> Weight;
+    fn stable_redeem() -> Weight;
+    fn set_app_redeem_account() -> Weight;
+    fn set_app_key_threshold() -> Weight;
+}
+
 pub trait Trait: system::Trait {
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
     type Currency: ReservableCurrency<Self::AccountId>;
@@ -85,6 +127,9 @@ pub trait Trait: system::Trait {
     type ModTreasuryModuleId: Get<ModuleId>;
     type MaxFinanceMembers: Get<u32>;
     type MinFinanceMemberDeposit: Get<BalanceOf<Self>>;
+    type MaxModelExperts: Get<u32>;
+    /// Weight information for extrinsics in this pallet.
+    type WeightInfo: WeightInfo;
 }
 
 const MAX_APP_KEYS: usize = 16;
@@ -99,14 +144,38 @@ decl_event!(
         MemberAdded(AccountId),
         /// Removed a member
         MemberRemoved(AccountId),
-        AppAdminSet(AccountId),
-        AppKeysSet(AccountId),
+        /// (app_id, investor)
+        InvestorMemberAdded(u32, AccountId),
+        InvestorMemberRemoved(u32, AccountId),
+        /// (member, deposit)
+        FinanceMemberAdded(AccountId, Balance),
+        FinanceMemberRemoved(AccountId, Balance),
+        /// (app_id, admin)
+        AppAdminSet(u32, AccountId),
+        AppAdminRemoved(u32, AccountId),
+        /// (app_id, key)
+        AppKeysSet(u32, AccountId),
+        AppKeyRemoved(u32, AccountId),
+        /// (app_id, expert)
+        AppPlatformExpertAdded(u32, AccountId),
+        AppPlatformExpertRemoved(u32, AccountId),
+        /// (app_id, model_id, expert, kpt profit rate)
+        ModelExpertAdded(u32, Vec<u8>, AccountId, u32),
+        /// (app_id, model_id, expert)
+        ModelExpertRemoved(u32, Vec<u8>, AccountId),
         ModleCreatorAdded(AccountId),
-        NewUserBenefitDrpped(AccountId, Balance),
-        StableExchanged(AccountId),
-        AppRedeemAccountSet(AccountId),
-        AppRedeemed(AccountId, AccountId, Balance),
-        FinanceMemberDeposit(AccountId),
+        /// (app_id, user_id, receiver, amount)
+        NewUserBenefitDrpped(u32, Vec<u8>, AccountId, Balance),
+        /// (sender, receiver, app_id, cash_receipt, amount)
+        StableExchanged(AccountId, AccountId, u32, Vec<u8>, Balance),
+        /// (app_id, account)
+        AppRedeemAccountSet(u32, AccountId),
+        /// (sender, app redeem account, app_id, cash_receipt, amount)
+        AppRedeemed(AccountId, AccountId, u32, Vec<u8>, Balance),
+        /// (member, added deposit, total deposit)
+        FinanceMemberDeposit(AccountId, Balance, Balance),
+        /// (app_id, threshold)
+        AppKeyThresholdSet(u32, u32),
     }
 );
 
@@ -122,9 +191,13 @@ decl_storage! {
         FinanceMemberDeposit get(fn finance_member_deposit):
             map hasher(twox_64_concat) T::AccountId => BalanceOf<T>;
 
-        // Investor members, system level
+        // Investor members, system level, replaced by AppInvestorMembers and only kept for migration
         InvestorMembers get(fn investor_members): Vec<T::AccountId>;
 
+        // app level investor members, key is app_id, managed by app_admins
+        AppInvestorMembers get(fn app_investor_members):
+            map hasher(twox_64_concat) u32 => Vec<T::AccountId>;
+
         // app level admin members key is app_id
         AppAdmins get(fn app_admins):
             map hasher(twox_64_concat) u32 => Vec<T::AccountId>;
@@ -164,6 +237,21 @@ decl_storage! {
         // app_id stash account(for redeem receiver)
         AppRedeemAccount get(fn app_redeem_account):
             map hasher(twox_64_concat) u32 => T::AccountId;
+
+        // app_id -> app key signatures required by kp submissions, 0 means 1
+        AppKeyThreshold get(fn app_key_threshold):
+            map hasher(twox_64_concat) u32 => u32;
+
+        // app_id -> suspended by governance, suspended app is not valid
+        AppSuspended get(fn app_suspended):
+            map hasher(twox_64_concat) u32 => bool;
+
+        // next envelope nonce of app user / admin / model creator signed payloads, shared with kp
+        SignNonces get(fn sign_nonce):
+            map hasher(twox_64_concat) AuthAccountId => u64;
+
+        // Storage version of the pallet, new networks start with the last version
+        StorageVersion build(|_: &GenesisConfig<T>| Releases::V2_0_0): Releases;
     }
 }
 
@@ -192,8 +280,13 @@ decl_error! {
         AppKeysLimitReached,
         AppKeysOnlyOne,
         FinanceMemberSizeOver,
+        ExpertMemberSizeOver,
         FinanceMemberDepositTooLow,
         DepositTooSmall,
+        SignEnvelopeExpired,
+        SignNonceInvalid,
+        AppKeyThresholdInvalid,
+        ProfitRateInvalid,
     }
 }
 
@@ -203,9 +296,28 @@ impl<T: Trait> Module<T> {
         T::AccountId::decode(&mut &tmp[..]).unwrap_or_default()
     }
 
-    fn verify_sign(pub_key: &AuthAccountId, sign: sr25519::Signature, msg: &[u8]) -> bool {
-        let ms: MultiSignature = sign.into();
-        ms.verify(msg, &pub_key)
+    // sr25519/ed25519 signers must be the account itself, ecdsa signers are recovered
+    // and must hash to the account, bare sr25519 signatures of older clients still decode
+    fn verify_sign(pub_key: &AuthAccountId, sign: AuthSignature, msg: &[u8]) -> bool {
+        sign.verify(msg, pub_key)
+    }
+
+    // check envelope of the primary signer, returns the message all signers signed
+    fn sign_envelope_message(
+        tag: &[u8],
+        signer: &AuthAccountId,
+        envelope: &SignEnvelope<T::BlockNumber>,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, Error<T>> {
+        let nonce = <SignNonces>::get(signer);
+        primitives::sign_envelope_message::<T>(tag, nonce, envelope, payload).map_err(|e| match e {
+            SignEnvelopeError::Expired => Error::<T>::SignEnvelopeExpired,
+            SignEnvelopeError::NonceInvalid => Error::<T>::SignNonceInvalid,
+        })
+    }
+
+    fn inc_sign_nonce(who: &AuthAccountId) {
+        <SignNonces>::mutate(who, |nonce| *nonce += 1);
     }
 
     pub fn is_platform_expert(who: &T::AccountId, app_id: u32) -> bool {
@@ -225,8 +337,8 @@ impl<T: Trait> Module<T> {
         }
     }
 
-    pub fn is_investor(who: &T::AccountId) -> bool {
-        let members = InvestorMembers::<T>::get();
+    pub fn is_investor(who: &T::AccountId, app_id: u32) -> bool {
+        let members = <AppInvestorMembers<T>>::get(app_id);
         match members.binary_search(who) {
             Ok(_) => true,
             Err(_) => false,
@@ -266,6 +378,47 @@ impl<T: Trait> Module<T> {
         <ExpertMembers<T>>::get(&key)
     }
 
+    // global investors were added by admins of any app, so every registered app keeps them,
+    // app admins can remove the ones they never approved
+    fn migrate_investor_members() -> Weight {
+        let db = T::DbWeight::get();
+        let investors = <InvestorMembers<T>>::take();
+        if investors.is_empty() {
+            return db.reads_writes(1, 1);
+        }
+
+        let mut apps: Weight = 0;
+        for (app_id, _) in <AppDataMap<T>>::iter() {
+            apps += 1;
+            let mut members = <AppInvestorMembers<T>>::get(app_id);
+            for investor in investors.iter() {
+                if let Err(index) = members.binary_search(investor) {
+                    members.insert(index, investor.clone());
+                }
+            }
+            <AppInvestorMembers<T>>::insert(app_id, members);
+        }
+
+        db.reads_writes(1 + 2 * apps, 1 + apps)
+    }
+
+    // experts sharing model cycle reward, creator takes what is left
+    pub fn model_expert_profit_rates(app_id: u32, model_id: &Vec<u8>) -> Vec<(T::AccountId, u32)> {
+        let key = T::Hashing::hash_of(&(app_id, model_id));
+        let creator = <ModelCreators<T>>::get(&key);
+
+        <ExpertMembers<T>>::get(&key)
+            .into_iter()
+            .filter(|member| *member != creator)
+            .map(|member| {
+                let profit_key = T::Hashing::hash_of(&(app_id, model_id, &member));
+                let rate = <ExpertMemberProfitRate<T>>::get(&profit_key);
+                (member, rate)
+            })
+            .filter(|(_, rate)| *rate > 0)
+            .collect()
+    }
+
     pub fn model_add_expert(key: &T::Hash, new_member: &T::AccountId) {
         let mut members = <ExpertMembers<T>>::get(key);
 
@@ -300,6 +453,12 @@ impl<T: Trait> Module<T> {
         <ModelCreators<T>>::get(&key)
     }
 
+    // members sets are sorted vec, weight grows with the decoded length
+    fn expert_members_len(app_id: u32, model_id: &Vec<u8>) -> u32 {
+        let key = T::Hashing::hash_of(&(app_id, model_id));
+        <ExpertMembers<T>>::decode_len(&key).unwrap_or_default() as u32
+    }
+
     pub fn is_finance_member(who: &T::AccountId) -> bool {
         <FinanceMembers<T>>::get().contains(who)
     }
@@ -376,8 +535,8 @@ impl<T: Trait> Membership<T::AccountId, T::Hash, BalanceOf<T>> for Module<T> {
         Self::is_app_admin(who, app_id)
     }
 
-    fn is_investor(who: &T::AccountId) -> bool {
-        Self::is_investor(who)
+    fn is_investor(who: &T::AccountId, app_id: u32) -> bool {
+        Self::is_investor(who, app_id)
     }
 
     fn is_finance_member(who: &T::AccountId) -> bool {
@@ -425,18 +584,28 @@ impl<T: Trait> Membership<T::AccountId, T::Hash, BalanceOf<T>> for Module<T> {
         Self::is_model_creator(who, app_id, model_id)
     }
 
+    fn model_expert_profit_rates(app_id: u32, model_id: &Vec<u8>) -> Vec<(T::AccountId, u32)> {
+        Self::model_expert_profit_rates(app_id, model_id)
+    }
+
     // only used for app register
     fn config_app_admin(who: &T::AccountId, app_id: u32) {
         let mut members = <AppAdmins<T>>::get(app_id);
-        members.push(who.clone());
-        <AppAdmins<T>>::insert(app_id, members);
+        // kept sorted for binary search
+        if let Err(index) = members.binary_search(who) {
+            members.insert(index, who.clone());
+            <AppAdmins<T>>::insert(app_id, members);
+        }
     }
 
     // only used for app register
     fn config_app_key(who: &T::AccountId, app_id: u32) {
         let mut members = <AppKeys<T>>::get(app_id);
-        members.push(who.clone());
-        <AppKeys<T>>::insert(app_id, members);
+        // kept sorted for binary search
+        if let Err(index) = members.binary_search(who) {
+            members.insert(index, who.clone());
+            <AppKeys<T>>::insert(app_id, members);
+        }
     }
 
     fn config_app_setting(app_id: u32, rate: u32, name: Vec<u8>, stake: BalanceOf<T>) {
@@ -455,8 +624,34 @@ impl<T: Trait> Membership<T::AccountId, T::Hash, BalanceOf<T>> for Module<T> {
         (setting.return_rate, setting.name, setting.stake)
     }
 
+    fn get_app_admins(app_id: u32) -> Vec<T::AccountId> {
+        <AppAdmins<T>>::get(app_id)
+    }
+
     fn is_valid_app(app_id: u32) -> bool {
-        <AppDataMap<T>>::contains_key(app_id)
+        <AppDataMap<T>>::contains_key(app_id) && !<AppSuspended>::get(app_id)
+    }
+
+    fn is_app_suspended(app_id: u32) -> bool {
+        <AppSuspended>::get(app_id)
+    }
+
+    fn set_app_suspended(app_id: u32, suspended: bool) {
+        if suspended {
+            <AppSuspended>::insert(app_id, true);
+        } else {
+            <AppSuspended>::remove(app_id);
+        }
+    }
+
+    fn remove_app(app_id: u32) -> Vec<T::AccountId> {
+        <AppKeys<T>>::remove(app_id);
+        <AppKeyThreshold>::remove(app_id);
+        <AppDataMap<T>>::remove(app_id);
+        <AppPlatformExpertMembers<T>>::remove(app_id);
+        <AppInvestorMembers<T>>::remove(app_id);
+        <AppSuspended>::remove(app_id);
+        <AppAdmins<T>>::take(app_id)
     }
 
     fn is_valid_app_key(app_id: u32, app_key: &T::AccountId) -> bool {
@@ -467,6 +662,18 @@ impl<T: Trait> Membership<T::AccountId, T::Hash, BalanceOf<T>> for Module<T> {
         Self::valid_finance_members()
     }
 
+    fn app_key_threshold(app_id: u32) -> u32 {
+        Self::app_key_threshold(app_id).max(1)
+    }
+
+    fn sign_nonce(who: &AuthAccountId) -> u64 {
+        Self::sign_nonce(who)
+    }
+
+    fn inc_sign_nonce(who: &AuthAccountId) {
+        Self::inc_sign_nonce(who)
+    }
+
     fn slash_finance_member(
         member: &T::AccountId,
         receiver: &T::AccountId,
@@ -474,6 +681,34 @@ impl<T: Trait> Membership<T::AccountId, T::Hash, BalanceOf<T>> for Module<T> {
     ) -> DispatchResult {
         Self::slash_finance_member(member, receiver, amount)
     }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn add_finance_member(who: &T::AccountId, deposit: BalanceOf<T>) {
+        let mut members = <FinanceMembers<T>>::get();
+        if let Err(index) = members.binary_search(who) {
+            members.insert(index, who.clone());
+            <FinanceMembers<T>>::put(members);
+        }
+        let _ = T::Currency::reserve(who, deposit);
+        <FinanceMemberDeposit<T>>::insert(who, deposit);
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn add_investor(who: &T::AccountId, app_id: u32) {
+        let mut members = <AppInvestorMembers<T>>::get(app_id);
+        if let Err(index) = members.binary_search(who) {
+            members.insert(index, who.clone());
+            <AppInvestorMembers<T>>::insert(app_id, members);
+        }
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn add_model_expert(who: &T::AccountId, app_id: u32, model_id: &Vec<u8>, rate: u32) {
+        let key = T::Hashing::hash_of(&(app_id, model_id));
+        Self::model_add_expert(&key, who);
+        let profit_key = T::Hashing::hash_of(&(app_id, model_id, who));
+        <ExpertMemberProfitRate<T>>::insert(&profit_key, rate);
+    }
 }
 
 decl_module! {
@@ -482,7 +717,18 @@ decl_module! {
 
         type Error = Error<T>;
 
-        #[weight = 0]
+        fn on_runtime_upgrade() -> Weight {
+            let mut weight = T::DbWeight::get().reads_writes(1, 1);
+
+            if StorageVersion::get() == Releases::V1_0_0 {
+                weight = weight.saturating_add(Self::migrate_investor_members());
+                StorageVersion::put(Releases::V2_0_0);
+            }
+
+            weight
+        }
+
+        #[weight = T::WeightInfo::add_investor_member(<AppInvestorMembers<T>>::decode_len(app_id).unwrap_or_default() as u32)]
         pub fn add_investor_member(origin, app_id: u32, new_member: T::AccountId) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
@@ -491,7 +737,7 @@ decl_module! {
             // check if who is app admin
             ensure!(Self::is_app_admin(&who, app_id), Error::<T>::NotAppAdmin);
 
-            let mut members = InvestorMembers::<T>::get();
+            let mut members = <AppInvestorMembers<T>>::get(app_id);
             //ensure!(members.len() < MAX_MEMBERS, Error::<T>::MembershipLimitReached);
 
             // We don't want to add duplicate members, so we check whether the potential new
@@ -504,15 +750,15 @@ decl_module! {
                 // they should be inserted
                 Err(index) => {
                     members.insert(index, new_member.clone());
-                    InvestorMembers::<T>::put(members);
-                    Self::deposit_event(RawEvent::MemberAdded(new_member));
+                    <AppInvestorMembers<T>>::insert(app_id, members);
+                    Self::deposit_event(RawEvent::InvestorMemberAdded(app_id, new_member));
                     Ok(())
                 }
             }
         }
 
         /// Removes a member.
-        #[weight = 0]
+        #[weight = T::WeightInfo::remove_investor_member(<AppInvestorMembers<T>>::decode_len(app_id).unwrap_or_default() as u32)]
         pub fn remove_investor_member(origin, app_id: u32, old_member: T::AccountId) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
@@ -520,15 +766,15 @@ decl_module! {
             // check if who is app admin
             ensure!(Self::is_app_admin(&who, app_id), Error::<T>::NotAppAdmin);
 
-            let mut members = InvestorMembers::<T>::get();
+            let mut members = <AppInvestorMembers<T>>::get(app_id);
 
             // We have to find out if the member exists in the sorted vec, and, if so, where.
             match members.binary_search(&old_member) {
                 // If the search succeeds, the caller is a member, so remove her
                 Ok(index) => {
                     members.remove(index);
-                    InvestorMembers::<T>::put(members);
-                    Self::deposit_event(RawEvent::MemberRemoved(old_member));
+                    <AppInvestorMembers<T>>::insert(app_id, members);
+                    Self::deposit_event(RawEvent::InvestorMemberRemoved(app_id, old_member));
                     Ok(())
                 },
                 // If the search fails, the caller is not a member, so just return
@@ -536,14 +782,15 @@ decl_module! {
             }
         }
 
-        #[weight = 0]
-        pub fn add_finance_member(origin, params: FinanceMemberParams<T::AccountId, BalanceOf<T>>, app_user_account: AuthAccountId, app_user_sign: sr25519::Signature) -> DispatchResult {
+        #[weight = T::WeightInfo::add_finance_member(<FinanceMembers<T>>::decode_len().unwrap_or_default() as u32)]
+        pub fn add_finance_member(origin, params: FinanceMemberParams<T::AccountId, BalanceOf<T>>, envelope: SignEnvelope<T::BlockNumber>, app_user_account: AuthAccountId, app_user_sign: AuthSignature) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
             ensure!(Self::is_finance_root(&who), Error::<T>::CallerNotFinanceRoot);
 
-            let buf = params.encode();
+            let buf = Self::sign_envelope_message(b"members/add_finance_member", &app_user_account, &envelope, &params.encode())?;
             ensure!(Self::verify_sign(&app_user_account, app_user_sign, &buf), Error::<T>::SignVerifyError);
+            Self::inc_sign_nonce(&app_user_account);
 
             let FinanceMemberParams {
                 deposit,
@@ -568,14 +815,14 @@ decl_module! {
 
                     members.insert(index, member.clone());
                     FinanceMembers::<T>::put(members);
-                    Self::deposit_event(RawEvent::MemberAdded(member));
+                    Self::deposit_event(RawEvent::FinanceMemberAdded(member, deposit));
                     Ok(())
                 }
             }
         }
 
         /// Removes a member.
-        #[weight = 0]
+        #[weight = T::WeightInfo::remove_finance_member(<FinanceMembers<T>>::decode_len().unwrap_or_default() as u32)]
         pub fn remove_finance_member(origin, old_member: T::AccountId) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
@@ -593,7 +840,7 @@ decl_module! {
 
                     members.remove(index);
                     FinanceMembers::<T>::put(members);
-                    Self::deposit_event(RawEvent::MemberRemoved(old_member));
+                    Self::deposit_event(RawEvent::FinanceMemberRemoved(old_member, deposit));
                     Ok(())
                 },
                 // If the search fails, the caller is not a member, so just return
@@ -601,7 +848,7 @@ decl_module! {
             }
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::finance_member_add_deposit()]
         pub fn finance_member_add_deposit(origin, deposit: BalanceOf<T>) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
@@ -609,16 +856,17 @@ decl_module! {
 
             T::Currency::reserve(&who, deposit)?;
 
-            <FinanceMemberDeposit<T>>::mutate(&who, |org_deposit| {
+            let total = <FinanceMemberDeposit<T>>::mutate(&who, |org_deposit| {
                 *org_deposit += deposit;
+                *org_deposit
             });
 
-            Self::deposit_event(RawEvent::FinanceMemberDeposit(who));
+            Self::deposit_event(RawEvent::FinanceMemberDeposit(who, deposit, total));
             Ok(())
         }
 
-        #[weight = 0]
-        pub fn add_app_admin(origin, params: AppKeyManageParams<T::AccountId>, sign: sr25519::Signature) -> DispatchResult {
+        #[weight = T::WeightInfo::add_app_admin(<AppAdmins<T>>::decode_len(params.app_id).unwrap_or_default() as u32)]
+        pub fn add_app_admin(origin, params: AppKeyManageParams<T::AccountId>, envelope: SignEnvelope<T::BlockNumber>, sign: AuthSignature) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
             let sign_buf = params.encode();
@@ -639,7 +887,9 @@ decl_module! {
             // check max length
             ensure!(members.len() < MAX_APP_KEYS, Error::<T>::AppKeysLimitReached);
             // check sign
+            let sign_buf = Self::sign_envelope_message(b"members/add_app_admin", &admin, &envelope, &sign_buf)?;
             ensure!(Self::verify_sign(&admin, sign, &sign_buf), Error::<T>::SignVerifyError);
+            Self::inc_sign_nonce(&admin);
             // all pass now add
             match members.binary_search(&member) {
                 // If the search succeeds, the caller is already a member, so just return
@@ -649,14 +899,14 @@ decl_module! {
                 Err(index) => {
                     members.insert(index, member.clone());
                     <AppAdmins<T>>::insert(app_id, members);
-                    Self::deposit_event(RawEvent::AppAdminSet(member));
+                    Self::deposit_event(RawEvent::AppAdminSet(app_id, member));
                     Ok(())
                 }
             }
         }
 
-         #[weight = 0]
-        pub fn remove_app_admin(origin, params: AppKeyManageParams<T::AccountId>, sign: sr25519::Signature) -> DispatchResult {
+         #[weight = T::WeightInfo::remove_app_admin(<AppAdmins<T>>::decode_len(params.app_id).unwrap_or_default() as u32)]
+        pub fn remove_app_admin(origin, params: AppKeyManageParams<T::AccountId>, envelope: SignEnvelope<T::BlockNumber>, sign: AuthSignature) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
             let sign_buf = params.encode();
@@ -677,14 +927,16 @@ decl_module! {
             // check max length
             ensure!(members.len() > 1, Error::<T>::AppKeysOnlyOne);
             // check sign
+            let sign_buf = Self::sign_envelope_message(b"members/remove_app_admin", &admin, &envelope, &sign_buf)?;
             ensure!(Self::verify_sign(&admin, sign, &sign_buf), Error::<T>::SignVerifyError);
+            Self::inc_sign_nonce(&admin);
             // all pass now add
             match members.binary_search(&member) {
                 // If the search succeeds, the caller is already a member, so just return
                 Ok(index) => {
                     members.remove(index);
                     <AppAdmins<T>>::insert(app_id, members);
-                    Self::deposit_event(RawEvent::MemberRemoved(member));
+                    Self::deposit_event(RawEvent::AppAdminRemoved(app_id, member));
                     Ok(())
                 },
                 // If the search fails, the caller is not a member, so just return
@@ -692,8 +944,8 @@ decl_module! {
             }
         }
 
-        #[weight = 0]
-        pub fn add_app_key(origin, params: AppKeyManageParams<T::AccountId>, sign: sr25519::Signature) -> DispatchResult {
+        #[weight = T::WeightInfo::add_app_key(<AppKeys<T>>::decode_len(params.app_id).unwrap_or_default() as u32)]
+        pub fn add_app_key(origin, params: AppKeyManageParams<T::AccountId>, envelope: SignEnvelope<T::BlockNumber>, sign: AuthSignature) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
             let sign_buf = params.encode();
@@ -714,7 +966,9 @@ decl_module! {
             // check max length
             ensure!(members.len() < MAX_APP_KEYS, Error::<T>::AppKeysLimitReached);
             // check sign
+            let sign_buf = Self::sign_envelope_message(b"members/add_app_key", &admin, &envelope, &sign_buf)?;
             ensure!(Self::verify_sign(&admin, sign, &sign_buf), Error::<T>::SignVerifyError);
+            Self::inc_sign_nonce(&admin);
             // all pass now add
             match members.binary_search(&member) {
                 // If the search succeeds, the caller is already a member, so just return
@@ -724,14 +978,14 @@ decl_module! {
                 Err(index) => {
                     members.insert(index, member.clone());
                     <AppKeys<T>>::insert(app_id, members);
-                    Self::deposit_event(RawEvent::AppKeysSet(member));
+                    Self::deposit_event(RawEvent::AppKeysSet(app_id, member));
                     Ok(())
                 }
             }
         }
 
-         #[weight = 0]
-        pub fn remove_app_key(origin, params: AppKeyManageParams<T::AccountId>, sign: sr25519::Signature) -> DispatchResult {
+         #[weight = T::WeightInfo::remove_app_key(<AppKeys<T>>::decode_len(params.app_id).unwrap_or_default() as u32)]
+        pub fn remove_app_key(origin, params: AppKeyManageParams<T::AccountId>, envelope: SignEnvelope<T::BlockNumber>, sign: AuthSignature) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
             let sign_buf = params.encode();
@@ -751,15 +1005,19 @@ decl_module! {
             let mut members = <AppKeys<T>>::get(app_id);
             // check max length
             ensure!(members.len() > 1, Error::<T>::AppKeysOnlyOne);
+            // remaining keys must still reach the threshold
+            ensure!(members.len() as u32 > Self::app_key_threshold(app_id), Error::<T>::AppKeyThresholdInvalid);
             // check sign
+            let sign_buf = Self::sign_envelope_message(b"members/remove_app_key", &admin, &envelope, &sign_buf)?;
             ensure!(Self::verify_sign(&admin, sign, &sign_buf), Error::<T>::SignVerifyError);
+            Self::inc_sign_nonce(&admin);
             // all pass now add
             match members.binary_search(&member) {
                 // If the search succeeds, the caller is already a member, so just return
                 Ok(index) => {
                     members.remove(index);
                     <AppKeys<T>>::insert(app_id, members);
-                    Self::deposit_event(RawEvent::MemberRemoved(member));
+                    Self::deposit_event(RawEvent::AppKeyRemoved(app_id, member));
                     Ok(())
                 },
                 // If the search fails, the caller is not a member, so just return
@@ -767,7 +1025,7 @@ decl_module! {
             }
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::add_app_platform_expert_member(<AppPlatformExpertMembers<T>>::decode_len(*app_id).unwrap_or_default() as u32)]
         pub fn add_app_platform_expert_member(origin, app_id: u32, new_member: T::AccountId) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
@@ -786,13 +1044,13 @@ decl_module! {
                 Err(index) => {
                     members.insert(index, new_member.clone());
                     <AppPlatformExpertMembers<T>>::insert(app_id, members);
-                    Self::deposit_event(RawEvent::MemberAdded(new_member));
+                    Self::deposit_event(RawEvent::AppPlatformExpertAdded(app_id, new_member));
                     Ok(())
                 }
             }
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::remove_app_platform_expert_member(<AppPlatformExpertMembers<T>>::decode_len(*app_id).unwrap_or_default() as u32)]
         pub fn remove_app_platform_expert_member(origin, app_id: u32, old_member: T::AccountId) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
@@ -808,7 +1066,7 @@ decl_module! {
                 Ok(index) => {
                     members.remove(index);
                     <AppPlatformExpertMembers<T>>::insert(app_id, members);
-                    Self::deposit_event(RawEvent::MemberRemoved(old_member));
+                    Self::deposit_event(RawEvent::AppPlatformExpertRemoved(app_id, old_member));
                     Ok(())
                 },
                 // If the search fails, the caller is not a member, so just return
@@ -816,11 +1074,13 @@ decl_module! {
             }
         }
 
-        #[weight = 0]
-        pub fn add_expert_member(origin, params: ModelExpertAddMemberParams, model_creator: AuthAccountId, model_creator_sign: sr25519::Signature) -> DispatchResult {
+        #[weight = T::WeightInfo::add_expert_member(<Module<T>>::expert_members_len(params.app_id, &params.model_id))]
+        pub fn add_expert_member(origin, params: ModelExpertAddMemberParams, envelope: SignEnvelope<T::BlockNumber>, model_creator: AuthAccountId, model_creator_sign: AuthSignature) -> DispatchResult {
             let new_member = ensure_signed(origin)?;
 
-            ensure!(Self::verify_sign(&model_creator, model_creator_sign, &params.encode()), Error::<T>::SignVerifyError);
+            let buf = Self::sign_envelope_message(b"members/add_expert_member", &model_creator, &envelope, &params.encode())?;
+            ensure!(Self::verify_sign(&model_creator, model_creator_sign, &buf), Error::<T>::SignVerifyError);
+            Self::inc_sign_nonce(&model_creator);
 
             let ModelExpertAddMemberParams {
                 app_id,
@@ -833,9 +1093,16 @@ decl_module! {
             // check if model creator valid
             ensure!(Self::is_model_creator(&Self::convert_account(&model_creator), app_id, &model_id), Error::<T>::NotModelCreator);
 
+            // experts together take at most the whole model reward
+            let rate_total = Self::model_expert_profit_rates(app_id, &model_id)
+                .iter()
+                .fold(kpt_profit_rate, |total, (_, rate)| total.saturating_add(*rate));
+            ensure!(rate_total <= 100, Error::<T>::ProfitRateInvalid);
+
             let key = T::Hashing::hash_of(&(app_id, &model_id));
 
             let mut members = <ExpertMembers<T>>::get(&key);
+            ensure!((members.len() as u32) < T::MaxModelExperts::get(), Error::<T>::ExpertMemberSizeOver);
 
             match members.binary_search(&new_member) {
                 // If the search succeeds, the caller is already a member, so just return
@@ -850,27 +1117,29 @@ decl_module! {
                     let profit_key = T::Hashing::hash_of(&(app_id, &model_id, &new_member));
                     <ExpertMemberProfitRate<T>>::insert(&profit_key, kpt_profit_rate);
 
-                    Self::deposit_event(RawEvent::MemberAdded(new_member));
+                    Self::deposit_event(RawEvent::ModelExpertAdded(app_id, model_id, new_member, kpt_profit_rate));
                     Ok(())
                 }
             }
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::remove_expert_member(<Module<T>>::expert_members_len(params.app_id, &params.model_id))]
         pub fn remove_expert_member(origin,
             params: ModelExpertDelMemberParams<T::AccountId>,
+            envelope: SignEnvelope<T::BlockNumber>,
             app_user_account: AuthAccountId,
-            app_user_sign: sr25519::Signature,
+            app_user_sign: AuthSignature,
 
             auth_server: AuthAccountId,
-            auth_sign: sr25519::Signature) -> DispatchResult {
+            auth_sign: AuthSignature) -> DispatchResult {
 
             // this is app server account
             let _who = ensure_signed(origin)?;
 
-            let buf = params.encode();
+            let buf = Self::sign_envelope_message(b"members/remove_expert_member", &app_user_account, &envelope, &params.encode())?;
             ensure!(Self::verify_sign(&app_user_account, app_user_sign, &buf), Error::<T>::SignVerifyError);
             ensure!(Self::verify_sign(&auth_server, auth_sign, &buf), Error::<T>::SignVerifyError);
+            Self::inc_sign_nonce(&app_user_account);
 
             let ModelExpertDelMemberParams {
                 app_id,
@@ -894,7 +1163,8 @@ decl_module! {
                 Ok(index) => {
                     members.remove(index);
                     <ExpertMembers<T>>::insert(&key, members);
-                    Self::deposit_event(RawEvent::MemberRemoved(member));
+                    <ExpertMemberProfitRate<T>>::remove(T::Hashing::hash_of(&(app_id, &model_id, &member)));
+                    Self::deposit_event(RawEvent::ModelExpertRemoved(app_id, model_id, member));
                     Ok(())
                 },
                 // If the search fails, the caller is not a member, so just return
@@ -902,7 +1172,7 @@ decl_module! {
             }
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::air_drop_new_user_benefit()]
         pub fn air_drop_new_user_benefit(origin, app_id: u32, user_id: Vec<u8>,
             receiver: <T as frame_system::Trait>::AccountId, amount: BalanceOf<T>) -> DispatchResult {
 
@@ -924,11 +1194,11 @@ decl_module! {
             // record it
             <NewAccountBenefitRecords<T>>::insert(&key, amount);
 
-            Self::deposit_event(RawEvent::NewUserBenefitDrpped(receiver, amount));
+            Self::deposit_event(RawEvent::NewUserBenefitDrpped(app_id, user_id, receiver, amount));
             Ok(())
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::stable_exchange()]
         pub fn stable_exchange(origin, amount: BalanceOf<T>, receiver: T::AccountId, app_id: u32, cash_receipt: Vec<u8>) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
@@ -948,11 +1218,11 @@ decl_module! {
                 redeemed: false,
             });
 
-            Self::deposit_event(RawEvent::StableExchanged(receiver));
+            Self::deposit_event(RawEvent::StableExchanged(who, receiver, app_id, cash_receipt, amount));
             Ok(())
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::stable_redeem()]
         pub fn stable_redeem(origin, app_id: u32, cash_receipt: Vec<u8>) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
@@ -976,11 +1246,11 @@ decl_module! {
             record.redeemed = true;
             <StableExchangeRecords<T>>::insert(&key, &record);
 
-            Self::deposit_event(RawEvent::AppRedeemed(who, receiver, record.amount));
+            Self::deposit_event(RawEvent::AppRedeemed(who, receiver, app_id, cash_receipt, record.amount));
             Ok(())
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::set_app_redeem_account()]
         pub fn set_app_redeem_account(origin, app_id: u32, account: T::AccountId) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
@@ -991,7 +1261,25 @@ decl_module! {
 
             <AppRedeemAccount<T>>::insert(app_id, &account);
 
-            Self::deposit_event(RawEvent::AppRedeemAccountSet(account));
+            Self::deposit_event(RawEvent::AppRedeemAccountSet(app_id, account));
+            Ok(())
+        }
+
+        #[weight = T::WeightInfo::set_app_key_threshold()]
+        pub fn set_app_key_threshold(origin, app_id: u32, threshold: u32) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(Self::is_valid_app(app_id), Error::<T>::AppIdInvalid);
+
+            // check if origin is app_id's admin
+            ensure!(Self::is_app_admin(&who, app_id),  Error::<T>::NotAppAdmin);
+
+            let keys_len = <AppKeys<T>>::decode_len(app_id).unwrap_or_default() as u32;
+            ensure!(threshold > 0 && threshold <= keys_len, Error::<T>::AppKeyThresholdInvalid);
+
+            <AppKeyThreshold>::insert(app_id, threshold);
+
+            Self::deposit_event(RawEvent::AppKeyThresholdSet(app_id, threshold));
             Ok(())
         }
     }
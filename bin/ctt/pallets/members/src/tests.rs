@@ -1,5 +1,7 @@
 use crate::*;
-use frame_support::{assert_ok, impl_outer_event, impl_outer_origin, parameter_types};
+use frame_support::{
+    assert_ok, impl_outer_event, impl_outer_origin, parameter_types, traits::OnRuntimeUpgrade,
+};
 use frame_system::{self as system, RawOrigin};
 use sp_core::H256;
 use sp_io::TestExternalities;
@@ -23,6 +25,7 @@ parameter_types! {
     pub const AvailableBlockRatio: Perbill = Perbill::one();
     pub const MaxFinanceMembers: u32 = 0;
     pub const MinFinanceMemberDeposit: u64 = 0;
+    pub const MaxModelExperts: u32 = 100;
 }
 impl system::Trait for TestRuntime {
     type Origin = Origin;
@@ -81,6 +84,8 @@ impl Trait for TestRuntime {
     type ModTreasuryModuleId = ();
     type MaxFinanceMembers = MaxFinanceMembers;
     type MinFinanceMemberDeposit = MinFinanceMemberDeposit;
+    type MaxModelExperts = MaxModelExperts;
+    type WeightInfo = ();
 }
 
 pub type System = system::Module<TestRuntime>;
@@ -111,3 +116,21 @@ fn add_member_works() {
         assert_eq!(Members::council_members(), vec![1]);
     })
 }
+
+#[test]
+fn investor_members_scoped_per_app_after_upgrade() {
+    ExtBuilder::build().execute_with(|| {
+        // global investors of storage version V1_0_0
+        StorageVersion::put(Releases::V1_0_0);
+        <InvestorMembers<TestRuntime>>::put(vec![3, 5]);
+        Members::config_app_setting(1, 0, b"one".to_vec(), 0);
+        Members::config_app_setting(2, 0, b"two".to_vec(), 0);
+        <AppInvestorMembers<TestRuntime>>::insert(2, vec![4]);
+
+        Members::on_runtime_upgrade();
+        assert_eq!(StorageVersion::get(), Releases::V2_0_0);
+        assert!(Members::investor_members().is_empty());
+        assert_eq!(Members::app_investor_members(1), vec![3, 5]);
+        assert_eq!(Members::app_investor_members(2), vec![3, 4, 5]);
+    })
+}
@@ -19,13 +19,14 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use codec::{Decode, Encode, EncodeLike, Input, Output};
 use sp_std::collections::btree_set::BTreeSet;
 use sp_std::prelude::*;
 
 use sp_runtime::{
     generic,
-    traits::{BlakeTwo256, IdentifyAccount, Verify},
-    MultiSignature, OpaqueExtrinsic,
+    traits::{BlakeTwo256, IdentifyAccount, Verify, Zero},
+    MultiSignature, OpaqueExtrinsic, RuntimeDebug,
 };
 
 use frame_support::dispatch::DispatchResult;
@@ -105,6 +106,63 @@ pub mod report {
 
 /// Used for outside interface
 pub type AuthAccountId = <<MultiSignature as Verify>::Signer as IdentifyAccount>::AccountId;
+
+/// Signature of app users and auth servers in kp and members calls.
+///
+/// Encodes as `MultiSignature`. Decodes a `MultiSignature`, or the bare 64 bytes sr25519
+/// signature clients sent before other schemes were accepted. Sr25519 signatures start with an
+/// even byte, so a bare one starting with 0x00 or 0x02 reads as another variant and fails to
+/// verify, such clients retry with a fresh signature until they send the `Sr25519` variant.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct AuthSignature(pub MultiSignature);
+
+impl AuthSignature {
+    pub fn verify(&self, msg: &[u8], signer: &AuthAccountId) -> bool {
+        self.0.verify(msg, signer)
+    }
+}
+
+impl From<MultiSignature> for AuthSignature {
+    fn from(sign: MultiSignature) -> Self {
+        AuthSignature(sign)
+    }
+}
+
+impl From<sp_core::sr25519::Signature> for AuthSignature {
+    fn from(sign: sp_core::sr25519::Signature) -> Self {
+        AuthSignature(sign.into())
+    }
+}
+
+impl Encode for AuthSignature {
+    fn size_hint(&self) -> usize {
+        self.0.size_hint()
+    }
+
+    fn encode_to<W: Output>(&self, dest: &mut W) {
+        self.0.encode_to(dest)
+    }
+}
+
+impl EncodeLike for AuthSignature {}
+
+impl Decode for AuthSignature {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+        let first = input.read_byte()?;
+        let sign = match first {
+            0 => MultiSignature::Ed25519(Decode::decode(input)?),
+            1 => MultiSignature::Sr25519(Decode::decode(input)?),
+            2 => MultiSignature::Ecdsa(Decode::decode(input)?),
+            _ => {
+                let mut raw = [0u8; 64];
+                raw[0] = first;
+                input.read(&mut raw[1..])?;
+                MultiSignature::Sr25519(sp_core::sr25519::Signature::from_raw(raw))
+            }
+        };
+        Ok(AuthSignature(sign))
+    }
+}
 /// Types that implement the AccountSet trait are able to supply a set of accounts
 /// The trait is generic over the notion of Account used.
 pub trait AccountSet {
@@ -113,27 +171,100 @@ pub trait AccountSet {
     fn accounts() -> BTreeSet<Self::AccountId>;
 }
 
+/// Replay protection of an app user / auth server signed payload.
+/// `nonce` is the current sign nonce of the primary signer (app user, admin or model creator),
+/// `expiry` the last block the payload can be submitted at.
+/// Nonces are strictly sequential without a window: only the current nonce is accepted and it
+/// increases by one on each accepted payload, so a signer submits payloads one at a time in order
+/// and re-signs a payload that lost its turn.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct SignEnvelope<BlockNumber> {
+    pub nonce: u64,
+    pub expiry: BlockNumber,
+}
+
+impl<BlockNumber: Encode> SignEnvelope<BlockNumber> {
+    /// The message actually signed, scoped by call tag and chain genesis hash
+    pub fn message<Hash: Encode>(
+        &self,
+        tag: &[u8],
+        genesis_hash: &Hash,
+        payload: &[u8],
+    ) -> Vec<u8> {
+        (tag, genesis_hash, self.nonce, &self.expiry, payload).encode()
+    }
+}
+
+/// Why a sign envelope is refused
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum SignEnvelopeError {
+    /// Current block is after envelope expiry
+    Expired,
+    /// Envelope nonce is not the current sign nonce of the signer
+    NonceInvalid,
+}
+
+/// Check envelope of the primary signer, whose current sign nonce is `signer_nonce`,
+/// returns the message all signers signed
+pub fn sign_envelope_message<T: frame_system::Trait>(
+    tag: &[u8],
+    signer_nonce: u64,
+    envelope: &SignEnvelope<T::BlockNumber>,
+    payload: &[u8],
+) -> Result<Vec<u8>, SignEnvelopeError> {
+    if <frame_system::Module<T>>::block_number() > envelope.expiry {
+        return Err(SignEnvelopeError::Expired);
+    }
+    if envelope.nonce != signer_nonce {
+        return Err(SignEnvelopeError::NonceInvalid);
+    }
+
+    let genesis_hash = <frame_system::Module<T>>::block_hash(T::BlockNumber::zero());
+    Ok(envelope.message(tag, &genesis_hash, payload))
+}
+
 pub trait Membership<AccountId, Hash, Balance> {
     fn is_platform(who: &AccountId, app_id: u32) -> bool;
     fn is_expert(who: &AccountId, app_id: u32, model_id: &Vec<u8>) -> bool;
     fn is_app_admin(who: &AccountId, app_id: u32) -> bool;
-    fn is_investor(who: &AccountId) -> bool;
+    fn is_investor(who: &AccountId, app_id: u32) -> bool;
     fn is_finance_member(who: &AccountId) -> bool;
     fn set_model_creator(key: &Hash, creator: &AccountId, is_give_benefit: bool) -> Balance;
     fn transfer_model_owner(key: &Hash, new_owner: &AccountId);
     fn is_model_creator(who: &AccountId, app_id: u32, model_id: &Vec<u8>) -> bool;
+    /// Experts sharing model cycle reward with their profit rate (percent), creator excluded.
+    fn model_expert_profit_rates(app_id: u32, model_id: &Vec<u8>) -> Vec<(AccountId, u32)>;
     fn config_app_admin(who: &AccountId, app_id: u32);
     fn config_app_key(who: &AccountId, app_id: u32);
     fn config_app_setting(app_id: u32, rate: u32, name: Vec<u8>, stake: Balance);
     fn get_app_setting(app_id: u32) -> (u32, Vec<u8>, Balance);
+    fn get_app_admins(app_id: u32) -> Vec<AccountId>;
+    /// Registered and not suspended.
     fn is_valid_app(app_id: u32) -> bool;
+    fn is_app_suspended(app_id: u32) -> bool;
+    fn set_app_suspended(app_id: u32, suspended: bool);
+    /// Remove app admins, keys and settings, returns the removed admins.
+    fn remove_app(app_id: u32) -> Vec<AccountId>;
     fn is_valid_app_key(app_id: u32, app_key: &AccountId) -> bool;
+    fn app_key_threshold(app_id: u32) -> u32;
     fn valid_finance_members() -> Vec<AccountId>;
+    fn sign_nonce(who: &AuthAccountId) -> u64;
+    fn inc_sign_nonce(who: &AuthAccountId);
     fn slash_finance_member(
         member: &AccountId,
         receiver: &AccountId,
         amount: Balance,
     ) -> DispatchResult;
+
+    /// Benchmark setup only: register a finance member with its deposit.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn add_finance_member(who: &AccountId, deposit: Balance);
+    /// Benchmark setup only: register an investor of the app.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn add_investor(who: &AccountId, app_id: u32);
+    /// Benchmark setup only: register a model expert with its profit rate.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn add_model_expert(who: &AccountId, app_id: u32, model_id: &Vec<u8>, rate: u32);
 }
 
 // For RPC return data types
@@ -38,7 +38,9 @@ use frame_support::{
 use frame_system::{EnsureOneOf, EnsureRoot};
 use kp::{
     AppFinancedData, AppFinancedUserExchangeData, AppIncomeCycleRecord, CommoditySlashRecord,
-    DocumentPowerInfo, LeaderBoardResult, ModelDisputeRecord, ModelIncomeCurrentStage,
+    DocumentPowerInfo, ExchangeStatus, LeaderBoardHistoryItem, LeaderBoardHistoryPage,
+    LeaderBoardResult, LeaderBoardRewardInfo, ModelCycleIncomeReward, ModelDisputeRecord,
+    ModelIncomeCurrentStage,
 };
 pub use node_primitives::{AccountId, AuthAccountId, PowerSize, Signature};
 use node_primitives::{AccountIndex, Balance, BlockNumber, Hash, Index, Moment};
@@ -118,10 +120,11 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     // and set impl_version to 0. If only runtime
     // implementation changes and behavior does not, then leave spec_version as
     // is and increment impl_version.
-    spec_version: 259,
+    spec_version: 260,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 1,
+    // kp and members calls take signing envelopes and MultiSignature encoded signatures since 2
+    transaction_version: 2,
 };
 
 /// Native version.
@@ -657,6 +660,7 @@ parameter_types! {
     pub const TreasuryTechModuleId: ModuleId = ModuleId(*b"py/trtch");
     pub const TreasuryModModuleId: ModuleId = ModuleId(*b"py/trmod");
     pub const AccountModModuleId: ModuleId = ModuleId(*b"py/acmod");
+    pub const LeaderBoardRewardModuleId: ModuleId = ModuleId(*b"py/ldbrw");
     pub const BountyUpdatePeriod: BlockNumber = 14 * DAYS;
     pub const MaximumReasonLength: u32 = 16384;
     pub const BountyCuratorDeposit: Permill = Permill::from_percent(50);
@@ -1035,6 +1039,8 @@ parameter_types! {
     pub const ModelCreatorCreateBenefit: Balance = 2000 * DOLLARS;
     pub const MaxFinanceMembers: u32 = 64;
     pub const MinFinanceMemberDeposit: Balance = 10000 * DOLLARS;
+    // creator included, experts share at most the whole model reward
+    pub const MaxModelExperts: u32 = 128;
 }
 
 impl members::Trait for Runtime {
@@ -1044,6 +1050,8 @@ impl members::Trait for Runtime {
     type ModTreasuryModuleId = AccountModModuleId;
     type MaxFinanceMembers = MaxFinanceMembers;
     type MinFinanceMemberDeposit = MinFinanceMemberDeposit;
+    type MaxModelExperts = MaxModelExperts;
+    type WeightInfo = weights::members::WeightInfo;
 }
 
 type EnsureRootOrHalfTech = EnsureOneOf<
@@ -1100,6 +1108,8 @@ parameter_types! {
     pub const CommentCMPowerWeightPositive: u8 = 5;
 
     pub const ModelCreateDeposit: Balance = 100 * DOLLARS;
+    // Reward total, leader board interval, finance exchange period, model income cycle and
+    // dispute delay are genesis values only, governance tunes them by democracy_set_economic_params
     //pub const ModelCycleIncomeRewardTotal: Balance = 10_000_000 * DOLLARS;
     // TODO: test
     pub const ModelCycleIncomeRewardTotal: Balance = 10000 * DOLLARS;
@@ -1109,6 +1119,13 @@ parameter_types! {
     //pub const AppLeaderBoardInterval: BlockNumber = 1 * DAYS;
     pub const AppLeaderBoardInterval: BlockNumber = 5 * MINUTES;
     pub const AppLeaderBoardMaxPos: u32 = 96;
+    pub const AppLeaderBoardSnapshotsPerBlock: u32 = 2;
+    pub const SubmitBatchMax: u32 = 100;
+    pub const DocumentCommentPoolMax: u32 = 500;
+    pub const UseVrfRandomness: bool = true;
+    pub const LeaderBoardReward: Balance = 1000 * DOLLARS;
+    //pub const LeaderBoardRewardClaimPeriod: BlockNumber = 7 * DAYS;
+    pub const LeaderBoardRewardClaimPeriod: BlockNumber = 30 * MINUTES; // TODO: Test only
     //pub const AppFinanceExchangePeriod: BlockNumber = 28 * DAYS;
     pub const AppFinanceExchangePeriod: BlockNumber = 30 * MINUTES; // TODO: Test only
 
@@ -1119,6 +1136,7 @@ parameter_types! {
     pub const ModelIncomeCyclePeriod: BlockNumber = 30 * MINUTES;
     pub const ModelIncomeCollectingPeriod: BlockNumber = 10 * MINUTES;
     pub const ModelIncomeRewardingPeriod: BlockNumber = 10 * MINUTES;
+    pub const ModelIncomeParamsMax: u32 = 100;
 
     pub const ModelDisputeCycleCount: u32 = 10;
     pub const ModelDisputeCycleLv2IncreaseCount: u32 = 3;
@@ -1132,14 +1150,40 @@ parameter_types! {
     //pub const ModelDisputeDelayTime: BlockNumber = 28 * DAYS;
     // TODO: test
     pub const ModelDisputeDelayTime: BlockNumber = 10 * MINUTES;
+    //pub const ModelRetireCoolDown: BlockNumber = 28 * DAYS;
+    pub const ModelRetireCoolDown: BlockNumber = 10 * MINUTES; // TODO: Test only
+    pub const ModelDisputeBond: Balance = 20 * DOLLARS;
+    //pub const ModelDisputeEvidencePeriod: BlockNumber = 3 * DAYS;
+    pub const ModelDisputeEvidencePeriod: BlockNumber = 10 * MINUTES; // TODO: Test only
+    //pub const ModelDisputeVotePeriod: BlockNumber = 7 * DAYS;
+    pub const ModelDisputeVotePeriod: BlockNumber = 10 * MINUTES; // TODO: Test only
+    //pub const ModelDisputeAppealPeriod: BlockNumber = 3 * DAYS;
+    pub const ModelDisputeAppealPeriod: BlockNumber = 10 * MINUTES; // TODO: Test only
 
     pub const TechFundBase: Balance = 1_000_000_00 * DOLLARS;
 
     // per 1000
     pub const RedeemFeeRate: u32 = 3;
+    pub const AppFinancedExposureMax: Balance = 10_000_000 * DOLLARS;
+    pub const AppFinancedOpenRoundsMax: u32 = 100;
 
     pub const CommentRewardNormalRate: u32 = 2;
     pub const CommentRewardExpertRate: u32 = 10;
+
+    // power keeps 95% each period, about one year half-life
+    //pub const PowerDecayPeriod: BlockNumber = 28 * DAYS;
+    pub const PowerDecayPeriod: BlockNumber = 30 * MINUTES; // TODO: Test only
+    pub const PowerDecayRatio: Perbill = Perbill::from_percent(95);
+}
+
+/// BABE epoch randomness is known a whole epoch ahead, the VRF output of the current block
+/// is mixed in, nobody but the block author knows it before the block is built
+pub struct BlockVrfRandomness;
+impl Randomness<Hash> for BlockVrfRandomness {
+    fn random(subject: &[u8]) -> Hash {
+        let block_vrf = Babe::initialized().flatten().unwrap_or_default();
+        <BlakeTwo256 as traits::Hash>::hash_of(&(subject, Babe::randomness(), block_vrf))
+    }
 }
 
 impl kp::Trait for Runtime {
@@ -1187,9 +1231,19 @@ impl kp::Trait for Runtime {
     type KptExchangeMinRate = KptExchangeMinRate;
     type AppLeaderBoardInterval = AppLeaderBoardInterval;
     type AppLeaderBoardMaxPos = AppLeaderBoardMaxPos;
+    type AppLeaderBoardSnapshotsPerBlock = AppLeaderBoardSnapshotsPerBlock;
+    type SubmitBatchMax = SubmitBatchMax;
+    type DocumentCommentPoolMax = DocumentCommentPoolMax;
+    type LeaderBoardReward = LeaderBoardReward;
+    type LeaderBoardRewardClaimPeriod = LeaderBoardRewardClaimPeriod;
+    type LeaderBoardRewardModuleId = LeaderBoardRewardModuleId;
     type Randomness = RandomnessCollectiveFlip;
+    type VrfRandomness = BlockVrfRandomness;
+    type UseVrfRandomness = UseVrfRandomness;
     type TreasuryModuleId = TreasuryModuleId;
     type FinTreasuryModuleId = TreasuryFinModuleId;
+    type AppFinancedExposureMax = AppFinancedExposureMax;
+    type AppFinancedOpenRoundsMax = AppFinancedOpenRoundsMax;
     type ModTreasuryModuleId = TreasuryModModuleId;
     type TechTreasuryModuleId = TreasuryTechModuleId;
     type BurnDestination = ();
@@ -1197,9 +1251,16 @@ impl kp::Trait for Runtime {
     type ModelIncomeCyclePeriod = ModelIncomeCyclePeriod;
     type ModelIncomeCollectingPeriod = ModelIncomeCollectingPeriod;
     type ModelIncomeRewardingPeriod = ModelIncomeRewardingPeriod;
+    type ModelIncomeParamsMax = ModelIncomeParamsMax;
+    type ModelExpertsMax = MaxModelExperts;
     type ModelCycleIncomeRewardTotal = ModelCycleIncomeRewardTotal;
     type ModelDisputeLv1Slash = ModelDisputeLv1Slash;
     type ModelDisputeDelayTime = ModelDisputeDelayTime;
+    type ModelRetireCoolDown = ModelRetireCoolDown;
+    type ModelDisputeBond = ModelDisputeBond;
+    type ModelDisputeEvidencePeriod = ModelDisputeEvidencePeriod;
+    type ModelDisputeVotePeriod = ModelDisputeVotePeriod;
+    type ModelDisputeAppealPeriod = ModelDisputeAppealPeriod;
     type TechMemberOrigin = EnsureRootOrHalfTech;
     type TechFundBase = TechFundBase;
 
@@ -1215,6 +1276,10 @@ impl kp::Trait for Runtime {
 
     type CommentRewardNormalRate = CommentRewardNormalRate;
     type CommentRewardExpertRate = CommentRewardExpertRate;
+    type PowerDecayPeriod = PowerDecayPeriod;
+    type PowerDecayRatio = PowerDecayRatio;
+
+    type WeightInfo = weights::kp::WeightInfo;
 }
 
 construct_runtime!(
@@ -1309,10 +1374,18 @@ impl_runtime_apis! {
             Kp::kp_total_power()
         }
 
+        fn total_raw_power() -> PowerSize {
+            Kp::kp_total_raw_power()
+        }
+
         fn account_power(account: AuthAccountId) -> PowerSize {
             Kp::kp_auth_account_power(account)
         }
 
+        fn account_raw_power(account: AuthAccountId) -> PowerSize {
+            Kp::kp_auth_account_raw_power(account)
+        }
+
         fn commodity_power(app_id: u32, cart_id: Vec<u8>) -> PowerSize {
             Kp::kp_commodity_power(app_id, cart_id)
         }
@@ -1329,6 +1402,14 @@ impl_runtime_apis! {
             Kp::leader_board_result(block, app_id, model_id)
         }
 
+        fn leader_board_seed(block: u32, app_id: u32, model_id: Vec<u8>) -> [u8; 32] {
+            Kp::leader_board_seed(block, app_id, model_id)
+        }
+
+        fn leader_board_history(app_id: u32, model_id: Option<Vec<u8>>, start_block: Option<BlockNumber>, end_block: Option<BlockNumber>, cursor: Option<LeaderBoardHistoryItem<BlockNumber>>, limit: u32) -> LeaderBoardHistoryPage<BlockNumber> {
+            Kp::leader_board_history(app_id, model_id, start_block, end_block, cursor, limit)
+        }
+
         fn stake_to_vote(account: AuthAccountId, stake: Balance) -> Balance {
             Kp::kp_staking_to_vote(&account, stake)
         }
@@ -1341,7 +1422,7 @@ impl_runtime_apis! {
             Kp::app_finance_exchange_accounts(app_id, proposal_id)
         }
 
-        fn app_finance_exchange_data(app_id: u32, proposal_id: Vec<u8>, account: AccountId) -> AppFinancedUserExchangeData<Balance> {
+        fn app_finance_exchange_data(app_id: u32, proposal_id: Vec<u8>, account: AccountId) -> AppFinancedUserExchangeData<Balance, BlockNumber> {
             Kp::app_finance_exchange_data(app_id, proposal_id, account)
         }
 
@@ -1349,10 +1430,18 @@ impl_runtime_apis! {
             Kp::app_income_exchange_accounts(app_id, cycle)
         }
 
-        fn app_income_exchange_data(app_id: u32, cycle: BlockNumber, account: AccountId) -> AppFinancedUserExchangeData<Balance> {
+        fn app_income_exchange_data(app_id: u32, cycle: BlockNumber, account: AccountId) -> AppFinancedUserExchangeData<Balance, BlockNumber> {
             Kp::app_income_exchange_data(app_id, cycle, account)
         }
 
+        fn app_finance_exchange_records(app_id: u32, proposal_id: Vec<u8>, status: ExchangeStatus) -> Vec<(AccountId, AppFinancedUserExchangeData<Balance, BlockNumber>)> {
+            Kp::app_finance_exchange_records(app_id, proposal_id, status)
+        }
+
+        fn app_income_exchange_records(app_id: u32, cycle: BlockNumber, status: ExchangeStatus) -> Vec<(AccountId, AppFinancedUserExchangeData<Balance, BlockNumber>)> {
+            Kp::app_income_exchange_records(app_id, cycle, status)
+        }
+
         fn app_income_record(app_id: u32, cycle: BlockNumber) -> AppIncomeCycleRecord<Balance, BlockNumber> {
             Kp::app_income_record(app_id, cycle)
         }
@@ -1361,6 +1450,10 @@ impl_runtime_apis! {
             Kp::model_income_current_stage()
         }
 
+        fn model_cycle_rewards(app_id: u32, model_id: Vec<u8>, cycle: BlockNumber) -> Vec<ModelCycleIncomeReward<AccountId, Balance>> {
+            Kp::model_cycle_rewards(app_id, model_id, cycle)
+        }
+
         fn is_tech_member_sign(account: AccountId, msg: Vec<u8>, sign: Vec<u8>) -> bool {
             Kp::is_tech_member_sign(account, msg, sign)
         }
@@ -1385,6 +1478,10 @@ impl_runtime_apis! {
             Kp::is_commodity_in_black_list(app_id, cart_id)
         }
 
+        fn leader_board_reward_status(block: u32, app_id: u32, model_id: Vec<u8>, account: AccountId) -> LeaderBoardRewardInfo<Balance, BlockNumber> {
+            Kp::leader_board_reward_info(block, app_id, model_id, account)
+        }
+
         fn power_ratio(account: AccountId) -> u64 {
             Kp::kp_account_power_ratio_by_mini(&account)
         }
@@ -1410,6 +1507,10 @@ impl_runtime_apis! {
         fn model_creator(app_id: u32, model_id: Vec<u8>) -> AccountId {
             Members::model_creator(app_id, model_id)
         }
+
+        fn sign_nonce(account: AccountId) -> u64 {
+            Members::sign_nonce(account)
+        }
     }
 
     impl sp_api::Core<Block> for Runtime {
@@ -1671,6 +1772,8 @@ impl_runtime_apis! {
             add_benchmark!(params, batches, pallet_treasury, Treasury);
             add_benchmark!(params, batches, pallet_utility, Utility);
             add_benchmark!(params, batches, pallet_vesting, Vesting);
+            add_benchmark!(params, batches, kp, Kp);
+            add_benchmark!(params, batches, members, Members);
 
             if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
             Ok(batches)
@@ -32,3 +32,5 @@ pub mod pallet_timestamp;
 pub mod pallet_utility;
 pub mod pallet_vesting;
 pub mod pallet_elections_phragmen;
+pub mod kp;
+pub mod members;